@@ -1,31 +1,102 @@
+use arboard::Clipboard;
 use std::{
+    collections::HashSet,
     fs::{self, Metadata},
+    io,
     mem::ManuallyDrop,
-    path::PathBuf,
-    sync::mpsc::{Receiver, SyncSender},
+    path::{Path, PathBuf},
+    sync::{Arc, Barrier, Mutex, mpsc::SyncSender},
+    time::{Duration, SystemTime},
 };
 use tui::backend::Backend;
 
+#[cfg(not(target_os = "windows"))]
+use crate::os::unix::{get_disk_space, open_in_pager, run_shell_command};
+#[cfg(target_os = "windows")]
+use crate::os::windows::{get_disk_space, open_in_pager, run_shell_command};
 use crate::{
     Event,
-    messages::{Instruction, handle_instructions},
+    messages::{BURST_WINDOW_MS, PriorityReceiver, handle_instructions},
+    output::{
+        debug_log::DebugLog,
+        ipc::{IpcState, PathSize},
+        text_report::{AppReport, LARGEST_FILES_IN_REPORT},
+    },
+    scanner::{ScanFilter, entry_metadata, scan_folder},
+    sftp::{RemoteEntry, SftpConnection},
     state::{
-        FileToDelete, UiEffects,
+        ExternalCommand, FileDetails, FileToDelete, ScanProgress, SessionState, UiEffects,
+        WarningsShown, expand_command,
         files::{FileOrFolder, FileTree, Folder},
-        tiles::Board,
+        layout::LayoutAlgorithm,
+        tiles::{Board, FileType},
     },
-    ui::Display,
+    ui::{Display, HelpModalState, LargestFilesBox, SpinnerStyle, input_widget::TextInput},
 };
 
+// in the box shown with 'L', which scrolls through them
+const LARGEST_FILES_SHOWN: usize = 100;
+
+// no file system we know of takes longer paths (PATH_MAX on linux)
+const JUMP_PATH_MAX_LEN: usize = 4096;
+
+// something we ask about before doing, carried out once the user answers 'y'
+#[derive(Clone)]
+pub enum ConfirmableAction {
+    Exit { app_loaded: bool },
+    DeleteFile(FileToDelete),
+}
+
+// how the app behaves and looks, as set on the command line (or restored from a session)
+#[derive(Default)]
+pub struct AppOptions {
+    pub show_apparent_size: bool,
+    pub disable_delete_confirmation: bool,
+    pub warn_delete_confirmation_disabled: bool, // once scanning is done
+    pub remember_warnings_shown: bool,           // so that they're not shown again next time
+    pub auto_confirm_secs: u64,                  // 0 means the prompt waits for an answer
+    pub read_only: bool,
+    pub clipboard_enabled: bool,
+    pub layout_algorithm: LayoutAlgorithm,
+    pub show_gradient: bool,
+    pub show_mime_category: bool,
+    pub color_by_age: bool,
+    pub show_zero_byte_files: bool,
+    pub show_clock: bool,
+    pub max_zoom: Option<u8>,
+    pub save_session: bool,
+    pub restored_session: Option<SessionState>,
+}
+
 #[derive(Clone)]
 pub enum UiMode {
     Loading,
     Normal,
     ScreenTooSmall,
-    DeleteFile(FileToDelete),
+    Confirming {
+        action: ConfirmableAction,
+    },
     ErrorMessage(String),
-    Exiting { app_loaded: bool },
     WarningMessage(FileToDelete),
+    DeleteConfirmationWarning,
+    ScanTimedOutWarning,
+    MostAccessed(Vec<(PathBuf, u32)>),
+    LargestFiles(Vec<(PathBuf, u128)>),
+    Duplicates(Vec<Vec<PathBuf>>), // largest groups first, see find_duplicates
+    ExtensionFilterInput(TextInput),
+    FileDetails(FileDetails),
+    SearchInput(TextInput),
+    SearchResults {
+        query: String,
+        matches: Vec<PathBuf>, // anywhere in the scanned folder, see FileTree::find_by_name
+    },
+    JumpToPath {
+        input: TextInput,
+        // the folders TAB goes through and which of them is shown, until the input is edited
+        completions: Option<(Vec<PathBuf>, usize)>,
+    },
+    ErrorList,
+    Help,
 }
 
 pub struct App<B>
@@ -41,6 +112,28 @@ where
     event_sender: SyncSender<Event>,
     ui_effects: UiEffects,
     delete_confirmation_disabled: bool,
+    delete_confirmation_warning: bool, // still to be shown once scanning is done
+    remember_warnings_shown: bool,
+    auto_confirm_secs: u64,
+    read_only: bool, // nothing can be deleted
+    deletion_prompt_id: u64,
+    clipboard_enabled: bool,
+    clipboard: Option<Clipboard>,
+    scan_complete_barrier: Option<Arc<Barrier>>,
+    save_session: bool,
+    restored_session: Option<SessionState>,
+    ipc_state: Option<Arc<Mutex<IpcState>>>,
+    scanned_at: SystemTime,
+    scan_duration: Option<Duration>,
+    deletion_log: Vec<(PathBuf, u128)>,
+    remote: Option<Arc<SftpConnection>>,
+    help_modal_state: HelpModalState,
+    largest_files_scroll: usize, // index of the first of the largest files shown
+    current_progress: ScanProgress,
+    top_level_count: Option<usize>, // counted by the scanner first, to estimate how long it takes
+    external_commands: Vec<ExternalCommand>,
+    scan_filter: ScanFilter, // what else is left out when rescanning, besides hidden files
+    debug_log: Option<DebugLog>,
 }
 
 impl<B> App<B>
@@ -51,13 +144,41 @@ where
         terminal_backend: B,
         path_in_filesystem: PathBuf,
         event_sender: SyncSender<Event>,
-        show_apparent_size: bool,
-        disable_delete_confirmation: bool,
+        options: AppOptions,
     ) -> Self {
-        let display = Display::new(terminal_backend);
-        let board = Board::new(&Folder::new(&path_in_filesystem));
+        let AppOptions {
+            show_apparent_size,
+            disable_delete_confirmation,
+            warn_delete_confirmation_disabled,
+            remember_warnings_shown,
+            auto_confirm_secs,
+            read_only,
+            clipboard_enabled,
+            layout_algorithm,
+            show_gradient,
+            show_mime_category,
+            color_by_age,
+            show_zero_byte_files,
+            show_clock,
+            max_zoom,
+            save_session,
+            restored_session,
+        } = options;
+        let mut display = Display::new(terminal_backend);
+        display.show_clock = show_clock;
+        display.read_only = read_only;
+        // once, it's not worth checking again every time something is deleted
+        display.disk_space = get_disk_space(&path_in_filesystem);
+        let mut board = Board::new(&Folder::new(&path_in_filesystem));
+        board.layout_algorithm = layout_algorithm;
+        board.show_gradient = show_gradient;
+        board.show_mime_category = show_mime_category;
+        board.color_by_age = color_by_age;
+        board.show_zero_byte_files = show_zero_byte_files;
+        board.max_zoom = max_zoom;
         let base_folder = Folder::new(&path_in_filesystem);
-        let file_tree = ManuallyDrop::new(FileTree::new(
+        let scan_filter = ScanFilter::new(&path_in_filesystem);
+        let mut file_tree = ManuallyDrop::new(FileTree::new(
             base_folder,
             path_in_filesystem,
             show_apparent_size,
@@ -74,21 +195,115 @@ where
             event_sender,
             ui_effects,
             delete_confirmation_disabled: disable_delete_confirmation,
+            delete_confirmation_warning: warn_delete_confirmation_disabled,
+            remember_warnings_shown,
+            auto_confirm_secs,
+            read_only,
+            deletion_prompt_id: 0,
+            clipboard_enabled,
+            clipboard: None,
+            scan_complete_barrier: None,
+            save_session,
+            restored_session,
+            ipc_state: None,
+            scanned_at: SystemTime::now(),
+            scan_duration: None,
+            deletion_log: vec![],
+            remote: None,
+            help_modal_state: HelpModalState::default(),
+            largest_files_scroll: 0,
+            current_progress: ScanProgress::default(),
+            top_level_count: None,
+            external_commands: vec![],
+            scan_filter,
+            debug_log: None,
+        }
+    }
+    pub fn set_ipc_state(&mut self, ipc_state: Arc<Mutex<IpcState>>) {
+        self.ipc_state = Some(ipc_state);
+    }
+    pub fn path_size(&self, path: &Path) -> Option<PathSize> {
+        // relative paths are relative to the folder we're in
+        let path = self.file_tree.get_current_path().join(path);
+        let size = self.file_tree.size_at_path(&path)?;
+        Some((size, self.file_tree.descendant_count_at_path(&path)))
+    }
+    fn update_ipc_state(&self) {
+        if let Some(ipc_state) = &self.ipc_state {
+            let mut ipc_state = ipc_state
+                .lock()
+                .expect("could not acquire lock on ipc state");
+            ipc_state.current_path = self.file_tree.get_current_path();
+            ipc_state.selected = self
+                .board
+                .tile_at_cursor()
+                .map(|tile| (tile.name.to_string_lossy().into_owned(), tile.size));
+            ipc_state.total_size = self.file_tree.get_total_size();
+            ipc_state.total_descendants = self.file_tree.get_total_descendants();
         }
     }
-    pub fn start(&mut self, receiver: Receiver<Instruction>) {
-        handle_instructions(self, receiver);
+    pub fn set_scan_filter(&mut self, scan_filter: ScanFilter) {
+        // from here on hidden files are toggled with 'H', so the file tree keeps track of it
+        self.file_tree.skip_hidden = scan_filter.skip_hidden;
+        self.scan_filter = scan_filter;
+    }
+    pub fn set_debug_log(&mut self, debug_log: DebugLog) {
+        self.debug_log = Some(debug_log);
+    }
+    pub fn debug_log(&self) -> Option<&DebugLog> {
+        self.debug_log.as_ref()
+    }
+    pub fn set_remote(&mut self, remote: Arc<SftpConnection>) {
+        // deleting and rescanning happen on the remote host from now on
+        self.display.remote = Some((remote.user_and_host(), remote.authenticated_with()));
+        // the local disk has nothing to do with it
+        self.display.disk_space = None;
+        self.remote = Some(remote);
+    }
+    pub fn set_external_commands(&mut self, external_commands: Vec<ExternalCommand>) {
+        // bound to keys in the config file
+        self.external_commands = external_commands;
+    }
+    pub fn set_spinner_style(&mut self, spinner_style: SpinnerStyle) {
+        self.display.spinner_style = spinner_style;
+    }
+    pub fn set_title_suffix(&mut self, title_suffix: String) {
+        // shown on the right of the title line
+        self.display.title_suffix = title_suffix;
+    }
+    pub fn start(&mut self, receiver: PriorityReceiver) {
+        handle_instructions(self, receiver, BURST_WINDOW_MS);
         self.display.clear();
     }
     pub fn render_and_update_board(&mut self) {
         let current_folder = self.file_tree.get_current_folder();
-        self.board.change_files(&current_folder);
+        let current_path = self.file_tree.get_current_path();
+        self.board.change_files(current_folder, &current_path);
         self.render();
     }
-    pub fn increment_loading_progress_indicator(&mut self) {
-        self.ui_effects.increment_loading_progress_indicator();
+    pub fn on_tick(&mut self) {
+        // everything that changes with the passage of time rather than with
+        // a keypress or a scanned entry
+        if !self.loaded {
+            self.ui_effects.increment_loading_progress_indicator();
+            self.ui_effects
+                .sample_scan_rate(self.current_progress.files_scanned);
+            self.ui_effects.scan_eta = self.top_level_count.and_then(|top_level_count| {
+                self.current_progress.estimate_remaining(
+                    self.ui_effects.scan_rate,
+                    self.file_tree.get_top_level_count(),
+                    top_level_count,
+                )
+            });
+        }
+        let effects_expired = self.ui_effects.clear_expired();
+        if !self.loaded || effects_expired {
+            // while loading, there are always new entries to show
+            self.render_and_update_board();
+        }
     }
     pub fn render(&mut self) {
+        self.ui_effects.clear_expired();
         let full_screen_size = self.display.size();
         if full_screen_size.width < 50 || full_screen_size.height < 15 {
             self.ui_mode = UiMode::ScreenTooSmall;
@@ -98,7 +313,13 @@ where
             &mut self.board,
             &self.ui_mode,
             &self.ui_effects,
+            &mut self.help_modal_state,
+            self.largest_files_scroll,
+            &self.current_progress,
         );
+        // this is only shown once, right after we restore
+        self.ui_effects.session_restored = false;
+        self.update_ipc_state();
     }
     pub fn flash_space_freed(&mut self) {
         self.ui_effects.flash_space_freed = true;
@@ -112,19 +333,102 @@ where
     pub fn reset_current_path_color(&mut self) {
         self.ui_effects.current_path_is_red = false;
     }
+    pub fn set_scan_complete_barrier(&mut self, barrier: Arc<Barrier>) {
+        self.scan_complete_barrier = Some(barrier);
+    }
     pub fn start_ui(&mut self) {
-        self.ui_mode = UiMode::Normal;
+        // deleting only becomes possible now, so this is when we warn about it
+        self.ui_mode = if std::mem::take(&mut self.delete_confirmation_warning) {
+            UiMode::DeleteConfirmationWarning
+        } else if self.ui_effects.scan_timed_out {
+            UiMode::ScanTimedOutWarning
+        } else {
+            UiMode::Normal
+        };
         self.loaded = true;
+        self.scan_duration = self.scanned_at.elapsed().ok();
+        if self.file_tree.failed_to_read > 0 {
+            // we might have gotten some of the sizes wrong along the way
+            self.file_tree.recompute_sizes_from_leaves();
+        }
+        self.restore_session_state();
         self.render_and_update_board();
+        if let Some(scan_complete_barrier) = &self.scan_complete_barrier {
+            // lets whoever is waiting on the other side know that we're done scanning
+            // (eg. the tests, so that they don't have to guess how long it takes)
+            scan_complete_barrier.wait();
+        }
+    }
+    fn restore_session_state(&mut self) {
+        // we can only do this once we're done scanning, otherwise the folder
+        // we want to restore to might not be there yet
+        let Some(session_state) = self.restored_session.take() else {
+            return;
+        };
+        self.board.sort_order = session_state.sort_order;
+        if self.file_tree.enter_path(&session_state.current_path) {
+            for _ in &self.file_tree.current_folder_names {
+                // so that going up from here resets the zoom
+                self.board.record_current_index_and_zoom_level();
+            }
+            let files_in_current_folder = self.file_tree.get_current_folder().contents.len();
+            self.board
+                .set_zoom_index(session_state.zoom_level.min(files_in_current_folder));
+            self.ui_effects.session_restored = true;
+        }
+    }
+    pub fn save_session_state(&self) {
+        let session_state = SessionState {
+            root_path: self.file_tree.path_in_filesystem.clone(),
+            current_path: self.file_tree.get_current_path(),
+            zoom_level: self.board.zoom_level,
+            apparent_size: self.file_tree.show_apparent_size(),
+            sort_order: self.board.sort_order,
+        };
+        // not being able to save the session should not keep anyone from quitting
+        let _ = session_state.save();
     }
     pub fn add_entry_to_base_folder(&mut self, file_metadata: &Metadata, entry_path: PathBuf) {
         self.file_tree.add_entry(file_metadata, &entry_path);
-        self.ui_effects.last_read_path = Some(entry_path);
+    }
+    pub fn add_unexpanded_entry_to_base_folder(
+        &mut self,
+        file_metadata: &Metadata,
+        entry_path: PathBuf,
+    ) {
+        self.file_tree
+            .add_unexpanded_entry(file_metadata, &entry_path);
+    }
+    pub fn add_mount_point_to_base_folder(&mut self, entry_path: PathBuf) {
+        self.file_tree.add_mount_point(&entry_path);
+    }
+    pub fn add_remote_entry_to_base_folder(&mut self, entry: RemoteEntry) {
+        self.file_tree
+            .add_remote_entry(&entry.path, entry.is_dir, entry.size, entry.modified);
+    }
+    pub fn update_last_read_path(&mut self, path: PathBuf) {
+        self.ui_effects.last_read_path = Some(path);
+    }
+    pub fn update_current_scan_dir(&mut self, path: PathBuf) {
+        self.ui_effects.current_scan_dir = Some(path);
+    }
+    pub fn scan_timed_out(&mut self) {
+        self.ui_effects.scan_timed_out = true;
+    }
+    pub fn update_progress(&mut self, progress: ScanProgress) {
+        self.current_progress = progress;
+    }
+    pub fn update_top_level_count(&mut self, top_level_count: usize) {
+        self.top_level_count = Some(top_level_count);
     }
     pub fn reset_ui_mode(&mut self) {
         match self.ui_mode {
             UiMode::Loading | UiMode::Normal => {}
             _ => {
+                // the search is only shown along with its results
+                if matches!(self.ui_mode, UiMode::SearchResults { .. }) {
+                    self.board.clear_highlight();
+                }
                 self.ui_mode = {
                     if self.loaded {
                         UiMode::Normal
@@ -135,19 +439,176 @@ where
             }
         };
     }
+    pub fn dismiss_warning_modal(&mut self) {
+        // only once it's been seen does it count as shown
+        if matches!(self.ui_mode, UiMode::DeleteConfirmationWarning) && self.remember_warnings_shown
+        {
+            let mut warnings_shown = WarningsShown::load();
+            warnings_shown.disable_delete_confirmation_warned = true;
+            // if we can't remember this, we'll warn again next time
+            let _ = warnings_shown.save();
+        }
+        self.reset_ui_mode();
+    }
     pub fn show_warning_modal(&mut self) {
         if let Some(file_to_delete) = self.get_file_to_delete() {
             self.ui_mode = UiMode::WarningMessage(file_to_delete);
             self.render();
         }
     }
+    pub fn show_most_accessed(&mut self) {
+        let base_path = &self.file_tree.path_in_filesystem;
+        let most_accessed = self
+            .board
+            .most_accessed(10)
+            .into_iter()
+            .map(|(path, access_count)| {
+                let relative_path = path.strip_prefix(base_path).unwrap_or(&path);
+                (relative_path.to_path_buf(), access_count)
+            })
+            .collect();
+        self.ui_mode = UiMode::MostAccessed(most_accessed);
+        self.render();
+    }
+    pub fn show_largest_files(&mut self) {
+        let base_path = &self.file_tree.path_in_filesystem;
+        let largest_files = self
+            .file_tree
+            .find_largest_files(LARGEST_FILES_SHOWN)
+            .into_iter()
+            .map(|(path, size)| {
+                let relative_path = path.strip_prefix(base_path).unwrap_or(&path);
+                (relative_path.to_path_buf(), size)
+            })
+            .collect();
+        self.largest_files_scroll = 0;
+        self.ui_mode = UiMode::LargestFiles(largest_files);
+        self.render();
+    }
+    pub fn scroll_largest_files_up(&mut self) {
+        self.largest_files_scroll = self.largest_files_scroll.saturating_sub(1);
+        self.render();
+    }
+    pub fn scroll_largest_files_down(&mut self) {
+        if let UiMode::LargestFiles(largest_files) = &self.ui_mode {
+            let max_scroll_offset =
+                LargestFilesBox::max_scroll_offset(largest_files.len(), self.display.size());
+            self.largest_files_scroll = (self.largest_files_scroll + 1).min(max_scroll_offset);
+        }
+        self.render();
+    }
+    pub fn compute_duplicates(&mut self) {
+        if self.remote.is_some() {
+            self.ui_mode = UiMode::ErrorMessage(String::from("Can't read remote files"));
+            self.render();
+            return;
+        }
+        // shown until the duplicates are found
+        self.ui_effects
+            .show_notice("Looking for duplicate files...");
+        self.render();
+        let same_size_files = self.file_tree.duplicate_candidates();
+        let _ = self
+            .event_sender
+            .try_send(Event::DuplicatesRequested(same_size_files));
+    }
+    pub fn show_duplicates(&mut self, duplicates: Vec<Vec<PathBuf>>) {
+        // if something else was opened in the meantime, we don't get in its way
+        if !matches!(self.ui_mode, UiMode::Normal) {
+            return;
+        }
+        let base_path = &self.file_tree.path_in_filesystem;
+        let duplicates = duplicates
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|path| path.strip_prefix(base_path).unwrap_or(&path).to_path_buf())
+                    .collect()
+            })
+            .collect();
+        self.ui_effects.clear_notice();
+        self.ui_mode = UiMode::Duplicates(duplicates);
+        self.render();
+    }
+    pub fn show_file_details(&mut self) {
+        if self.remote.is_some() {
+            self.ui_mode = UiMode::ErrorMessage(String::from("Can't show details of remote files"));
+            self.render();
+            return;
+        }
+        if let Some(path) = self.selected_path() {
+            let relative_path = path
+                .strip_prefix(&self.file_tree.path_in_filesystem)
+                .unwrap_or(&path)
+                .to_path_buf();
+            self.ui_mode = match FileDetails::read(&path, relative_path) {
+                Ok(details) => UiMode::FileDetails(details),
+                Err(err) => UiMode::ErrorMessage(format!("{}", err)),
+            };
+            self.render();
+        }
+    }
+    pub fn show_error_list(&mut self) {
+        // only once there's something to show
+        if self.file_tree.failed_to_read > 0 {
+            self.ui_mode = UiMode::ErrorList;
+            self.render();
+        }
+    }
+    pub fn show_item_count(&mut self) {
+        let current_folder = self.file_tree.get_current_folder();
+        let (files, folders) = current_folder.count_files_and_folders();
+        self.ui_effects.show_info_flash(format!(
+            "Current folder: {} items ({} files, {} subdirectories)",
+            current_folder.num_descendants, files, folders
+        ));
+        let _ = self.event_sender.try_send(Event::InfoFlashShown);
+        self.render();
+    }
+    pub fn show_help(&mut self) {
+        // always start from the top
+        self.help_modal_state = HelpModalState::default();
+        self.ui_mode = UiMode::Help;
+        self.render();
+    }
+    pub fn scroll_help_up(&mut self, lines: usize) {
+        self.help_modal_state.scroll_up(lines);
+        self.render();
+    }
+    pub fn scroll_help_down(&mut self, lines: usize) {
+        self.help_modal_state.scroll_down(lines);
+        self.render();
+    }
     pub fn prompt_exit(&mut self) {
-        self.ui_mode = UiMode::Exiting {
-            app_loaded: self.loaded,
+        self.ui_mode = UiMode::Confirming {
+            action: ConfirmableAction::Exit {
+                app_loaded: self.loaded,
+            },
         };
         self.render();
     }
+    pub fn confirm(&mut self, action: ConfirmableAction) {
+        match action {
+            ConfirmableAction::Exit { .. } => self.exit(),
+            ConfirmableAction::DeleteFile(file_to_delete) => self.delete_file(&file_to_delete),
+        }
+    }
+    pub fn cancel_confirmation(&mut self, action: &ConfirmableAction) {
+        match action {
+            ConfirmableAction::Exit { .. } => {
+                self.reset_ui_mode();
+                // we have to manually call render here to make sure ui gets updated
+                // because reset_ui_mode does not call it itself
+                self.render();
+            }
+            ConfirmableAction::DeleteFile(_) => self.cancel_deletion(),
+        }
+    }
     pub fn exit(&mut self) {
+        if self.save_session {
+            self.save_session_state();
+        }
         self.is_running = false;
         // here we do a blocking send rather than a try_send
         // because we want to make sure that if the receiver
@@ -155,6 +616,26 @@ where
         // would exit cleanly
         let _ = self.event_sender.send(Event::AppExit);
     }
+    pub fn exit_path(&self) -> PathBuf {
+        // the selected folder if there is one, otherwise the folder we're in
+        let current_path = self.file_tree.get_current_path();
+        match self.board.tile_at_cursor() {
+            Some(tile) if tile.file_type == FileType::Folder => current_path.join(&tile.name),
+            _ => current_path,
+        }
+    }
+    pub fn report(&self) -> AppReport {
+        let largest_files = self.file_tree.find_largest_files(LARGEST_FILES_IN_REPORT);
+        AppReport {
+            scanned_at: self.scanned_at,
+            scanned_path: self.file_tree.path_in_filesystem.clone(),
+            total_size: self.file_tree.get_total_size(),
+            total_files: self.file_tree.get_total_descendants(),
+            scan_duration: self.scan_duration,
+            deleted: self.deletion_log.clone(),
+            largest_files,
+        }
+    }
     pub fn handle_enter(&mut self) {
         if !self.board.has_selected_index() {
             self.board.move_to_largest_folder();
@@ -162,36 +643,68 @@ where
         self.enter_selected();
     }
     pub fn move_selected_right(&mut self) {
-        self.board.move_selected_right();
-        self.render();
+        self.move_selected_right_by(1);
     }
     pub fn move_selected_left(&mut self) {
-        self.board.move_selected_left();
-        self.render();
+        self.move_selected_left_by(1);
     }
     pub fn move_selected_down(&mut self) {
-        self.board.move_selected_down();
-        self.render();
+        self.move_selected_down_by(1);
     }
     pub fn move_selected_up(&mut self) {
-        self.board.move_selected_up();
+        self.move_selected_up_by(1);
+    }
+    pub fn select_tile_at(&mut self, column: u16, row: u16) {
+        if self.board.select_tile_at_position(column, row) {
+            self.render();
+        }
+    }
+    // these move a few times but only render once at the end
+    pub fn move_selected_right_by(&mut self, times: usize) {
+        for _ in 0..times {
+            self.board.move_selected_right();
+        }
+        self.render();
+    }
+    pub fn move_selected_left_by(&mut self, times: usize) {
+        for _ in 0..times {
+            self.board.move_selected_left();
+        }
+        self.render();
+    }
+    pub fn move_selected_down_by(&mut self, times: usize) {
+        for _ in 0..times {
+            self.board.move_selected_down();
+        }
+        self.render();
+    }
+    pub fn move_selected_up_by(&mut self, times: usize) {
+        for _ in 0..times {
+            self.board.move_selected_up();
+        }
         self.render();
     }
     pub fn enter_selected(&mut self) {
         self.board.record_current_index_and_zoom_level();
-        if let Some(tile) = &self.board.currently_selected() {
-            let selected_name = &tile.name;
-            if let Some(file_or_folder) = self.file_tree.item_in_current_folder(&selected_name) {
-                match file_or_folder {
-                    FileOrFolder::Folder(_) => {
-                        self.file_tree.enter_folder(&selected_name);
-                        self.board.reset_zoom_index();
-                        self.board.reset_selected_index();
-                        self.render_and_update_board();
+        if let Some(selected_name) = self.board.tile_at_cursor().map(|tile| tile.name.clone())
+            && let Some(file_or_folder) = self.file_tree.item_in_current_folder(&selected_name)
+        {
+            match file_or_folder {
+                FileOrFolder::Folder(_) => {
+                    self.board.count_access_to_selected();
+                    self.file_tree.enter_folder(&selected_name);
+                    self.board.reset_zoom_index();
+                    self.board.reset_selected_index();
+                    self.ui_effects.highlight_path();
+                    self.render_and_update_board();
+                    if self.ui_effects.path_highlight_active {
+                        let _ = self.event_sender.try_send(Event::PathHighlighted(
+                            self.ui_effects.path_highlight_duration,
+                        ));
                     }
-                    FileOrFolder::File(_) => {} // do not enter if currently_selected is a file
                 }
-            };
+                FileOrFolder::File(_) => {} // do not enter if currently_selected is a file
+            }
         }
     }
     pub fn go_up(&mut self) {
@@ -207,8 +720,25 @@ where
             let _ = self.event_sender.try_send(Event::PathError);
         }
     }
+    pub fn go_to_root(&mut self) {
+        if !self.file_tree.navigate_to_root() {
+            let _ = self.event_sender.try_send(Event::PathError);
+            return;
+        }
+        self.board.reset_selected_index();
+        if let Some(index) = self.board.take_root_index() {
+            self.board.set_selected_index(&index);
+        }
+        // everything in the base folder is shown again
+        self.board.reset_zoom_index();
+        self.render_and_update_board();
+    }
     pub fn get_file_to_delete(&self) -> Option<FileToDelete> {
-        let currently_selected = self.board.currently_selected()?;
+        let currently_selected = self.board.tile_at_cursor()?;
+        if currently_selected.zero_byte_files.is_some() {
+            // there's no single file behind this tile
+            return None;
+        }
         let mut path_to_file = self.file_tree.current_folder_names.clone();
         path_to_file.push(currently_selected.name.clone());
         let file_to_delete = FileToDelete {
@@ -221,77 +751,582 @@ where
         Some(file_to_delete)
     }
     pub fn prompt_file_deletion(&mut self) {
+        if self.read_only {
+            self.ui_mode = UiMode::ErrorMessage(String::from("Deletion is disabled (--read-only)"));
+            self.render();
+            return;
+        }
         if let Some(file_to_delete) = self.get_file_to_delete() {
-            self.ui_mode = UiMode::DeleteFile(file_to_delete.clone());
+            self.ui_mode = UiMode::Confirming {
+                action: ConfirmableAction::DeleteFile(file_to_delete.clone()),
+            };
 
             if self.delete_confirmation_disabled {
                 // Here we just delete the file.
                 // As we have set the UI mode above we will get the deletion in progress message box instead of the prompt.
                 self.delete_file(&file_to_delete);
             } else {
+                if self.auto_confirm_secs > 0 {
+                    self.deletion_prompt_id += 1;
+                    self.ui_effects.auto_confirm_countdown = Some(self.auto_confirm_secs);
+                    let _ = self.event_sender.send(Event::DeletionPrompted {
+                        prompt_id: self.deletion_prompt_id,
+                        seconds: self.auto_confirm_secs,
+                    });
+                }
                 // Here we will render which will display the confirmation prompt
                 self.render();
             }
         }
     }
+    pub fn cancel_deletion(&mut self) {
+        self.ui_effects.auto_confirm_countdown = None;
+        self.normal_mode();
+    }
+    fn auto_confirm_pending(&self, prompt_id: u64) -> bool {
+        prompt_id == self.deletion_prompt_id
+            && self.ui_effects.auto_confirm_countdown.is_some()
+            && matches!(
+                self.ui_mode,
+                UiMode::Confirming {
+                    action: ConfirmableAction::DeleteFile(_),
+                    ..
+                }
+            )
+    }
+    pub fn auto_confirm_tick(&mut self, prompt_id: u64) {
+        if self.auto_confirm_pending(prompt_id)
+            && let Some(seconds_left) = self.ui_effects.auto_confirm_countdown
+        {
+            self.ui_effects.auto_confirm_countdown = Some(seconds_left.saturating_sub(1));
+            self.render();
+        }
+    }
+    pub fn auto_confirm_deletion(&mut self, prompt_id: u64) {
+        if self.auto_confirm_pending(prompt_id)
+            && let UiMode::Confirming {
+                action: ConfirmableAction::DeleteFile(file_to_delete),
+                ..
+            } = &self.ui_mode
+        {
+            let file_to_delete = file_to_delete.clone();
+            self.delete_file(&file_to_delete);
+        }
+    }
     pub fn normal_mode(&mut self) {
         self.ui_mode = UiMode::Normal;
         self.render_and_update_board();
     }
     pub fn delete_file(&mut self, file_to_delete: &FileToDelete) {
+        self.ui_effects.auto_confirm_countdown = None;
         self.ui_effects.deletion_in_progress = true;
         self.render();
         self.ui_effects.deletion_in_progress = false;
 
         let full_path = file_to_delete.full_path();
-        match fs::metadata(&full_path) {
-            Ok(metadata) => {
-                let file_type = metadata.file_type();
-                let file_removed = if file_type.is_dir() {
-                    fs::remove_dir_all(&full_path)
-                } else {
-                    fs::remove_file(&full_path)
-                };
-                match file_removed {
-                    Ok(_) => {
-                        self.remove_file_from_ui(file_to_delete);
-                        self.ui_mode = UiMode::Normal;
-                        self.render_and_update_board();
-                        let _ = self.event_sender.try_send(Event::FileDeleted);
-                    }
-                    Err(msg) => {
-                        self.ui_mode = UiMode::ErrorMessage(format!("{}", msg));
-                        self.render();
-                    }
-                };
+        let file_removed = match &self.remote {
+            Some(remote) => remote.remove(&full_path).map_err(|msg| format!("{}", msg)),
+            None => remove_local_path(&full_path).map_err(|msg| format!("{}", msg)),
+        };
+        match file_removed {
+            Ok(_) => {
+                self.deletion_log.push((full_path, file_to_delete.size));
+                self.remove_file_from_ui(file_to_delete);
+                self.ui_mode = UiMode::Normal;
+                self.render_and_update_board();
+                let _ = self.event_sender.try_send(Event::FileDeleted);
+            }
+            Err(msg) => {
+                self.ui_mode = UiMode::ErrorMessage(msg);
+                self.render();
+            }
+        }
+    }
+    pub fn selected_file_path(&self) -> Option<PathBuf> {
+        let tile = self.board.tile_at_cursor()?;
+        if tile.file_type != FileType::File || tile.zero_byte_files.is_some() {
+            return None;
+        }
+        self.file_tree.find_path_of_tile(tile)
+    }
+    pub fn open_in_pager(&mut self, path: &Path) {
+        if self.remote.is_some() {
+            self.ui_mode = UiMode::ErrorMessage(String::from("Can't open remote files"));
+            self.render();
+            return;
+        }
+        self.display.clear();
+        let opened = open_in_pager(path);
+        self.display.resume();
+        if let Err(err) = opened {
+            self.ui_mode = UiMode::ErrorMessage(format!("{}", err));
+        }
+        self.render();
+    }
+    pub fn external_command_for(&self, key: char) -> Option<&ExternalCommand> {
+        self.external_commands
+            .iter()
+            .find(|external_command| external_command.key == key)
+    }
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        let tile = self.board.tile_at_cursor()?;
+        self.file_tree.find_path_of_tile(tile)
+    }
+    pub fn spawn_external_command(&mut self, template: &str, path: &Path, interactive: bool) {
+        if self.remote.is_some() {
+            self.ui_mode = UiMode::ErrorMessage(String::from("Can't run commands on remote files"));
+            self.render();
+            return;
+        }
+        let command = expand_command(template, path);
+        if interactive {
+            self.display.clear();
+        }
+        let exited = run_shell_command(&command, interactive);
+        if interactive {
+            self.display.resume();
+        }
+        match exited {
+            Ok(status) if !status.success() => {
+                self.ui_mode = UiMode::ErrorMessage(format!("{} failed ({})", command, status));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                self.ui_mode = UiMode::ErrorMessage(format!("{}", err));
+            }
+        }
+        self.render();
+    }
+    pub fn selected_name(&self) -> Option<String> {
+        let tile = self.board.tile_at_cursor()?;
+        Some(tile.name.to_string_lossy().into_owned())
+    }
+    pub fn copy_to_clipboard(&mut self, text: String) {
+        if !self.clipboard_enabled {
+            // rather than leaving 'y' looking like it's broken
+            self.ui_effects
+                .show_notice("Start diskonaut with --clipboard to copy paths");
+            let _ = self.event_sender.try_send(Event::NoticeShown);
+            self.render();
+            return;
+        }
+        // we hold on to the clipboard once it's created, because on some
+        // platforms its contents are lost as soon as it is dropped
+        let copied = match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text.clone()),
+            None => Clipboard::new().and_then(|mut clipboard| {
+                clipboard.set_text(text.clone())?;
+                self.clipboard = Some(clipboard);
+                Ok(())
+            }),
+        };
+        match copied {
+            Ok(_) => {
+                self.ui_effects.show_copied_to_clipboard(text);
+                self.render();
+                let _ = self.event_sender.try_send(Event::CopiedToClipboard);
             }
             Err(msg) => {
-                self.ui_mode = UiMode::ErrorMessage(format!("{}", msg));
+                self.ui_mode = UiMode::ErrorMessage(format!("Failed to access clipboard: {}", msg));
                 self.render();
             }
         }
     }
-    pub fn increment_failed_to_read(&mut self) {
-        self.file_tree.failed_to_read += 1;
+    pub fn increment_failed_to_read(&mut self, failed_path: Option<(PathBuf, String)>) {
+        self.file_tree.add_failed_to_read(failed_path);
     }
     pub fn zoom_in(&mut self) {
+        self.change_zoom(1);
+    }
+    pub fn zoom_out(&mut self) {
+        self.change_zoom(-1);
+    }
+    fn change_zoom(&mut self, delta: i8) {
         let current_folder = self.file_tree.get_current_folder();
-        self.board.zoom_in(current_folder);
+        if !self.board.change_zoom(current_folder, delta) {
+            // so that pressing the key again and again doesn't seem to do nothing
+            let notice = if delta > 0 {
+                "Already at maximum zoom"
+            } else {
+                "Already at minimum zoom"
+            };
+            self.ui_effects.show_notice(notice);
+            let _ = self.event_sender.try_send(Event::NoticeShown);
+        }
         self.render();
     }
-    pub fn zoom_out(&mut self) {
+    pub fn toggle_scroll_mode(&mut self) {
+        self.board.toggle_scroll_mode();
+        // nothing might look different until moving around, so we say what happened
+        let notice = if self.board.scroll_mode {
+            "Scrolling through all tiles"
+        } else {
+            "Zooming to see smaller tiles"
+        };
+        self.ui_effects.show_notice(notice);
+        let _ = self.event_sender.try_send(Event::NoticeShown);
+        self.render();
+    }
+    pub fn cycle_layout_algorithm(&mut self) {
+        self.board.cycle_layout_algorithm();
+        self.render();
+    }
+    pub fn cycle_sort_order(&mut self) {
+        self.board.cycle_sort_order();
+        self.render();
+    }
+    pub fn toggle_zero_byte_files(&mut self) {
+        let current_folder = self.file_tree.get_current_folder();
+        self.board.toggle_zero_byte_files(current_folder);
+        self.render();
+    }
+    pub fn toggle_skip_hidden(&mut self) {
+        self.file_tree.skip_hidden = !self.file_tree.skip_hidden;
+        self.rescan_current_folder();
+    }
+    pub fn show_extension_filter_input(&mut self) {
+        // starting from the filter in place, if any
+        let mut input = TextInput::new();
+        if let Some(extension) = self.file_tree.extension_filter() {
+            input.set_text(&format!(".{}", extension));
+        }
+        self.ui_mode = UiMode::ExtensionFilterInput(input);
+        self.render();
+    }
+    pub fn edit_extension_filter(&mut self, edit: impl FnOnce(&mut TextInput)) {
+        if let UiMode::ExtensionFilterInput(input) = &mut self.ui_mode {
+            edit(input);
+        }
+        self.render();
+    }
+    pub fn confirm_extension_filter(&mut self) {
+        let UiMode::ExtensionFilterInput(input) = &self.ui_mode else {
+            return;
+        };
+        let input = input.as_str().to_owned();
+        // an empty filter is no filter
+        self.file_tree.filter_by_extension(&input);
+        if let Some(extension) = self.file_tree.extension_filter()
+            && self.file_tree.paths_shown_by_filter().is_empty()
+        {
+            // rather than leaving nothing at all on screen
+            let notice = format!("No .{} files were found", extension);
+            self.file_tree.clear_filter();
+            self.ui_effects.show_notice(&notice);
+            let _ = self.event_sender.try_send(Event::NoticeShown);
+        }
+        self.update_extension_filter();
+        self.ui_mode = UiMode::Normal;
+        self.render();
+    }
+    pub fn clear_extension_filter(&mut self) {
+        self.file_tree.clear_filter();
+        self.update_extension_filter();
+        self.ui_mode = UiMode::Normal;
+        self.render();
+    }
+    fn update_extension_filter(&mut self) {
+        // the board hides what the file tree filters out, until it's told otherwise
         let current_folder = self.file_tree.get_current_folder();
-        self.board.zoom_out(current_folder);
+        if self.file_tree.is_filtered() {
+            let shown = self.file_tree.paths_shown_by_filter();
+            self.board
+                .apply_filter(current_folder, move |path, _| shown.contains(path));
+        } else {
+            self.board.clear_filter(current_folder);
+        }
+    }
+    pub fn show_search_input(&mut self) {
+        self.ui_mode = UiMode::SearchInput(TextInput::new());
         self.render();
     }
+    pub fn edit_search(&mut self, edit: impl FnOnce(&mut TextInput)) {
+        if let UiMode::SearchInput(input) = &mut self.ui_mode {
+            edit(input);
+        }
+        self.render();
+    }
+    pub fn confirm_search(&mut self) {
+        let UiMode::SearchInput(query) = &self.ui_mode else {
+            return;
+        };
+        let query = query.as_str().to_owned();
+        if query.is_empty() {
+            self.clear_search();
+            return;
+        }
+        let matches = self.file_tree.find_by_name(&query);
+        if matches.is_empty() {
+            let notice = format!("Nothing named like '{}' was found", query);
+            self.ui_effects.show_notice(&notice);
+            let _ = self.event_sender.try_send(Event::NoticeShown);
+            self.clear_search();
+            return;
+        }
+        // tiles with matches inside them are highlighted too, so that they can be found
+        let mut highlighted: HashSet<PathBuf> = HashSet::new();
+        for path in &matches {
+            highlighted.insert(path.clone());
+            highlighted.extend(self.file_tree.ancestors_of(path));
+        }
+        self.board
+            .highlight_matching(move |path, _| highlighted.contains(path));
+        self.board.reset_selected_index();
+        self.ui_mode = UiMode::SearchResults { query, matches };
+        self.next_search_match();
+    }
+    pub fn clear_search(&mut self) {
+        self.board.clear_highlight();
+        self.ui_mode = UiMode::Normal;
+        self.render();
+    }
+    pub fn next_search_match(&mut self) {
+        self.select_search_match(true);
+    }
+    pub fn previous_search_match(&mut self) {
+        self.select_search_match(false);
+    }
+    fn select_search_match(&mut self, forward: bool) {
+        // the highlighted tiles of the current folder, in the order they're laid out
+        let mut indices: Vec<usize> = self
+            .board
+            .matching_indices()
+            .map(|indices| indices.iter().copied().collect())
+            .unwrap_or_default();
+        indices.sort_unstable();
+        let next_index = match (self.board.selected_index, forward) {
+            (Some(selected), true) => indices
+                .iter()
+                .find(|index| **index > selected)
+                .or(indices.first()),
+            (Some(selected), false) => indices
+                .iter()
+                .rev()
+                .find(|index| **index < selected)
+                .or(indices.last()),
+            (None, true) => indices.first(),
+            (None, false) => indices.last(),
+        };
+        if let Some(name) = next_index.map(|index| self.board.tiles[*index].name.clone()) {
+            self.board.select_by_name(&name);
+        }
+        self.render();
+    }
+    pub fn show_jump_to_path(&mut self) {
+        // starting from where we are
+        let mut input = TextInput::with_max_len(JUMP_PATH_MAX_LEN);
+        input.set_text(&self.file_tree.get_current_path().to_string_lossy());
+        self.ui_mode = UiMode::JumpToPath {
+            input,
+            completions: None,
+        };
+        self.render();
+    }
+    pub fn edit_jump_path(&mut self, edit: impl FnOnce(&mut TextInput)) {
+        if let UiMode::JumpToPath { input, completions } = &mut self.ui_mode {
+            edit(input);
+            // the next TAB completes whatever is typed now
+            *completions = None;
+        }
+        self.render();
+    }
+    pub fn complete_jump_path(&mut self) {
+        let UiMode::JumpToPath { input, completions } = &mut self.ui_mode else {
+            return;
+        };
+        let (candidates, index) = match completions {
+            Some((candidates, index)) => {
+                *index = (*index + 1) % candidates.len().max(1);
+                (candidates, *index)
+            }
+            None => {
+                // the folders in the typed parent whose name starts with what's typed after it
+                let typed = PathBuf::from(input.as_str());
+                let (parent, prefix) = if input.as_str().ends_with(std::path::MAIN_SEPARATOR) {
+                    (typed.as_path(), String::new())
+                } else {
+                    (
+                        typed.parent().unwrap_or(&typed),
+                        typed
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                    )
+                };
+                let candidates = self
+                    .file_tree
+                    .subfolders_at_path(parent)
+                    .into_iter()
+                    .filter(|path| {
+                        path.file_name()
+                            .is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+                    })
+                    .collect();
+                let (candidates, index) = completions.insert((candidates, 0));
+                (candidates, *index)
+            }
+        };
+        if let Some(candidate) = candidates.get(index) {
+            input.set_text(&candidate.to_string_lossy());
+        }
+        self.render();
+    }
+    pub fn jump_to_path(&mut self, path: &Path) {
+        let base_path = self.file_tree.path_in_filesystem.clone();
+        let path = self.file_tree.get_current_path().join(path); // unless it's absolute
+        if !path.starts_with(&base_path) {
+            self.ui_mode = UiMode::ErrorMessage(format!(
+                "Only paths inside the scanned folder ({}) can be shown",
+                base_path.display()
+            ));
+            self.render();
+            return;
+        }
+        if self.file_tree.size_at_path(&path).is_none() {
+            self.ui_mode =
+                UiMode::ErrorMessage(format!("Nothing was scanned at {}", path.display()));
+            self.render();
+            return;
+        }
+        // a file is shown selected in the folder it's in
+        let (folder_path, file_name) = match self.file_tree.folder_at_path(&path) {
+            Some(_) => (path.as_path(), None),
+            None => (path.parent().unwrap_or(&base_path), path.file_name()),
+        };
+        if !self.file_tree.enter_path(folder_path) {
+            return;
+        }
+        // going up from here goes through every folder in between, zoomed out
+        self.board.previous_indices_and_zoom_level.clear();
+        self.board.reset_selected_index();
+        self.board.reset_zoom_index();
+        for _ in &self.file_tree.current_folder_names {
+            self.board.record_current_index_and_zoom_level();
+        }
+        self.ui_mode = UiMode::Normal;
+        self.ui_effects.highlight_path();
+        let current_folder = self.file_tree.get_current_folder();
+        let current_path = self.file_tree.get_current_path();
+        self.board.change_files(current_folder, &current_path);
+        if let Some(file_name) = file_name {
+            self.board.select_by_name(file_name);
+        }
+        self.render();
+        if self.ui_effects.path_highlight_active {
+            let _ = self.event_sender.try_send(Event::PathHighlighted(
+                self.ui_effects.path_highlight_duration,
+            ));
+        }
+    }
+    pub fn refresh_current_folder(&mut self) {
+        // scanning happens right here, so this is shown until it's done
+        self.ui_effects.show_notice("Refreshing this folder...");
+        self.render();
+        self.rescan_current_folder();
+        self.ui_effects.show_notice("Refreshed this folder");
+        let _ = self.event_sender.try_send(Event::NoticeShown);
+        self.render();
+    }
+    fn rescan_current_folder(&mut self) {
+        let current_path = self.file_tree.get_current_path();
+        let subtree = self.scan_subtree(&current_path);
+        self.file_tree.replace_subtree(subtree);
+        if self.file_tree.is_filtered() {
+            // files with the extension might have come or gone
+            self.update_extension_filter();
+        }
+        self.board.reset_selected_index();
+        self.render_and_update_board();
+    }
+    fn scan_subtree(&self, current_path: &Path) -> FileTree {
+        // what's in the folder now, to take the place of what we have for it
+        let mut subtree = FileTree::new(
+            Folder::new(&current_path.to_path_buf()),
+            current_path.to_path_buf(),
+            self.file_tree.show_apparent_size(),
+        );
+        if let Some(remote) = &self.remote {
+            remote.walk(current_path, self.file_tree.skip_hidden, |entry| {
+                match entry {
+                    Ok(entry) => subtree.add_remote_entry(
+                        &entry.path,
+                        entry.is_dir,
+                        entry.size,
+                        entry.modified,
+                    ),
+                    // sftp errors don't say which path they're about
+                    Err(_) => subtree.add_failed_to_read(None),
+                };
+                true
+            });
+        } else {
+            // the first entry is the current folder itself, which we keep
+            // --depth counts from the scanned folder, not from the one we're in
+            let current_depth = self.file_tree.depth_of(current_path).unwrap_or(0);
+            let max_depth = self
+                .scan_filter
+                .max_depth
+                .map(|max_depth| max_depth.saturating_sub(current_depth));
+            let scan_filter = self
+                .scan_filter
+                .clone()
+                .skip_hidden(self.file_tree.skip_hidden)
+                .max_depth(max_depth);
+            for entry in scan_folder(current_path, scan_filter.clone()).skip(1) {
+                let past_max_depth = entry
+                    .as_ref()
+                    .is_ok_and(|entry| scan_filter.is_past_max_depth(entry.depth));
+                match entry.map(|entry| (entry_metadata(&entry), entry.path())) {
+                    Ok((Ok(file_metadata), entry_path)) if past_max_depth => {
+                        subtree.add_unexpanded_entry(&file_metadata, &entry_path);
+                    }
+                    Ok((Ok(file_metadata), entry_path))
+                        if scan_filter.is_mount_point(&file_metadata) =>
+                    {
+                        subtree.add_mount_point(&entry_path);
+                    }
+                    Ok((Ok(file_metadata), entry_path)) => {
+                        subtree.add_entry(&file_metadata, &entry_path);
+                    }
+                    Ok((Err(err), entry_path)) => {
+                        if let Some(debug_log) = &self.debug_log {
+                            debug_log.log(format_args!(
+                                "failed to read {} while rescanning: {}",
+                                entry_path.to_string_lossy(),
+                                err
+                            ));
+                        }
+                        subtree.add_failed_to_read(Some((entry_path, err.to_string())));
+                    }
+                    Err(err) => {
+                        if let Some(debug_log) = &self.debug_log {
+                            debug_log.log(format_args!("walk error while rescanning: {}", err));
+                        }
+                        let failed_path =
+                            err.path().map(|path| (path.to_path_buf(), err.to_string()));
+                        subtree.add_failed_to_read(failed_path);
+                    }
+                }
+            }
+        }
+        subtree
+    }
     pub fn reset_zoom(&mut self) {
         let current_folder = self.file_tree.get_current_folder();
         self.board.reset_zoom(current_folder);
         self.render();
     }
     fn remove_file_from_ui(&mut self, file_to_delete: &FileToDelete) {
-        self.file_tree.space_freed += file_to_delete.size;
+        self.file_tree.add_space_freed(file_to_delete.size);
         self.file_tree.delete_file(file_to_delete);
         self.board.reset_selected_index();
     }
 }
+
+fn remove_local_path(path: &Path) -> io::Result<()> {
+    if fs::metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
@@ -1,7 +1,14 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, read};
+use std::{collections::VecDeque, path::PathBuf};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, read};
 use tui::backend::Backend;
 
-use crate::{App, state::FileToDelete};
+use crate::{
+    App, UiMode,
+    app::ConfirmableAction,
+    messages::Instruction,
+    ui::{HELP_PAGE_SIZE, input_widget::TextInput},
+};
 
 #[derive(Clone)]
 pub struct TerminalEvents;
@@ -39,7 +46,12 @@ macro_rules! key {
     };
 }
 
-pub fn handle_keypress_loading_mode<B: Backend>(evt: Event, app: &mut App<B>) {
+// instructions pushed to the front of the burst are handled right after this keypress
+pub fn handle_keypress_loading_mode<B: Backend>(
+    evt: Event,
+    app: &mut App<B>,
+    burst: &mut VecDeque<Instruction>,
+) {
     match evt {
         key!(ctrl 'c') | key!(char 'q') => {
             app.prompt_exit();
@@ -56,30 +68,94 @@ pub fn handle_keypress_loading_mode<B: Backend>(evt: Event, app: &mut App<B>) {
         key!(char 'k') | key!(Up) | key!(ctrl 'p') => {
             app.move_selected_up();
         }
-        key!(char '+') | key!(shift '+') => {
+        Event::Mouse(MouseEvent::Down(MouseButton::Left, column, row, _)) => {
+            app.select_tile_at(column, row);
+        }
+        key!(char '+') | key!(shift '+') | Event::Mouse(MouseEvent::ScrollUp(..)) => {
             app.zoom_in();
         }
-        key!(char '-') => {
+        key!(char '-') | Event::Mouse(MouseEvent::ScrollDown(..)) => {
             app.zoom_out();
         }
         key!(char '0') => {
             app.reset_zoom();
         }
+        key!(char '~')
+        | key!(shift '~')
+        | Event::Key(KeyEvent {
+            code: KeyCode::Home,
+            modifiers: KeyModifiers::CONTROL,
+        }) => {
+            burst.push_front(Instruction::NavigateRoot);
+        }
+        key!(char 'A') | key!(shift 'A') => {
+            app.cycle_layout_algorithm();
+        }
+        key!(char 'o') => {
+            app.cycle_sort_order();
+        }
+        key!(char 's') => {
+            app.toggle_scroll_mode();
+        }
         key!(char '\n') | key!(Enter) => {
             app.handle_enter();
         }
+        key!(char 'M') | key!(shift 'M') => {
+            app.show_most_accessed();
+        }
+        key!(char '?') | key!(shift '?') => {
+            app.show_help();
+        }
+        key!(char 'Z') | key!(shift 'Z') => {
+            app.toggle_zero_byte_files();
+        }
+        key!(char 'y') => {
+            if let Some(selected_path) = app.selected_path() {
+                burst.push_front(Instruction::CopyToClipboard(
+                    selected_path.to_string_lossy().into_owned(),
+                ));
+            }
+        }
+        key!(char 'Y') | key!(shift 'Y') => {
+            if let Some(selected_name) = app.selected_name() {
+                burst.push_front(Instruction::CopyToClipboard(selected_name));
+            }
+        }
         key!(Backspace) => {
             app.show_warning_modal();
         }
-        key!(Esc) => {
-            app.go_up();
-        }
         _ => (),
     };
 }
 
-pub fn handle_keypress_normal_mode<B: Backend>(evt: Event, app: &mut App<B>) {
+pub fn handle_keypress_normal_mode<B: Backend>(
+    evt: Event,
+    app: &mut App<B>,
+    burst: &mut VecDeque<Instruction>,
+) {
     match evt {
+        key!(char '~')
+        | key!(shift '~')
+        | Event::Key(KeyEvent {
+            code: KeyCode::Home,
+            modifiers: KeyModifiers::CONTROL,
+        }) => {
+            burst.push_front(Instruction::NavigateRoot);
+        }
+        // keys bound in the config file take precedence over our own
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(key),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) if app.external_command_for(key).is_some() => {
+            let external_command = app.external_command_for(key).cloned();
+            if let (Some(external_command), Some(path)) = (external_command, app.selected_path()) {
+                burst.push_front(Instruction::RunExternalCommand(
+                    external_command.template,
+                    path,
+                    external_command.interactive,
+                ));
+            }
+        }
         key!(ctrl 'c') | key!(char 'q') => {
             app.prompt_exit();
         }
@@ -98,36 +174,102 @@ pub fn handle_keypress_normal_mode<B: Backend>(evt: Event, app: &mut App<B>) {
         key!(char 'k') | key!(Up) | key!(ctrl 'p') => {
             app.move_selected_up();
         }
-        key!(char '+') | key!(shift '+') => {
+        Event::Mouse(MouseEvent::Down(MouseButton::Left, column, row, _)) => {
+            app.select_tile_at(column, row);
+        }
+        key!(char '+') | key!(shift '+') | Event::Mouse(MouseEvent::ScrollUp(..)) => {
             app.zoom_in();
         }
-        key!(char '-') => {
+        key!(char '-') | Event::Mouse(MouseEvent::ScrollDown(..)) => {
             app.zoom_out();
         }
         key!(char '0') => {
             app.reset_zoom();
         }
+        key!(char 'A') | key!(shift 'A') => {
+            app.cycle_layout_algorithm();
+        }
+        key!(char 'o') => {
+            app.cycle_sort_order();
+        }
+        key!(char 's') => {
+            app.toggle_scroll_mode();
+        }
         key!(char '\n') | key!(Enter) => {
             app.handle_enter();
         }
-        key!(Esc) => {
-            app.go_up();
+        key!(char 'M') | key!(shift 'M') => {
+            app.show_most_accessed();
+        }
+        key!(char 'L') | key!(shift 'L') => {
+            app.show_largest_files();
+        }
+        key!(char '?') | key!(shift '?') => {
+            app.show_help();
+        }
+        key!(char 'Z') | key!(shift 'Z') => {
+            app.toggle_zero_byte_files();
+        }
+        key!(char 'H') | key!(shift 'H') => {
+            app.toggle_skip_hidden();
+        }
+        key!(char 'E') | key!(shift 'E') => {
+            app.show_error_list();
+        }
+        key!(char 'D') | key!(shift 'D') => {
+            burst.push_front(Instruction::ComputeDuplicates);
+        }
+        key!(char 'r') => {
+            burst.push_front(Instruction::RefreshCurrentFolder);
+        }
+        key!(char 'v') => {
+            // only files can be viewed, anything else is ignored
+            if let Some(path) = app.selected_file_path() {
+                burst.push_front(Instruction::OpenInPager(path));
+            }
+        }
+        key!(char 'c') => {
+            app.show_item_count();
+        }
+        key!(char 'i') => {
+            app.show_file_details();
+        }
+        key!(char 'f') => {
+            app.show_extension_filter_input();
+        }
+        key!(char '/') => {
+            app.show_search_input();
+        }
+        key!(char 'g') => {
+            app.show_jump_to_path();
+        }
+        key!(char 'y') => {
+            if let Some(selected_path) = app.selected_path() {
+                burst.push_front(Instruction::CopyToClipboard(
+                    selected_path.to_string_lossy().into_owned(),
+                ));
+            }
+        }
+        key!(char 'Y') | key!(shift 'Y') => {
+            if let Some(selected_name) = app.selected_name() {
+                burst.push_front(Instruction::CopyToClipboard(selected_name));
+            }
         }
         _ => (),
     };
 }
 
-pub fn handle_keypress_delete_file_mode<B: Backend>(
+pub fn handle_keypress_confirming_mode<B: Backend>(
     evt: Event,
     app: &mut App<B>,
-    file_to_delete: FileToDelete,
+    action: ConfirmableAction,
 ) {
     match evt {
         key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'n') => {
-            app.normal_mode();
+            app.cancel_confirmation(&action);
         }
         key!(char 'y') => {
-            app.delete_file(&file_to_delete);
+            app.confirm(action);
         }
         _ => (),
     };
@@ -142,24 +284,237 @@ pub fn handle_keypress_error_message<B: Backend>(evt: Event, app: &mut App<B>) {
     };
 }
 
-pub fn handle_keypress_screen_too_small<B: Backend>(evt: Event, app: &mut App<B>) {
+pub fn handle_keypress_most_accessed<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'M') | key!(shift 'M') => {
+            app.reset_ui_mode();
+            app.render();
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_largest_files<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'L') | key!(shift 'L') => {
+            app.reset_ui_mode();
+            app.render();
+        }
+        key!(char 'k') | key!(Up) => {
+            app.scroll_largest_files_up();
+        }
+        key!(char 'j') | key!(Down) => {
+            app.scroll_largest_files_down();
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_duplicates<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'D') | key!(shift 'D') => {
+            app.reset_ui_mode();
+            app.render();
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_file_details<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'i') => {
+            app.reset_ui_mode();
+            app.render();
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_extension_filter_input<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(Esc) => {
+            app.clear_extension_filter();
+        }
+        key!(ctrl 'c') => {
+            // leaving whatever filter was there before as it was
+            app.reset_ui_mode();
+            app.render();
+        }
+        key!(char '\n') | key!(Enter) => {
+            app.confirm_extension_filter();
+        }
+        key!(Backspace) => {
+            app.edit_extension_filter(TextInput::backspace);
+        }
+        key!(Delete) => {
+            app.edit_extension_filter(TextInput::delete);
+        }
+        key!(Left) => {
+            app.edit_extension_filter(TextInput::move_cursor_left);
+        }
+        key!(Right) => {
+            app.edit_extension_filter(TextInput::move_cursor_right);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) => {
+            app.edit_extension_filter(|input| input.push_char(character));
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_search_input<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(Esc) | key!(ctrl 'c') => {
+            app.clear_search();
+        }
+        key!(char '\n') | key!(Enter) => {
+            app.confirm_search();
+        }
+        key!(Backspace) => {
+            app.edit_search(TextInput::backspace);
+        }
+        key!(Delete) => {
+            app.edit_search(TextInput::delete);
+        }
+        key!(Left) => {
+            app.edit_search(TextInput::move_cursor_left);
+        }
+        key!(Right) => {
+            app.edit_search(TextInput::move_cursor_right);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) => {
+            app.edit_search(|input| input.push_char(character));
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_search_results<B: Backend>(evt: Event, app: &mut App<B>) {
+    // moving around is fine, the matches stay highlighted in every folder
     match evt {
         key!(ctrl 'c') | key!(char 'q') => {
-            app.exit();
+            app.prompt_exit();
+        }
+        key!(Esc) => {
+            app.clear_search();
+        }
+        key!(char 'n') => {
+            app.next_search_match();
+        }
+        key!(char 'N') | key!(shift 'N') => {
+            app.previous_search_match();
+        }
+        key!(char '/') => {
+            app.show_search_input();
+        }
+        key!(char 'l') | key!(Right) | key!(ctrl 'f') => {
+            app.move_selected_right();
+        }
+        key!(char 'h') | key!(Left) | key!(ctrl 'b') => {
+            app.move_selected_left();
+        }
+        key!(char 'j') | key!(Down) | key!(ctrl 'n') => {
+            app.move_selected_down();
+        }
+        key!(char 'k') | key!(Up) | key!(ctrl 'p') => {
+            app.move_selected_up();
+        }
+        Event::Mouse(MouseEvent::Down(MouseButton::Left, column, row, _)) => {
+            app.select_tile_at(column, row);
+        }
+        key!(char '\n') | key!(Enter) => {
+            app.handle_enter();
         }
         _ => (),
     };
 }
 
-pub fn handle_keypress_exiting_mode<B: Backend>(evt: Event, app: &mut App<B>) {
+pub fn handle_keypress_jump_to_path<B: Backend>(
+    evt: Event,
+    app: &mut App<B>,
+    burst: &mut VecDeque<Instruction>,
+) {
     match evt {
-        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'n') => {
+        key!(Esc) | key!(ctrl 'c') => {
             app.reset_ui_mode();
-            // we have to manually call render here to make sure ui gets updated
-            // because reset_ui_mode does not call it itself
             app.render();
         }
-        key!(char 'y') => {
+        key!(char '\n') | key!(Enter) => {
+            if let UiMode::JumpToPath { input, .. } = &app.ui_mode {
+                burst.push_front(Instruction::JumpToPath(PathBuf::from(input.as_str())));
+            }
+        }
+        key!(Tab) => {
+            app.complete_jump_path();
+        }
+        key!(Backspace) => {
+            app.edit_jump_path(TextInput::backspace);
+        }
+        key!(Delete) => {
+            app.edit_jump_path(TextInput::delete);
+        }
+        key!(Left) => {
+            app.edit_jump_path(TextInput::move_cursor_left);
+        }
+        key!(Right) => {
+            app.edit_jump_path(TextInput::move_cursor_right);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(character),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        }) => {
+            app.edit_jump_path(|input| input.push_char(character));
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_error_list<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char 'E') | key!(shift 'E') => {
+            app.reset_ui_mode();
+            app.render();
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_help<B: Backend>(
+    evt: Event,
+    app: &mut App<B>,
+    burst: &mut VecDeque<Instruction>,
+) {
+    // the arrow keys might have been coalesced, they arrive as Instruction::NavigateUp/Down
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') | key!(Esc) | key!(char '?') | key!(shift '?') => {
+            app.reset_ui_mode();
+            app.render();
+        }
+        key!(char 'k') => {
+            burst.push_front(Instruction::HelpScrollUp(1));
+        }
+        key!(char 'j') => {
+            burst.push_front(Instruction::HelpScrollDown(1));
+        }
+        key!(PageUp) => {
+            burst.push_front(Instruction::HelpScrollUp(HELP_PAGE_SIZE));
+        }
+        key!(PageDown) => {
+            burst.push_front(Instruction::HelpScrollDown(HELP_PAGE_SIZE));
+        }
+        _ => (),
+    };
+}
+
+pub fn handle_keypress_screen_too_small<B: Backend>(evt: Event, app: &mut App<B>) {
+    match evt {
+        key!(ctrl 'c') | key!(char 'q') => {
             app.exit();
         }
         _ => (),
@@ -169,7 +524,7 @@ pub fn handle_keypress_exiting_mode<B: Backend>(evt: Event, app: &mut App<B>) {
 pub fn handle_keypress_warning_message<B: Backend>(evt: Event, app: &mut App<B>) {
     match evt {
         _ => {
-            app.reset_ui_mode();
+            app.dismiss_warning_modal();
         }
     }
 }
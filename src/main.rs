@@ -1,9 +1,11 @@
 use std::{
-    env, io,
-    path::PathBuf,
+    collections::HashSet,
+    env,
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
     process,
     sync::{
-        Arc,
+        Arc, Barrier, Mutex,
         atomic::{AtomicBool, Ordering},
         mpsc::{self, Receiver, SyncSender},
     },
@@ -13,18 +15,39 @@ use std::{
 
 use argh::FromArgs;
 use crossterm::{
-    event::{Event as BackEvent, KeyCode, KeyEvent, KeyModifiers},
+    event::{DisableMouseCapture, EnableMouseCapture, Event as BackEvent},
+    execute,
     terminal::{disable_raw_mode, enable_raw_mode},
 };
-use jwalk::{
-    Parallelism::{RayonDefaultPool, Serial},
-    WalkDir,
-};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use tui::backend::{Backend, CrosstermBackend};
 
-use app::{App, UiMode};
+use anyhow::anyhow;
+use app::{App, AppOptions, UiMode};
 use input::TerminalEvents;
-use messages::{Event, Instruction, handle_events};
+use messages::{Event, Instruction, handle_events, priority_channel};
+#[cfg(not(target_os = "windows"))]
+use os::unix::{inode, num_hardlinks};
+#[cfg(target_os = "windows")]
+use os::windows::{inode, num_hardlinks};
+use output::{
+    debug_log::DebugLog,
+    ipc::{IpcServer, IpcState},
+    json_lines,
+    largest::{LargestFiles, OutputFormat, print_largest},
+    shell_quote,
+    text_report::{AppReport, write_report},
+};
+use scanner::{
+    MetadataProvider, ScanFilter, count_top_level_entries, entry_metadata, filesystem_metadata,
+    scan_folder, scan_folder_with,
+};
+use sftp::{SFTP_PREFIX, SftpConnection, SftpLocation};
+use state::{
+    Config, ExternalCommand, SCAN_PROGRESS_INTERVAL, ScanProgress, SessionState, WarningsShown,
+    files::file_size, layout::LayoutAlgorithm,
+};
+use ui::SpinnerStyle;
 
 #[cfg(test)]
 mod tests;
@@ -33,39 +56,181 @@ mod app;
 mod input;
 mod messages;
 mod os;
+mod output;
+mod scanner;
+mod sftp;
 mod state;
 mod ui;
+#[cfg(feature = "version-check")]
+mod version_check;
 
 #[cfg(not(test))]
 const SHOULD_SHOW_LOADING_ANIMATION: bool = true;
 #[cfg(test)]
 const SHOULD_SHOW_LOADING_ANIMATION: bool = false;
+// how often the scanner lets the ui know which path it's at, more often than that
+// would be too fast to read anyway (0 means after every entry)
 #[cfg(not(test))]
-const SHOULD_HANDLE_WIN_CHANGE: bool = true;
+const LAST_READ_PATH_INTERVAL_MS: u64 = 50;
 #[cfg(test)]
-const SHOULD_HANDLE_WIN_CHANGE: bool = false;
-#[cfg(not(test))]
-const SHOULD_SCAN_HD_FILES_IN_MULTIPLE_THREADS: bool = true;
-#[cfg(test)]
-const SHOULD_SCAN_HD_FILES_IN_MULTIPLE_THREADS: bool = false;
+const LAST_READ_PATH_INTERVAL_MS: u64 = 0;
+// how often the loading animation moves and the ui is redrawn while scanning
+const DEFAULT_TICK_RATE_MS: u64 = 100;
+const MIN_TICK_RATE_MS: u64 = 16;
+const MAX_TICK_RATE_MS: u64 = 10_000;
+// how long an ipc client waits for the app to look up a size, eg. while it's busy deleting
+const IPC_QUERY_TIMEOUT: time::Duration = time::Duration::from_secs(1);
 
 /// Terminal disk space visual navigator
 #[derive(FromArgs)]
+#[argh(note = "Environment variables:
+  DISKONAUT_FOLDER          the folder to scan if none is given
+  DISKONAUT_APPARENT_SIZE   set to 1 to show apparent sizes, like --apparent-size
+  DISKONAUT_TITLE_SUFFIX    text to show on the right of the title line (eg. the hostname)")]
 pub struct Opt {
-    /// the folde to scan
+    /// the folder to scan, same as giving it as an argument
     #[argh(option)]
     folder: Option<PathBuf>,
-    /// show file sizes rather than their block usage on disk
+    /// show file sizes rather than their block usage on disk (or set
+    /// $DISKONAUT_APPARENT_SIZE=1)
     #[argh(switch, short = 'a')]
     apparent_size: bool,
+    /// show block usage on disk even if the last session on this folder
+    /// (or $DISKONAUT_APPARENT_SIZE) showed apparent sizes
+    #[argh(switch)]
+    no_apparent_size: bool,
+    /// leave out hidden files and folders ('H' toggles this while running)
+    #[argh(switch, short = 'H')]
+    skip_hidden: bool,
     /// don't ask for confirmation before deleting
     #[argh(switch, short = 'd')]
     disable_delete_confirmation: bool,
+    /// don't allow deleting anything, for looking around where a slip of the
+    /// finger would be costly
+    #[argh(switch, short = 'r')]
+    read_only: bool,
+    /// warn about deleting without confirmation every time -d is used,
+    /// rather than only the first time
+    #[argh(switch)]
+    always_warn_no_confirm: bool,
+    /// when asking for confirmation before deleting, go ahead by
+    /// ourselves after this many seconds (0, the default, waits for an answer)
+    #[argh(option)]
+    auto_confirm_secs: Option<u64>,
+    /// copy the selected path to the clipboard with 'y' (or only its name with 'Y')
+    #[argh(switch)]
+    clipboard: bool,
+    /// when exiting, print a command to cd into the folder you were in
+    /// (eg. eval "$(diskonaut --cd-on-exit)")
+    #[argh(switch)]
+    cd_on_exit: bool,
+    /// leave the mouse to the terminal, so that text can be selected as usual
+    /// (clicking no longer selects tiles)
+    #[argh(switch)]
+    no_mouse: bool,
+    /// check whether a newer version of diskonaut was released, and exit
+    #[argh(switch)]
+    version_check: bool,
+    /// how to lay out the tiles: squarified (default), slice-and-dice, strip
+    /// or binary-partition ('A' cycles through them while running)
+    #[argh(option)]
+    layout: Option<LayoutAlgorithm>,
+    /// color the background of tiles from red (largest) to white (smallest),
+    /// needs a terminal with true color support
+    #[argh(switch)]
+    gradient: bool,
+    /// color files by their kind (video, audio, image, text, archive or other
+    /// application files), guessed from their extension
+    #[argh(switch)]
+    show_mime_category: bool,
+    /// color the borders and names of tiles by how long ago they were last modified,
+    /// from green (today) to dark red (over two years ago), folders by their oldest file
+    #[argh(switch)]
+    color_by_age: bool,
+    /// gather the zero-byte files of each folder into a single tile
+    /// ('Z' toggles this while running)
+    #[argh(switch)]
+    show_zero_byte: bool,
+    /// don't show the time of day at the bottom right corner (only shown on wide terminals)
+    #[argh(switch)]
+    no_clock: bool,
+    /// don't zoom in ('+') further than this many levels, each of which leaves out the largest file
+    #[argh(option)]
+    max_zoom: Option<u8>,
+    /// how often to move the loading animation and redraw while scanning, in milliseconds
+    /// (16 to 10000, default 100), lower is smoother but uses more cpu
+    #[argh(option)]
+    tick_rate: Option<u64>,
+    /// what moves while scanning: a spinner before the title, classic (default),
+    /// braille or dots, or highlight (a few bold characters running along the title)
+    #[argh(option)]
+    spinner: Option<SpinnerStyle>,
+    /// stop scanning after this many seconds and show what was found until then
+    #[argh(option)]
+    scan_timeout: Option<u64>,
+    /// don't save where you were when quitting or pick up from there
+    /// when starting again on the same folder
+    #[argh(switch)]
+    no_session: bool,
+    /// don't start the ui, instead print each entry as a line of json while scanning,
+    /// followed by a summary line (eg. diskonaut --json-lines / | jq 'select(.size > 1000000)')
+    #[argh(switch)]
+    json_lines: bool,
+    /// don't start the ui, instead print the N largest files once the scan is done,
+    /// largest first (eg. diskonaut --print-largest 20 /)
+    #[argh(option)]
+    print_largest: Option<usize>,
+    /// how --print-largest prints the files: text (default, size and path separated by a tab),
+    /// json (a single array) or csv
+    #[argh(option)]
+    output_format: Option<OutputFormat>,
+    /// answer questions about the current path, the selected tile and the total size
+    /// on a unix socket at this path, one json object per line (eg. {"cmd":"get_current_path"})
+    #[argh(option)]
+    ipc_socket: Option<PathBuf>,
+    /// when exiting, write a plain text report of the session to this file: what was
+    /// scanned, what was deleted and the largest files that are left
+    #[argh(option)]
+    report: Option<PathBuf>,
+    /// log every instruction and every error we come across while scanning to this
+    /// file (emptied first), to help figure out what went wrong when reporting a bug
+    #[argh(option)]
+    log: Option<PathBuf>,
+    /// private key to log in with when scanning over sftp, if the ssh agent can't log us in
+    #[argh(option)]
+    ssh_identity: Option<PathBuf>,
+    /// leave out files and folders matching this glob pattern, either their name
+    /// or their whole path (eg. node_modules, '**/.git' or /mnt/backup), can be given more than once
+    #[argh(option)]
+    exclude: Vec<String>,
+    /// leave out files smaller than this many bytes, folders are as large as what's left in them
+    #[argh(option)]
+    min_size: Option<u64>,
+    /// only scan this many levels of folders below the one we start in, the folders at the
+    /// last level are only as large as the files right inside them
+    #[argh(option)]
+    depth: Option<usize>,
+    /// scan what symbolic links point to as if it was where they are, rather than the links
+    /// themselves (links that lead back to a folder they're in count as failing to read)
+    #[argh(switch, short = 'L')]
+    follow_links: bool,
+    /// stay on the file system of the scanned folder, folders where another one is
+    /// mounted are shown empty and grayed out
+    #[argh(switch, short = 'x')]
+    one_file_system: bool,
+    /// how many threads to scan with (defaults to one for each CPU, 0 or 1 scans
+    /// without any extra threads)
+    #[argh(option)]
+    threads: Option<usize>,
+    /// the folder to scan (defaults to $DISKONAUT_FOLDER, then to the current folder),
+    /// or a folder on another machine as sftp://user@host:/path
+    #[argh(positional)]
+    path: Option<PathBuf>,
 }
 
 fn main() {
     if let Err(err) = try_main() {
-        println!("Error: {}", err);
+        eprintln!("Error: {}", err);
         process::exit(2);
     }
 }
@@ -73,52 +238,422 @@ fn get_stdout() -> io::Result<io::Stdout> {
     Ok(io::stdout())
 }
 
+fn capture_mouse(cd_on_exit: bool, capture: bool) -> crossterm::Result<()> {
+    // on whichever of stdout and stderr the ui is drawn. The terminal has to stop
+    // capturing before we exit, or the shell gets our clicks as garbage input
+    let mut ui_output: Box<dyn Write> = if cd_on_exit {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    };
+    if capture {
+        execute!(ui_output, EnableMouseCapture)
+    } else {
+        execute!(ui_output, DisableMouseCapture)
+    }
+}
+
 fn try_main() -> anyhow::Result<()> {
     let opts: Opt = argh::from_env();
+    if opts.version_check {
+        #[cfg(feature = "version-check")]
+        return version_check::check_latest_version();
+        #[cfg(not(feature = "version-check"))]
+        anyhow::bail!(
+            "this diskonaut was built without --version-check (the \"version-check\" feature)"
+        );
+    }
 
-    match get_stdout() {
+    let folder_arg = match (opts.path, opts.folder) {
+        (Some(path), Some(folder)) if path != folder => anyhow::bail!(
+            "Got both '{}' and --folder '{}', only one folder can be scanned",
+            path.to_string_lossy(),
+            folder.to_string_lossy()
+        ),
+        (path, folder) => path.or(folder),
+    };
+    let remote = match folder_arg
+        .as_ref()
+        .and_then(|folder| folder.to_str())
+        .filter(|folder| folder.starts_with(SFTP_PREFIX))
+    {
+        Some(uri) => {
+            let location = SftpLocation::parse(uri)
+                .map_err(|err| anyhow!("Invalid sftp location '{}': {}", uri, err))?;
+            match SftpConnection::connect(location, opts.ssh_identity.as_deref()) {
+                Ok(connection) => Some(Arc::new(connection)),
+                Err(err) => anyhow::bail!("Failed to connect to '{}': {:#}", uri, err),
+            }
+        }
+        None => None,
+    };
+    if remote.is_some() && opts.cd_on_exit {
+        anyhow::bail!("--cd-on-exit can't be used when scanning over sftp")
+    }
+    let folder = match (&remote, folder_arg) {
+        (Some(remote), _) => remote.root().to_path_buf(),
+        (None, Some(folder)) => expand_home(folder),
+        (None, None) => match env::var_os("DISKONAUT_FOLDER") {
+            Some(folder) => {
+                let folder = expand_home(PathBuf::from(folder));
+                if !folder.as_path().is_dir() {
+                    anyhow::bail!(
+                        "DISKONAUT_FOLDER={}: no such directory",
+                        folder.to_string_lossy()
+                    )
+                }
+                folder
+            }
+            None => env::current_dir()?,
+        },
+    };
+    let tick_rate = opts.tick_rate.unwrap_or(DEFAULT_TICK_RATE_MS);
+    if !(MIN_TICK_RATE_MS..=MAX_TICK_RATE_MS).contains(&tick_rate) {
+        anyhow::bail!(
+            "--tick-rate must be between {} and {} milliseconds",
+            MIN_TICK_RATE_MS,
+            MAX_TICK_RATE_MS
+        )
+    }
+    if remote.is_none() && !folder.as_path().is_dir() {
+        anyhow::bail!("Folder '{}' does not exist", folder.to_string_lossy())
+    }
+    // sessions are saved per local folder, so remote folders could be mistaken for them
+    let save_session = !opts.no_session && remote.is_none();
+    let restored_session = if !save_session {
+        None
+    } else {
+        SessionState::load(&folder)
+    };
+    // whatever is asked for on the command line wins over what we remember
+    let apparent_size = match (opts.apparent_size, opts.no_apparent_size) {
+        (true, true) => {
+            anyhow::bail!("--apparent-size and --no-apparent-size can't be used together")
+        }
+        (true, false) => true,
+        (false, true) => false,
+        (false, false) => {
+            apparent_size_from_env()
+                || restored_session
+                    .as_ref()
+                    .is_some_and(|session_state| session_state.apparent_size)
+        }
+    };
+    let title_suffix = env::var("DISKONAUT_TITLE_SUFFIX").ok();
+    let config = Config::load();
+    let warn_delete_confirmation_disabled = opts.disable_delete_confirmation
+        && !opts.read_only
+        && (opts.always_warn_no_confirm
+            || !WarningsShown::load().disable_delete_confirmation_warned);
+    let ipc_server = match &opts.ipc_socket {
+        Some(socket_path) => match IpcServer::bind(socket_path) {
+            Ok(ipc_server) => Some(ipc_server),
+            Err(err) => anyhow::bail!(
+                "Failed to create socket '{}': {}",
+                socket_path.to_string_lossy(),
+                err
+            ),
+        },
+        None => None,
+    };
+    let scan_filter = ScanFilter::new(&folder)
+        .skip_hidden(opts.skip_hidden)
+        .excluded_patterns(excluded_patterns(&opts.exclude)?)
+        .min_size_bytes(opts.min_size.unwrap_or(0) as u128)
+        .max_depth(opts.depth)
+        .follow_links(opts.follow_links)
+        .one_file_system(opts.one_file_system)
+        .threads(opts.threads);
+    if opts.json_lines {
+        return print_json_lines(&folder, apparent_size, scan_filter, remote.as_deref());
+    }
+    if let Some(count) = opts.print_largest {
+        return print_largest_files(
+            &folder,
+            apparent_size,
+            scan_filter,
+            remote.as_deref(),
+            count,
+            opts.output_format.unwrap_or_default(),
+        );
+    } else if opts.output_format.is_some() {
+        anyhow::bail!("--output-format only applies to --print-largest");
+    }
+    let debug_log = match &opts.log {
+        Some(log_path) => Some(DebugLog::create(log_path).map_err(|err| {
+            anyhow!(
+                "Failed to create log file '{}': {}",
+                log_path.to_string_lossy(),
+                err
+            )
+        })?),
+        None => None,
+    };
+
+    let (exit_path, report) = match get_stdout() {
         Ok(stdout) => {
             enable_raw_mode()?;
-            let terminal_backend = CrosstermBackend::new(stdout);
+            if !opts.no_mouse {
+                capture_mouse(opts.cd_on_exit, true)?;
+            }
             let terminal_events = TerminalEvents {};
-            let folder = match opts.folder {
-                Some(folder) => folder,
-                None => env::current_dir()?,
+            // stdout is reserved for the cd command with --cd-on-exit, so we draw the ui on stderr
+            let ui_output: Box<dyn Write + Send> = if opts.cd_on_exit {
+                Box::new(io::stderr())
+            } else {
+                Box::new(stdout)
             };
-            if !folder.as_path().is_dir() {
-                anyhow::bail!("Folder '{}' does not exist", folder.to_string_lossy())
-            }
             start(
-                terminal_backend,
+                CrosstermBackend::new(ui_output),
                 Box::new(terminal_events),
                 folder,
-                opts.apparent_size,
-                opts.disable_delete_confirmation,
-            );
+                StartOptions {
+                    app: AppOptions {
+                        show_apparent_size: apparent_size,
+                        disable_delete_confirmation: opts.disable_delete_confirmation,
+                        warn_delete_confirmation_disabled,
+                        // the app marks them as shown once they're dismissed
+                        remember_warnings_shown: !opts.always_warn_no_confirm,
+                        auto_confirm_secs: opts.auto_confirm_secs.unwrap_or(0),
+                        read_only: opts.read_only,
+                        clipboard_enabled: opts.clipboard,
+                        layout_algorithm: opts.layout.unwrap_or_default(),
+                        show_gradient: opts.gradient,
+                        show_mime_category: opts.show_mime_category,
+                        color_by_age: opts.color_by_age,
+                        show_zero_byte_files: opts.show_zero_byte,
+                        show_clock: !opts.no_clock,
+                        max_zoom: opts.max_zoom,
+                        save_session,
+                        restored_session,
+                    },
+                    scan_filter: Some(scan_filter),
+                    tick_rate: time::Duration::from_millis(tick_rate),
+                    spinner_style: opts.spinner.unwrap_or_default(),
+                    scan_timeout: opts.scan_timeout.map(time::Duration::from_secs),
+                    title_suffix,
+                    external_commands: config.external_commands,
+                    ipc_server,
+                    collect_report: opts.report.is_some(),
+                    remote,
+                    debug_log,
+                    ..StartOptions::default()
+                },
+            )
         }
         Err(_) => anyhow::bail!("Failed to get stdout: are you trying to pipe 'diskonaut'?"),
+    };
+    let report_written = match (&opts.report, report) {
+        (Some(report_path), Some(report)) => {
+            write_report(&report, report_path).map_err(|err| (report_path, err))
+        }
+        _ => Ok(()),
+    };
+    if !opts.no_mouse {
+        capture_mouse(opts.cd_on_exit, false)?;
     }
     disable_raw_mode()?;
+    if let Err((report_path, err)) = report_written {
+        anyhow::bail!(
+            "Failed to write report to '{}': {}",
+            report_path.to_string_lossy(),
+            err
+        )
+    }
+    if opts.cd_on_exit {
+        println!("cd {}", shell_quote(&exit_path.to_string_lossy()));
+    }
+    Ok(())
+}
+
+fn excluded_patterns(patterns: &[String]) -> anyhow::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|err| anyhow!("Invalid --exclude pattern '{}': {}", pattern, err))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
+
+fn expand_home(path: PathBuf) -> PathBuf {
+    // the shell does this for us, but not when we're run from a script or a config file
+    let mut components = path.components();
+    match (components.next(), env::var_os("HOME")) {
+        (Some(Component::Normal(first)), Some(home)) if first == "~" => {
+            let mut expanded = PathBuf::from(home);
+            expanded.extend(components);
+            expanded
+        }
+        _ => path,
+    }
+}
+
+fn apparent_size_from_env() -> bool {
+    match env::var("DISKONAUT_APPARENT_SIZE") {
+        Ok(value) => value == "1" || value.eq_ignore_ascii_case("true"),
+        Err(_) => false,
+    }
+}
+
+fn print_json_lines(
+    path: &Path,
+    show_apparent_size: bool,
+    scan_filter: ScanFilter,
+    remote: Option<&SftpConnection>,
+) -> anyhow::Result<()> {
+    let mut writer = json_lines::Writer::new();
+    if let Some(remote) = remote {
+        let mut written = Ok(());
+        remote.walk(path, scan_filter.skip_hidden, |entry| {
+            written = match entry {
+                Ok(entry) if entry.is_dir => writer.folder(&entry.path),
+                Ok(entry) => writer.file(&entry.path, entry.size),
+                Err(_) => {
+                    writer.error();
+                    Ok(())
+                }
+            };
+            written.is_ok()
+        });
+        written?;
+        writer.summary()?;
+        return Ok(());
+    }
+    // the first entry is the scanned folder itself
+    for entry in scan_folder(path, scan_filter.clone()).skip(1) {
+        match entry {
+            // only there for the size of the folders at --depth
+            Ok(entry) if scan_filter.is_past_max_depth(entry.depth) => {}
+            Ok(entry) => match entry_metadata(&entry) {
+                Ok(file_metadata) => {
+                    let entry_path = entry.path();
+                    if file_metadata.is_dir() {
+                        writer.folder(&entry_path)?;
+                    } else {
+                        let size = file_size(&entry_path, &file_metadata, show_apparent_size);
+                        writer.file(&entry_path, size)?;
+                    }
+                }
+                Err(_) => writer.error(),
+            },
+            Err(_) => writer.error(),
+        }
+    }
+    writer.summary()?;
+    Ok(())
+}
+
+fn print_largest_files(
+    path: &Path,
+    show_apparent_size: bool,
+    scan_filter: ScanFilter,
+    remote: Option<&SftpConnection>,
+    count: usize,
+    output_format: OutputFormat,
+) -> anyhow::Result<()> {
+    // only the files go to stdout, so that errors don't end up in whatever reads it
+    let mut largest_files = LargestFiles::new(count);
+    if let Some(remote) = remote {
+        remote.walk(path, scan_filter.skip_hidden, |entry| {
+            match entry {
+                Ok(entry) if entry.is_dir => {}
+                Ok(entry) => largest_files.add(&entry.path, entry.size),
+                Err(err) => eprintln!("{}", err),
+            };
+            true
+        });
+    } else {
+        for entry in scan_folder(path, scan_filter.clone()) {
+            match entry {
+                Ok(entry) if scan_filter.is_past_max_depth(entry.depth) => {}
+                Ok(entry) => {
+                    let entry_path = entry.path();
+                    match entry_metadata(&entry) {
+                        Ok(file_metadata) if file_metadata.is_dir() => {}
+                        Ok(file_metadata) => largest_files.add(
+                            &entry_path,
+                            file_size(&entry_path, &file_metadata, show_apparent_size),
+                        ),
+                        Err(err) => eprintln!("{}: {}", entry_path.to_string_lossy(), err),
+                    }
+                }
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+    print_largest(&largest_files.into_sorted(), output_format, io::stdout())?;
     Ok(())
 }
 
+// everything about a run besides where it draws, where keys come from and what it scans
+pub struct StartOptions {
+    pub app: AppOptions,
+    pub scan_filter: Option<ScanFilter>, // nothing is left out if None
+    pub tick_rate: time::Duration,
+    pub spinner_style: SpinnerStyle,
+    pub scan_timeout: Option<time::Duration>,
+    pub metadata_provider: Option<Arc<dyn MetadataProvider>>, // the filesystem if None
+    pub title_suffix: Option<String>,
+    pub external_commands: Vec<ExternalCommand>,
+    pub ipc_server: Option<IpcServer>,
+    pub collect_report: bool,
+    pub remote: Option<Arc<SftpConnection>>,
+    pub debug_log: Option<DebugLog>,
+    pub scan_complete_barrier: Option<Arc<Barrier>>,
+}
+
+impl Default for StartOptions {
+    fn default() -> Self {
+        StartOptions {
+            app: AppOptions::default(),
+            scan_filter: None,
+            tick_rate: time::Duration::from_millis(DEFAULT_TICK_RATE_MS),
+            spinner_style: SpinnerStyle::default(),
+            scan_timeout: None,
+            metadata_provider: None,
+            title_suffix: None,
+            external_commands: vec![],
+            ipc_server: None,
+            collect_report: false,
+            remote: None,
+            debug_log: None,
+            scan_complete_barrier: None,
+        }
+    }
+}
+
 pub fn start<B>(
     terminal_backend: B,
     terminal_events: Box<dyn Iterator<Item = BackEvent> + Send>,
     path: PathBuf,
-    show_apparent_size: bool,
-    disable_delete_confirmation: bool,
-) where
+    options: StartOptions,
+) -> (PathBuf, Option<AppReport>)
+where
     B: Backend + Send + 'static,
 {
+    let StartOptions {
+        app: app_options,
+        scan_filter,
+        tick_rate,
+        spinner_style,
+        scan_timeout,
+        metadata_provider,
+        title_suffix,
+        external_commands,
+        ipc_server,
+        collect_report,
+        remote,
+        debug_log,
+        scan_complete_barrier,
+    } = options;
+    let show_apparent_size = app_options.show_apparent_size;
     let mut active_threads = vec![];
+    let scan_filter = scan_filter.unwrap_or_else(|| ScanFilter::new(&path));
 
     let (event_sender, event_receiver): (SyncSender<Event>, Receiver<Event>) =
         mpsc::sync_channel(1);
-    let (instruction_sender, instruction_receiver): (
-        SyncSender<Instruction>,
-        Receiver<Instruction>,
-    ) = mpsc::sync_channel(100);
+    let (instruction_sender, instruction_receiver) = priority_channel(100);
 
     let running = Arc::new(AtomicBool::new(true));
     let loaded = Arc::new(AtomicBool::new(false));
@@ -133,54 +668,26 @@ pub fn start<B>(
             .unwrap(),
     );
 
-    active_threads.push(
-        thread::Builder::new()
-            .name("stdin_handler".to_string())
-            .spawn({
-                let instruction_sender = instruction_sender.clone();
-                let running = running.clone();
-                move || {
-                    for evt in terminal_events {
-                        if let BackEvent::Resize(_x, _y) = evt {
-                            if SHOULD_HANDLE_WIN_CHANGE {
-                                let _ = instruction_sender.send(Instruction::ResetUiMode);
-                                let _ = instruction_sender.send(Instruction::Render);
-                            }
-                            continue;
-                        }
-
-                        if let BackEvent::Key(KeyEvent {
-                            code: KeyCode::Char('y'),
-                            modifiers: KeyModifiers::NONE,
-                        })
-                        | BackEvent::Key(KeyEvent {
-                            code: KeyCode::Char('q'),
-                            modifiers: KeyModifiers::NONE,
-                        })
-                        | BackEvent::Key(KeyEvent {
-                            code: KeyCode::Char('c'),
-                            modifiers: KeyModifiers::CONTROL,
-                        }) = evt
-                        {
-                            // not ideal, but works in a pinch
-                            let _ = instruction_sender.send(Instruction::Keypress(evt));
-                            park_timeout(time::Duration::from_millis(100));
-                            // if we don't wait, the app won't have time to quit
-                            if !running.load(Ordering::Acquire) {
-                                // sometimes ctrl-c doesn't shut down the app
-                                // (eg. dismissing an error message)
-                                // in order not to be aware of those particularities
-                                // we check "running"
-                                break;
-                            }
-                        } else if instruction_sender.send(Instruction::Keypress(evt)).is_err() {
-                            break;
-                        }
+    // not joined, since it is stuck reading the terminal until the next key is pressed,
+    // which might never happen. once the app is gone, sending that key fails and it stops
+    thread::Builder::new()
+        .name("stdin_handler".to_string())
+        .spawn({
+            let instruction_sender = instruction_sender.clone();
+            move || {
+                for evt in terminal_events {
+                    if let BackEvent::Resize(_x, _y) = evt {
+                        let _ = instruction_sender.send(Instruction::ResetUiMode);
+                        let _ = instruction_sender.send(Instruction::Render);
+                        continue;
+                    }
+                    if instruction_sender.send(Instruction::Keypress(evt)).is_err() {
+                        break;
                     }
                 }
-            })
-            .unwrap(),
-    );
+            }
+        })
+        .unwrap();
 
     active_threads.push(
         thread::Builder::new()
@@ -189,38 +696,159 @@ pub fn start<B>(
                 let path = path.clone();
                 let instruction_sender = instruction_sender.clone();
                 let loaded = loaded.clone();
+                let remote_for_scanner = remote.clone();
+                let debug_log = debug_log.clone();
+                let scan_filter = scan_filter.clone();
+                let metadata_provider = metadata_provider
+                    .unwrap_or_else(|| filesystem_metadata(scan_filter.follow_links));
                 move || {
-                    'scanning: for entry in WalkDir::new(&path)
-                        .parallelism(if SHOULD_SCAN_HD_FILES_IN_MULTIPLE_THREADS {
-                            RayonDefaultPool
-                        } else {
-                            Serial
-                        })
-                        .skip_hidden(false)
-                        .follow_links(false)
-                        .into_iter()
-                    {
-                        let instruction_sent = match entry {
-                            Ok(entry) => match entry.metadata() {
-                                Ok(file_metadata) => {
-                                    let entry_path = entry.path();
-                                    instruction_sender.send(Instruction::AddEntryToBaseFolder((
-                                        file_metadata,
-                                        entry_path,
-                                    )))
+                    let last_read_path_interval =
+                        time::Duration::from_millis(LAST_READ_PATH_INTERVAL_MS);
+                    let mut last_read_path_sent: Option<time::Instant> = None;
+                    let mut update_last_read_path = |entry_path: &PathBuf| {
+                        if last_read_path_sent
+                            .is_none_or(|sent| sent.elapsed() >= last_read_path_interval)
+                        {
+                            last_read_path_sent = Some(time::Instant::now());
+                            // if this fails, so will adding the entry right after
+                            let _ = instruction_sender
+                                .send(Instruction::UpdateLastReadPath(entry_path.clone()));
+                        }
+                    };
+                    let mut current_scan_dir: Option<PathBuf> = None;
+                    let mut update_current_scan_dir = |entry_path: &Path| {
+                        // entries come folder by folder, so this is sent once per folder
+                        if let Some(scan_dir) = entry_path.parent()
+                            && scan_dir.starts_with(&path)
+                            && current_scan_dir.as_deref() != Some(scan_dir)
+                        {
+                            current_scan_dir = Some(scan_dir.to_path_buf());
+                            let _ = instruction_sender
+                                .send(Instruction::UpdateCurrentScanDir(scan_dir.to_path_buf()));
+                        }
+                    };
+                    let mut progress = ScanProgress::default();
+                    let scan_started = time::Instant::now();
+                    let scan_timed_out = || {
+                        let timed_out =
+                            scan_timeout.is_some_and(|timeout| scan_started.elapsed() >= timeout);
+                        if timed_out {
+                            let _ = instruction_sender.send(Instruction::ScanTimedOut);
+                        }
+                        timed_out
+                    };
+                    // every link to a hard-linked file shows up as an entry of its own
+                    let mut hardlinked_inodes = HashSet::new();
+                    let mut progress_sent = time::Instant::now();
+                    let mut update_progress = |progress: &ScanProgress| {
+                        if progress_sent.elapsed() >= SCAN_PROGRESS_INTERVAL {
+                            progress_sent = time::Instant::now();
+                            let _ = instruction_sender.send(Instruction::UpdateProgress(*progress));
+                        }
+                    };
+                    if let Some(remote) = remote_for_scanner {
+                        remote.walk(&path, scan_filter.skip_hidden, |entry| {
+                            let instruction_sent = match entry {
+                                Ok(entry) => {
+                                    update_last_read_path(&entry.path);
+                                    update_current_scan_dir(&entry.path);
+                                    if entry.is_dir {
+                                        progress.add_directory();
+                                    } else {
+                                        progress.add_file(entry.size);
+                                    }
+                                    update_progress(&progress);
+                                    instruction_sender
+                                        .send(Instruction::AddRemoteEntryToBaseFolder(entry))
                                 }
-                                Err(_) => {
-                                    instruction_sender.send(Instruction::IncrementFailedToRead)
+                                Err(_) => instruction_sender
+                                    .send(Instruction::IncrementFailedToRead(None)),
+                            };
+                            // same as below, stop once the program has ended
+                            instruction_sent.is_ok() && !scan_timed_out()
+                        });
+                    } else {
+                        // done here rather than in the app, so that the ui isn't waiting on it
+                        if let Some(top_level_count) =
+                            count_top_level_entries(&path, scan_filter.skip_hidden)
+                        {
+                            let _ = instruction_sender
+                                .send(Instruction::UpdateTopLevelCount(top_level_count));
+                        }
+                        'scanning: for entry in
+                            scan_folder_with(&path, scan_filter.clone(), metadata_provider)
+                        {
+                            let instruction_sent = match entry {
+                                Ok(entry) => match entry_metadata(&entry) {
+                                    Ok(file_metadata) => {
+                                        let entry_path = entry.path();
+                                        update_last_read_path(&entry_path);
+                                        update_current_scan_dir(&entry_path);
+                                        if file_metadata.is_dir() {
+                                            // the scanned folder itself is not one we entered along the way
+                                            if entry.depth > 0 {
+                                                progress.add_directory();
+                                            }
+                                        } else {
+                                            progress.add_file(file_size(
+                                                &entry_path,
+                                                &file_metadata,
+                                                show_apparent_size,
+                                            ));
+                                            if num_hardlinks(&file_metadata).is_some_and(|n| n > 1)
+                                                && let Some(inode) = inode(&file_metadata)
+                                                && hardlinked_inodes.insert(inode)
+                                            {
+                                                progress.add_hardlinked_file();
+                                            }
+                                        }
+                                        update_progress(&progress);
+                                        let add_entry =
+                                            if scan_filter.is_past_max_depth(entry.depth) {
+                                                Instruction::AddUnexpandedEntryToBaseFolder((
+                                                    file_metadata,
+                                                    entry_path,
+                                                ))
+                                            } else if scan_filter.is_mount_point(&file_metadata) {
+                                                Instruction::AddMountPointToBaseFolder(entry_path)
+                                            } else {
+                                                Instruction::AddEntryToBaseFolder((
+                                                    file_metadata,
+                                                    entry_path,
+                                                ))
+                                            };
+                                        instruction_sender.send(add_entry)
+                                    }
+                                    Err(err) => {
+                                        instruction_sender.send(Instruction::IncrementFailedToRead(
+                                            Some((entry.path(), err.to_string())),
+                                        ))
+                                    }
+                                },
+                                Err(err) => {
+                                    if let Some(debug_log) = &debug_log {
+                                        debug_log.log(format_args!("walk error: {}", err));
+                                    }
+                                    let failed_path = err
+                                        .path()
+                                        .map(|path| (path.to_path_buf(), err.to_string()));
+                                    instruction_sender
+                                        .send(Instruction::IncrementFailedToRead(failed_path))
                                 }
-                            },
-                            Err(_) => instruction_sender.send(Instruction::IncrementFailedToRead),
-                        };
-                        if instruction_sent.is_err() {
-                            // if we fail to send an instruction here, this likely means the program has
-                            // ended and we need to break this loop as well in order not to hang
-                            break 'scanning;
-                        };
+                            };
+                            if instruction_sent.is_err() {
+                                // if we fail to send an instruction here, this likely means the program has
+                                // ended and we need to break this loop as well in order not to hang
+                                break 'scanning;
+                            };
+                            if scan_timed_out() {
+                                // whatever we have so far is shown as is
+                                break 'scanning;
+                            }
+                        }
                     }
+                    // whatever was scanned since the last update
+                    let _ = instruction_sender.send(Instruction::UpdateProgress(progress));
                     let _ = instruction_sender.send(Instruction::StartUi);
                     loaded.store(true, Ordering::Release);
                 }
@@ -228,6 +856,30 @@ pub fn start<B>(
             .unwrap(),
     );
 
+    let ipc_state = ipc_server.map(|ipc_server| {
+        let ipc_state = Arc::new(Mutex::new(IpcState::default()));
+        active_threads.push(
+            thread::Builder::new()
+                .name("ipc_server".to_string())
+                .spawn({
+                    let ipc_state = ipc_state.clone();
+                    let running = running.clone();
+                    let instruction_sender = instruction_sender.clone();
+                    // sizes are looked up in the file tree, which only the app thread has
+                    let size_at_path = move |path: &Path| {
+                        let (reply_sender, reply_receiver) = mpsc::sync_channel(1);
+                        instruction_sender
+                            .send(Instruction::QueryPathSize(path.to_path_buf(), reply_sender))
+                            .ok()?;
+                        reply_receiver.recv_timeout(IPC_QUERY_TIMEOUT).ok()?
+                    };
+                    move || ipc_server.serve(ipc_state, running, size_at_path)
+                })
+                .unwrap(),
+        );
+        ipc_state
+    });
+
     if SHOULD_SHOW_LOADING_ANIMATION {
         active_threads.push(
             thread::Builder::new()
@@ -237,10 +889,8 @@ pub fn start<B>(
                     let running = running.clone();
                     move || {
                         while running.load(Ordering::Acquire) && !loaded.load(Ordering::Acquire) {
-                            let _ =
-                                instruction_sender.send(Instruction::ToggleScanningVisualIndicator);
-                            let _ = instruction_sender.send(Instruction::RenderAndUpdateBoard);
-                            park_timeout(time::Duration::from_millis(100));
+                            let _ = instruction_sender.send(Instruction::Tick);
+                            park_timeout(tick_rate);
                         }
                     }
                 })
@@ -248,17 +898,36 @@ pub fn start<B>(
         );
     }
 
-    let mut app = App::new(
-        terminal_backend,
-        path,
-        event_sender,
-        show_apparent_size,
-        disable_delete_confirmation,
-    );
+    let mut app = App::new(terminal_backend, path, event_sender, app_options);
+    if let Some(ipc_state) = ipc_state {
+        app.set_ipc_state(ipc_state);
+    }
+    if let Some(title_suffix) = title_suffix {
+        app.set_title_suffix(title_suffix);
+    }
+    app.set_external_commands(external_commands);
+    app.set_spinner_style(spinner_style);
+    app.set_scan_filter(scan_filter);
+    if let Some(debug_log) = debug_log {
+        app.set_debug_log(debug_log);
+    }
+    if let Some(remote) = remote {
+        app.set_remote(remote);
+    }
+    if let Some(scan_complete_barrier) = scan_complete_barrier {
+        app.set_scan_complete_barrier(scan_complete_barrier);
+    }
     app.start(instruction_receiver);
     running.store(false, Ordering::Release);
 
     for thread_handler in active_threads {
         thread_handler.join().unwrap();
     }
+    // walking the whole tree for the largest files takes a while, so only if it's wanted
+    let report = if collect_report {
+        Some(app.report())
+    } else {
+        None
+    };
+    (app.exit_path(), report)
 }
@@ -1,18 +1,32 @@
 use std::{
-    sync::mpsc::{Receiver, SyncSender},
-    thread::park_timeout,
+    path::PathBuf,
+    sync::mpsc::Receiver,
+    thread::{self, park_timeout},
     time,
 };
 
-use crate::messages::Instruction;
+use crate::{
+    messages::{Instruction, PrioritySender},
+    state::{
+        AUTO_CONFIRM_TICK, COPIED_TO_CLIPBOARD_DURATION, INFO_FLASH_DURATION, NOTICE_DURATION,
+        files::find_duplicates,
+    },
+};
 
 pub enum Event {
     PathError,
     FileDeleted,
+    PathHighlighted(time::Duration), // for how long
+    CopiedToClipboard,
+    NoticeShown,
+    InfoFlashShown,
+    DeletionPrompted { prompt_id: u64, seconds: u64 },
+    // files of the same size, see FileTree::duplicate_candidates
+    DuplicatesRequested(Vec<(u128, Vec<PathBuf>)>),
     AppExit,
 }
 
-pub fn handle_events(event_receiver: Receiver<Event>, instruction_sender: SyncSender<Instruction>) {
+pub fn handle_events(event_receiver: Receiver<Event>, instruction_sender: PrioritySender) {
     loop {
         let event = event_receiver
             .recv()
@@ -32,6 +46,49 @@ pub fn handle_events(event_receiver: Receiver<Event>, instruction_sender: SyncSe
                 let _ = instruction_sender.send(Instruction::UnflashSpaceFreed);
                 let _ = instruction_sender.send(Instruction::Render);
             }
+            Event::PathHighlighted(duration) => {
+                // the highlight is cleared on the first render after it expires
+                park_timeout(duration);
+                let _ = instruction_sender.send(Instruction::Render);
+            }
+            Event::CopiedToClipboard => {
+                park_timeout(COPIED_TO_CLIPBOARD_DURATION);
+                let _ = instruction_sender.send(Instruction::Render);
+            }
+            Event::NoticeShown => {
+                park_timeout(NOTICE_DURATION);
+                let _ = instruction_sender.send(Instruction::Render);
+            }
+            Event::InfoFlashShown => {
+                park_timeout(INFO_FLASH_DURATION);
+                let _ = instruction_sender.send(Instruction::Render);
+            }
+            Event::DeletionPrompted { prompt_id, seconds } => {
+                // counting down on our own thread so that other events
+                // are not held up while the prompt is open
+                let instruction_sender = instruction_sender.clone();
+                thread::spawn(move || {
+                    for seconds_left in (0..seconds).rev() {
+                        thread::sleep(AUTO_CONFIRM_TICK);
+                        let instruction = if seconds_left > 0 {
+                            Instruction::AutoConfirmTick(prompt_id)
+                        } else {
+                            Instruction::ConfirmDelete(prompt_id)
+                        };
+                        if instruction_sender.send(instruction).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            Event::DuplicatesRequested(same_size_files) => {
+                // reading all those files can take a while, and the ui stays usable meanwhile
+                let instruction_sender = instruction_sender.clone();
+                thread::spawn(move || {
+                    let duplicates = find_duplicates(same_size_files);
+                    let _ = instruction_sender.send(Instruction::DuplicatesReady(duplicates));
+                });
+            }
             Event::AppExit => {
                 break;
             }
@@ -1,103 +1,502 @@
-use std::{fs::Metadata, path::PathBuf, sync::mpsc::Receiver};
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    fs::Metadata,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{Once, mpsc::SyncSender},
+    time::{Duration, Instant},
+};
 
-use crossterm::event::Event as BackEvent;
+use crossterm::event::{Event as BackEvent, KeyCode, KeyEvent, KeyModifiers};
 use tui::backend::Backend;
 
 use crate::{
     App, UiMode,
     input::{
-        handle_keypress_delete_file_mode, handle_keypress_error_message,
-        handle_keypress_exiting_mode, handle_keypress_loading_mode, handle_keypress_normal_mode,
-        handle_keypress_screen_too_small, handle_keypress_warning_message,
+        handle_keypress_confirming_mode, handle_keypress_duplicates, handle_keypress_error_list,
+        handle_keypress_error_message, handle_keypress_extension_filter_input,
+        handle_keypress_file_details, handle_keypress_help, handle_keypress_jump_to_path,
+        handle_keypress_largest_files, handle_keypress_loading_mode, handle_keypress_most_accessed,
+        handle_keypress_normal_mode, handle_keypress_screen_too_small,
+        handle_keypress_search_input, handle_keypress_search_results,
+        handle_keypress_warning_message,
     },
+    messages::PriorityReceiver,
+    output::ipc::PathSize,
+    sftp::RemoteEntry,
+    state::ScanProgress,
 };
 
+#[derive(Debug)]
 pub enum Instruction {
     SetPathToRed,
     ResetCurrentPathColor,
     FlashSpaceFreed,
     UnflashSpaceFreed,
     AddEntryToBaseFolder((Metadata, PathBuf)),
+    // one level past --depth, only counted towards the size of the folder it's in
+    AddUnexpandedEntryToBaseFolder((Metadata, PathBuf)),
+    // a folder on another file system, which --one-file-system doesn't go into
+    AddMountPointToBaseFolder(PathBuf),
+    AddRemoteEntryToBaseFolder(RemoteEntry),
+    UpdateLastReadPath(PathBuf),
+    // the folder whose contents are being scanned, sent once per folder
+    UpdateCurrentScanDir(PathBuf),
+    UpdateProgress(ScanProgress),
+    // how many entries are right inside the scanned folder, counted before scanning it
+    UpdateTopLevelCount(usize),
+    // the scan ran for longer than --scan-timeout, StartUi follows right after
+    ScanTimedOut,
     StartUi,
-    ToggleScanningVisualIndicator,
-    RenderAndUpdateBoard,
+    // sent at a fixed interval while loading, see App::on_tick
+    Tick,
     Render,
     ResetUiMode,
     Keypress(BackEvent),
-    IncrementFailedToRead,
+    // leaving the current folder, as opposed to moving around the tiles inside it
+    NavigateToParentDirectory,
+    // straight back to the scanned folder, from however deep we are
+    NavigateRoot,
+    // one or more consecutive presses of the same arrow key
+    NavigateLeft(usize),
+    NavigateRight(usize),
+    NavigateUp(usize),
+    NavigateDown(usize),
+    // with the path we failed to read and why, when we know it
+    IncrementFailedToRead(Option<(PathBuf, String)>),
+    // these carry the id of the deletion prompt they were started for,
+    // so that a countdown for a prompt that was since dismissed is ignored
+    AutoConfirmTick(u64),
+    ConfirmDelete(u64),
+    // the selected file, with $PAGER
+    OpenInPager(PathBuf),
+    // scanned again, for when it changed outside of diskonaut
+    RefreshCurrentFolder,
+    // files with the same contents are looked for on their own thread,
+    // which sends back what it found (see Event::DuplicatesRequested)
+    ComputeDuplicates,
+    DuplicatesReady(Vec<Vec<PathBuf>>),
+    // by this many keybindings, while the help modal is open
+    HelpScrollUp(usize),
+    HelpScrollDown(usize),
+    // bound to a key in the config file: the command, the selected path and
+    // whether the command gets the terminal while it runs
+    RunExternalCommand(String, PathBuf, bool),
+    // to the folder at this path (or the folder a file is in), as typed after pressing 'g'
+    JumpToPath(PathBuf),
+    // the selected path, or only its name, with --clipboard
+    CopyToClipboard(String),
+    // asked over the ipc socket, answered on the given channel
+    QueryPathSize(PathBuf, SyncSender<Option<PathSize>>),
+}
+
+// how long we keep collecting instructions that are already waiting on the channel
+// before handling them, so that eg. holding down an arrow key results in one render
+// per burst rather than one per keypress (0 means handling them one by one)
+#[cfg(not(test))]
+pub const BURST_WINDOW_MS: u64 = 16;
+#[cfg(test)]
+pub const BURST_WINDOW_MS: u64 = 0;
+
+fn arrow_key(code: KeyCode) -> BackEvent {
+    BackEvent::Key(KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+    })
 }
 
-pub fn handle_instructions<B>(app: &mut App<B>, receiver: Receiver<Instruction>)
+fn as_navigation(evt: &BackEvent) -> Option<Instruction> {
+    match evt {
+        BackEvent::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }) => match code {
+            KeyCode::Left => Some(Instruction::NavigateLeft(1)),
+            KeyCode::Right => Some(Instruction::NavigateRight(1)),
+            KeyCode::Up => Some(Instruction::NavigateUp(1)),
+            KeyCode::Down => Some(Instruction::NavigateDown(1)),
+            KeyCode::Esc => Some(Instruction::NavigateToParentDirectory),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn receive_burst(receiver: &PriorityReceiver, burst_window: Duration) -> Vec<Instruction> {
+    let mut burst = vec![
+        receiver
+            .recv()
+            .expect("failed to receive instruction on channel"),
+    ];
+    let burst_end = Instant::now() + burst_window;
+    while Instant::now() < burst_end {
+        match receiver.try_recv() {
+            Ok(instruction) => burst.push(instruction),
+            Err(_) => break,
+        }
+    }
+    burst
+}
+
+fn coalesce_keypresses(burst: Vec<Instruction>) -> VecDeque<Instruction> {
+    let mut coalesced: VecDeque<Instruction> = VecDeque::with_capacity(burst.len());
+    for instruction in burst {
+        let instruction = match instruction {
+            Instruction::Keypress(evt) => as_navigation(&evt).unwrap_or(Instruction::Keypress(evt)),
+            instruction => instruction,
+        };
+        match (coalesced.back_mut(), instruction) {
+            (Some(Instruction::NavigateLeft(times)), Instruction::NavigateLeft(1))
+            | (Some(Instruction::NavigateRight(times)), Instruction::NavigateRight(1))
+            | (Some(Instruction::NavigateUp(times)), Instruction::NavigateUp(1))
+            | (Some(Instruction::NavigateDown(times)), Instruction::NavigateDown(1)) => {
+                *times += 1;
+            }
+            (_, instruction) => coalesced.push_back(instruction),
+        }
+    }
+    coalesced
+}
+
+fn handle_keypress<B>(evt: BackEvent, app: &mut App<B>, burst: &mut VecDeque<Instruction>)
 where
     B: Backend,
 {
-    loop {
-        let instruction = receiver
-            .recv()
-            .expect("failed to receive instruction on channel");
-        match instruction {
-            Instruction::SetPathToRed => {
-                app.set_path_to_red();
-            }
-            Instruction::ResetCurrentPathColor => {
-                app.reset_current_path_color();
-            }
-            Instruction::FlashSpaceFreed => {
-                app.flash_space_freed();
-            }
-            Instruction::UnflashSpaceFreed => {
-                app.unflash_space_freed();
-            }
-            Instruction::AddEntryToBaseFolder((file_metadata, entry)) => {
-                app.add_entry_to_base_folder(&file_metadata, entry);
-            }
-            Instruction::StartUi => {
-                app.start_ui();
-            }
-            Instruction::ToggleScanningVisualIndicator => {
-                app.increment_loading_progress_indicator();
-            }
-            Instruction::RenderAndUpdateBoard => {
-                app.render_and_update_board();
+    match &app.ui_mode {
+        UiMode::Loading => {
+            handle_keypress_loading_mode(evt, app, burst);
+        }
+        UiMode::Normal => {
+            handle_keypress_normal_mode(evt, app, burst);
+        }
+        UiMode::ScreenTooSmall => {
+            handle_keypress_screen_too_small(evt, app);
+        }
+        UiMode::Confirming { action } => {
+            let action = action.clone();
+            handle_keypress_confirming_mode(evt, app, action);
+        }
+        UiMode::ErrorMessage(_) => {
+            handle_keypress_error_message(evt, app);
+        }
+        UiMode::WarningMessage(_)
+        | UiMode::DeleteConfirmationWarning
+        | UiMode::ScanTimedOutWarning => {
+            handle_keypress_warning_message(evt, app);
+        }
+        UiMode::MostAccessed(_) => {
+            handle_keypress_most_accessed(evt, app);
+        }
+        UiMode::LargestFiles(_) => {
+            handle_keypress_largest_files(evt, app);
+        }
+        UiMode::Duplicates(_) => {
+            handle_keypress_duplicates(evt, app);
+        }
+        UiMode::ExtensionFilterInput(_) => {
+            handle_keypress_extension_filter_input(evt, app);
+        }
+        UiMode::FileDetails(_) => {
+            handle_keypress_file_details(evt, app);
+        }
+        UiMode::SearchInput(_) => {
+            handle_keypress_search_input(evt, app);
+        }
+        UiMode::SearchResults { .. } => {
+            handle_keypress_search_results(evt, app);
+        }
+        UiMode::JumpToPath { .. } => {
+            handle_keypress_jump_to_path(evt, app, burst);
+        }
+        UiMode::ErrorList => {
+            handle_keypress_error_list(evt, app);
+        }
+        UiMode::Help => {
+            handle_keypress_help(evt, app, burst);
+        }
+    }
+}
+
+fn navigate<B>(
+    app: &mut App<B>,
+    times: usize,
+    code: KeyCode,
+    move_by: fn(&mut App<B>, usize),
+    burst: &mut VecDeque<Instruction>,
+) where
+    B: Backend,
+{
+    match app.ui_mode {
+        UiMode::Loading | UiMode::Normal => move_by(app, times),
+        UiMode::Help => match code {
+            KeyCode::Up => app.scroll_help_up(times),
+            KeyCode::Down => app.scroll_help_down(times),
+            _ => {}
+        },
+        _ => {
+            // other modes have their own idea about what arrow keys do
+            for _ in 0..times {
+                handle_keypress(arrow_key(code), app, burst);
             }
-            Instruction::Render => {
-                app.render();
+        }
+    }
+}
+
+fn handle_instruction<B>(
+    app: &mut App<B>,
+    instruction: Instruction,
+    burst: &mut VecDeque<Instruction>,
+) where
+    B: Backend,
+{
+    match instruction {
+        Instruction::SetPathToRed => {
+            app.set_path_to_red();
+        }
+        Instruction::ResetCurrentPathColor => {
+            app.reset_current_path_color();
+        }
+        Instruction::FlashSpaceFreed => {
+            app.flash_space_freed();
+        }
+        Instruction::UnflashSpaceFreed => {
+            app.unflash_space_freed();
+        }
+        Instruction::AddEntryToBaseFolder((file_metadata, entry)) => {
+            app.add_entry_to_base_folder(&file_metadata, entry);
+        }
+        Instruction::AddUnexpandedEntryToBaseFolder((file_metadata, entry)) => {
+            app.add_unexpanded_entry_to_base_folder(&file_metadata, entry);
+        }
+        Instruction::AddMountPointToBaseFolder(entry) => {
+            app.add_mount_point_to_base_folder(entry);
+        }
+        Instruction::AddRemoteEntryToBaseFolder(entry) => {
+            app.add_remote_entry_to_base_folder(entry);
+        }
+        Instruction::UpdateLastReadPath(path) => {
+            app.update_last_read_path(path);
+        }
+        Instruction::UpdateCurrentScanDir(path) => {
+            app.update_current_scan_dir(path);
+        }
+        Instruction::UpdateProgress(progress) => {
+            app.update_progress(progress);
+        }
+        Instruction::UpdateTopLevelCount(top_level_count) => {
+            app.update_top_level_count(top_level_count);
+        }
+        Instruction::ScanTimedOut => {
+            app.scan_timed_out();
+        }
+        Instruction::StartUi => {
+            app.start_ui();
+        }
+        Instruction::Tick => {
+            app.on_tick();
+        }
+        Instruction::Render => {
+            app.render();
+        }
+        Instruction::ResetUiMode => {
+            app.reset_ui_mode();
+        }
+        Instruction::Keypress(evt) => {
+            handle_keypress(evt, app, burst);
+        }
+        Instruction::NavigateToParentDirectory => match app.ui_mode {
+            UiMode::Loading | UiMode::Normal => app.go_up(),
+            // everywhere else, ESC dismisses whatever is open
+            _ => handle_keypress(arrow_key(KeyCode::Esc), app, burst),
+        },
+        Instruction::NavigateRoot => {
+            app.go_to_root();
+        }
+        Instruction::NavigateLeft(times) => {
+            navigate(app, times, KeyCode::Left, App::move_selected_left_by, burst);
+        }
+        Instruction::NavigateRight(times) => {
+            navigate(
+                app,
+                times,
+                KeyCode::Right,
+                App::move_selected_right_by,
+                burst,
+            );
+        }
+        Instruction::NavigateUp(times) => {
+            navigate(app, times, KeyCode::Up, App::move_selected_up_by, burst);
+        }
+        Instruction::NavigateDown(times) => {
+            navigate(app, times, KeyCode::Down, App::move_selected_down_by, burst);
+        }
+        Instruction::IncrementFailedToRead(failed_path) => {
+            app.increment_failed_to_read(failed_path);
+        }
+        Instruction::AutoConfirmTick(prompt_id) => {
+            app.auto_confirm_tick(prompt_id);
+        }
+        Instruction::ConfirmDelete(prompt_id) => {
+            app.auto_confirm_deletion(prompt_id);
+        }
+        Instruction::OpenInPager(path) => {
+            app.open_in_pager(&path);
+        }
+        Instruction::RefreshCurrentFolder => {
+            app.refresh_current_folder();
+        }
+        Instruction::ComputeDuplicates => {
+            app.compute_duplicates();
+        }
+        Instruction::DuplicatesReady(duplicates) => {
+            app.show_duplicates(duplicates);
+        }
+        Instruction::JumpToPath(path) => {
+            app.jump_to_path(&path);
+        }
+        Instruction::CopyToClipboard(text) => {
+            app.copy_to_clipboard(text);
+        }
+        Instruction::RunExternalCommand(template, path, interactive) => {
+            app.spawn_external_command(&template, &path, interactive);
+        }
+        Instruction::HelpScrollUp(lines) => {
+            app.scroll_help_up(lines);
+        }
+        Instruction::HelpScrollDown(lines) => {
+            app.scroll_help_down(lines);
+        }
+        Instruction::QueryPathSize(path, reply) => {
+            // nobody might be waiting for this anymore
+            let _ = reply.send(app.path_size(&path));
+        }
+    }
+}
+
+thread_local! {
+    static RECOVERING_FROM_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+fn quiet_panics_while_recovering() {
+    // the default hook prints the panic to stderr, right over the ui, even though
+    // we show it in an error box. panics anywhere else still go through it
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !RECOVERING_FROM_PANIC.with(Cell::get) {
+                default_hook(info);
             }
-            Instruction::ResetUiMode => {
-                app.reset_ui_mode();
+        }));
+    });
+}
+
+// a bug in handling one instruction shouldn't take the whole session down with it,
+// so we show what went wrong instead and carry on with the next one
+fn recover_from_panic<B>(app: &mut App<B>, handle: impl FnOnce(&mut App<B>))
+where
+    B: Backend,
+{
+    quiet_panics_while_recovering();
+    RECOVERING_FROM_PANIC.with(|recovering| recovering.set(true));
+    let handled = panic::catch_unwind(AssertUnwindSafe(|| handle(app)));
+    RECOVERING_FROM_PANIC.with(|recovering| recovering.set(false));
+    if let Err(panic) = handled {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("unknown error"));
+        app.ui_mode = UiMode::ErrorMessage(format!("Internal error: {}", message));
+        app.render();
+    }
+}
+
+pub fn handle_instructions<B>(app: &mut App<B>, receiver: PriorityReceiver, burst_window_ms: u64)
+where
+    B: Backend,
+{
+    let burst_window = Duration::from_millis(burst_window_ms);
+    'instructions: loop {
+        let mut burst = coalesce_keypresses(receive_burst(&receiver, burst_window));
+        while let Some(instruction) = burst.pop_front() {
+            if let Some(debug_log) = app.debug_log() {
+                debug_log.log(format_args!("{:?}", instruction));
             }
-            Instruction::Keypress(evt) => {
-                match &app.ui_mode {
-                    UiMode::Loading => {
-                        handle_keypress_loading_mode(evt, app);
-                    }
-                    UiMode::Normal => {
-                        handle_keypress_normal_mode(evt, app);
-                    }
-                    UiMode::ScreenTooSmall => {
-                        handle_keypress_screen_too_small(evt, app);
-                    }
-                    UiMode::DeleteFile(file_to_delete) => {
-                        let file_to_delete = file_to_delete.clone();
-                        handle_keypress_delete_file_mode(evt, app, file_to_delete);
-                    }
-                    UiMode::ErrorMessage(_) => {
-                        handle_keypress_error_message(evt, app);
-                    }
-                    UiMode::Exiting { app_loaded: _ } => {
-                        handle_keypress_exiting_mode(evt, app);
-                    }
-                    UiMode::WarningMessage(_) => {
-                        handle_keypress_warning_message(evt, app);
-                    }
-                }
-                if !app.is_running {
-                    break;
-                }
+            recover_from_panic(app, |app| handle_instruction(app, instruction, &mut burst));
+            if !app.is_running {
+                break 'instructions;
             }
-            Instruction::IncrementFailedToRead => {
-                app.increment_failed_to_read();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::sync_channel;
+
+    use super::*;
+    use crate::{app::AppOptions, tests::cases::test_utils::test_backend_factory};
+
+    fn describe(instructions: VecDeque<Instruction>) -> Vec<String> {
+        instructions
+            .into_iter()
+            .map(|instruction| match instruction {
+                Instruction::NavigateLeft(times) => format!("left {}", times),
+                Instruction::NavigateRight(times) => format!("right {}", times),
+                Instruction::NavigateUp(times) => format!("up {}", times),
+                Instruction::NavigateDown(times) => format!("down {}", times),
+                Instruction::NavigateToParentDirectory => String::from("parent"),
+                Instruction::Keypress(_) => String::from("keypress"),
+                _ => String::from("other"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn coalesce_consecutive_arrow_keys() {
+        let burst = vec![
+            Instruction::Keypress(arrow_key(KeyCode::Left)),
+            Instruction::Keypress(arrow_key(KeyCode::Left)),
+            Instruction::Keypress(arrow_key(KeyCode::Left)),
+            Instruction::Keypress(arrow_key(KeyCode::Down)),
+            Instruction::Render,
+            Instruction::Keypress(arrow_key(KeyCode::Down)),
+            Instruction::Keypress(arrow_key(KeyCode::Down)),
+            Instruction::Keypress(arrow_key(KeyCode::Enter)),
+            Instruction::Keypress(arrow_key(KeyCode::Right)),
+            Instruction::Keypress(arrow_key(KeyCode::Esc)),
+            Instruction::Keypress(arrow_key(KeyCode::Esc)),
+        ];
+        assert_eq!(
+            describe(coalesce_keypresses(burst)),
+            vec![
+                "left 3", "down 1", "other", "down 2", "keypress", "right 1", "parent", "parent"
+            ]
+        );
+    }
+
+    #[test]
+    fn panicking_instruction_shows_error_message() {
+        let (_terminal_events, _terminal_draw_events, backend) = test_backend_factory(190, 50);
+        let (event_sender, _event_receiver) = sync_channel(100);
+        let mut app = App::new(
+            backend,
+            PathBuf::from("/tmp/diskonaut_panic_test"),
+            event_sender,
+            AppOptions::default(),
+        );
+        app.ui_mode = UiMode::Normal;
+        recover_from_panic(&mut app, |_| panic!("bad instruction"));
+        match &app.ui_mode {
+            UiMode::ErrorMessage(message) => {
+                assert_eq!(message, "Internal error: bad instruction")
             }
+            _ => panic!("did not recover to an error message"),
         }
+        assert!(app.is_running);
+        // panics after this one are printed again
+        assert!(!RECOVERING_FROM_PANIC.with(Cell::get));
     }
 }
@@ -1,5 +1,7 @@
 pub use event::*;
 pub use instruction::*;
+pub use priority_channel::*;
 
 mod event;
 mod instruction;
+mod priority_channel;
@@ -0,0 +1,160 @@
+use std::sync::mpsc::{self, Receiver, RecvError, SendError, SyncSender, TryRecvError};
+
+use crate::messages::Instruction;
+
+// instructions that come with the scan and the ones that are sent when it's done
+// have to be handled in the order they were sent. Everything else (keypresses,
+// renders...) goes ahead of them, so that eg. quitting in the middle of a large
+// scan isn't stuck behind all of the entries still waiting to be added
+fn is_scan_data(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::AddEntryToBaseFolder(_)
+            | Instruction::AddUnexpandedEntryToBaseFolder(_)
+            | Instruction::AddMountPointToBaseFolder(_)
+            | Instruction::AddRemoteEntryToBaseFolder(_)
+            | Instruction::IncrementFailedToRead(_)
+            | Instruction::UpdateLastReadPath(_)
+            | Instruction::UpdateCurrentScanDir(_)
+            | Instruction::UpdateProgress(_)
+            | Instruction::UpdateTopLevelCount(_)
+            | Instruction::ScanTimedOut
+            | Instruction::StartUi
+    )
+}
+
+pub fn priority_channel(bound: usize) -> (PrioritySender, PriorityReceiver) {
+    let (high_sender, high_receiver) = mpsc::sync_channel(bound);
+    let (low_sender, low_receiver) = mpsc::sync_channel(bound);
+    (
+        PrioritySender {
+            high: high_sender,
+            low: low_sender,
+        },
+        PriorityReceiver {
+            high: high_receiver,
+            low: low_receiver,
+        },
+    )
+}
+
+#[derive(Clone)]
+pub struct PrioritySender {
+    high: SyncSender<Instruction>,
+    // None is only there to wake up the receiver when something was sent on high
+    low: SyncSender<Option<Instruction>>,
+}
+
+impl PrioritySender {
+    pub fn send(&self, instruction: Instruction) -> Result<(), SendError<Instruction>> {
+        if is_scan_data(&instruction) {
+            return self
+                .low
+                .send(Some(instruction))
+                .map_err(|SendError(instruction)| {
+                    SendError(instruction.expect("sent an instruction"))
+                });
+        }
+        self.high.send(instruction)?;
+        // if the low channel is full, the receiver has plenty to wake up to
+        // and looks at the high one first anyway
+        let _ = self.low.try_send(None);
+        Ok(())
+    }
+}
+
+pub struct PriorityReceiver {
+    high: Receiver<Instruction>,
+    low: Receiver<Option<Instruction>>,
+}
+
+impl PriorityReceiver {
+    pub fn recv(&self) -> Result<Instruction, RecvError> {
+        loop {
+            if let Ok(instruction) = self.high.try_recv() {
+                return Ok(instruction);
+            }
+            match self.low.recv() {
+                Ok(Some(instruction)) => return Ok(instruction),
+                Ok(None) => continue,
+                // the senders of both channels are gone, but something might be left on high
+                Err(RecvError) => return self.high.try_recv().map_err(|_| RecvError),
+            }
+        }
+    }
+    pub fn try_recv(&self) -> Result<Instruction, TryRecvError> {
+        if let Ok(instruction) = self.high.try_recv() {
+            return Ok(instruction);
+        }
+        loop {
+            match self.low.try_recv()? {
+                Some(instruction) => return Ok(instruction),
+                None => continue,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(instruction: Instruction) -> &'static str {
+        match instruction {
+            Instruction::UpdateLastReadPath(_) => "scan data",
+            Instruction::StartUi => "start ui",
+            Instruction::Render => "render",
+            Instruction::ResetUiMode => "reset ui mode",
+            _ => "other",
+        }
+    }
+
+    #[test]
+    fn control_instructions_go_first() {
+        let (sender, receiver) = priority_channel(10);
+        sender
+            .send(Instruction::UpdateLastReadPath("/tmp/a".into()))
+            .unwrap();
+        sender
+            .send(Instruction::UpdateLastReadPath("/tmp/b".into()))
+            .unwrap();
+        sender.send(Instruction::StartUi).unwrap();
+        sender.send(Instruction::ResetUiMode).unwrap();
+        sender.send(Instruction::Render).unwrap();
+        let received: Vec<&str> = (0..5).map(|_| describe(receiver.recv().unwrap())).collect();
+        assert_eq!(
+            received,
+            vec![
+                "reset ui mode",
+                "render",
+                "scan data",
+                "scan data",
+                "start ui"
+            ]
+        );
+        assert!(receiver.try_recv().is_err(), "only wake-ups are left");
+    }
+
+    #[test]
+    fn control_instructions_get_through_a_full_channel() {
+        let (sender, receiver) = priority_channel(2);
+        sender
+            .send(Instruction::UpdateLastReadPath("/tmp/a".into()))
+            .unwrap();
+        sender
+            .send(Instruction::UpdateLastReadPath("/tmp/b".into()))
+            .unwrap();
+        sender.send(Instruction::Render).unwrap();
+        assert_eq!(describe(receiver.try_recv().unwrap()), "render");
+        assert_eq!(describe(receiver.recv().unwrap()), "scan data");
+    }
+
+    #[test]
+    fn nothing_left_once_senders_are_gone() {
+        let (sender, receiver) = priority_channel(10);
+        sender.send(Instruction::Render).unwrap();
+        drop(sender);
+        assert_eq!(describe(receiver.recv().unwrap()), "render");
+        assert!(receiver.recv().is_err());
+    }
+}
@@ -1,5 +1,92 @@
-use nix::unistd::geteuid;
+use std::{
+    env,
+    fs::Metadata,
+    io,
+    os::unix::fs::MetadataExt,
+    path::Path,
+    process::{Command, ExitStatus, Stdio},
+};
+
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use nix::{
+    sys::statvfs::statvfs,
+    unistd::{Gid, Group, Uid, User, geteuid},
+};
 
 pub(crate) fn is_user_admin() -> bool {
     geteuid().is_root()
 }
+
+pub(crate) fn num_hardlinks(metadata: &Metadata) -> Option<u32> {
+    Some(metadata.nlink().min(u32::MAX as u64) as u32)
+}
+
+pub(crate) fn inode(metadata: &Metadata) -> Option<(u64, u64)> {
+    // device and inode, which hard links to the same file share
+    Some((metadata.dev(), metadata.ino()))
+}
+
+pub(crate) fn permission_bits(metadata: &Metadata) -> Option<u32> {
+    Some(metadata.mode())
+}
+
+pub(crate) fn owner(metadata: &Metadata) -> Option<String> {
+    // the name if there's a user by that id, and the id either way
+    let uid = metadata.uid();
+    Some(match User::from_uid(Uid::from_raw(uid)) {
+        Ok(Some(user)) => format!("{} ({})", user.name, uid),
+        _ => uid.to_string(),
+    })
+}
+
+pub(crate) fn group(metadata: &Metadata) -> Option<String> {
+    let gid = metadata.gid();
+    Some(match Group::from_gid(Gid::from_raw(gid)) {
+        Ok(Some(group)) => format!("{} ({})", group.name, gid),
+        _ => gid.to_string(),
+    })
+}
+
+#[cfg(not(test))]
+pub(crate) fn get_disk_space(path: &Path) -> Option<(u64, u64)> {
+    // used and total bytes of the file system the path is on, the way df counts
+    // them: space reserved for root is neither
+    let stats = statvfs(path).ok()?;
+    let fragment_size = stats.fragment_size() as u64;
+    let used = (stats.blocks() as u64 - stats.blocks_free() as u64) * fragment_size;
+    let available = stats.blocks_available() as u64 * fragment_size;
+    Some((used, used + available))
+}
+// so that what's shown doesn't depend on the disk the tests run on
+#[cfg(test)]
+pub(crate) fn get_disk_space(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+pub(crate) fn open_in_pager(path: &Path) -> io::Result<()> {
+    // the pager gets the terminal the way it expects it (not in raw mode)
+    // until it exits, and then we take it back
+    let pager = env::var("PAGER").unwrap_or_else(|_| String::from("less"));
+    disable_raw_mode().map_err(io::Error::other)?;
+    let exited = Command::new(pager).arg(path).status();
+    enable_raw_mode().map_err(io::Error::other)?;
+    exited.map(|_| ())
+}
+
+pub(crate) fn run_shell_command(command: &str, interactive: bool) -> io::Result<ExitStatus> {
+    // an interactive command gets the terminal like the pager does, anything else
+    // runs without it so that it doesn't draw over us
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    if !interactive {
+        shell
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        return shell.status();
+    }
+    disable_raw_mode().map_err(io::Error::other)?;
+    let exited = shell.status();
+    enable_raw_mode().map_err(io::Error::other)?;
+    exited
+}
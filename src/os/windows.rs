@@ -1,11 +1,25 @@
+use std::{
+    env,
+    fs::Metadata,
+    io,
+    path::Path,
+    process::{Command, ExitStatus, Stdio},
+};
+
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+
 #[cfg(not(test))]
 use winapi::um::winnt::{
     DOMAIN_ALIAS_RID_ADMINS, PVOID, SECURITY_BUILTIN_DOMAIN_RID, SECURITY_NT_AUTHORITY,
     SID_IDENTIFIER_AUTHORITY,
 };
 
+#[cfg(not(test))]
+use std::{mem, os::windows::ffi::OsStrExt};
 #[cfg(not(test))]
 use winapi::um::securitybaseapi::{AllocateAndInitializeSid, CheckTokenMembership};
+#[cfg(not(test))]
+use winapi::{shared::ntdef::ULARGE_INTEGER, um::fileapi::GetDiskFreeSpaceExW};
 // https://stackoverflow.com/questions/4230602/detect-if-program-is-running-with-full-administrator-rights
 #[cfg(not(test))]
 pub(crate) fn is_user_admin() -> bool {
@@ -39,3 +53,76 @@ pub(crate) fn is_user_admin() -> bool {
 pub(crate) fn is_user_admin() -> bool {
     false
 }
+
+#[cfg(not(test))]
+pub(crate) fn get_disk_space(path: &Path) -> Option<(u64, u64)> {
+    // used and total bytes of the drive the path is on
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut free_to_caller: ULARGE_INTEGER = unsafe { mem::zeroed() };
+    let mut total: ULARGE_INTEGER = unsafe { mem::zeroed() };
+    let mut free: ULARGE_INTEGER = unsafe { mem::zeroed() };
+    let succeeded = unsafe {
+        GetDiskFreeSpaceExW(path.as_ptr(), &mut free_to_caller, &mut total, &mut free) != 0
+    };
+    if !succeeded {
+        return None;
+    }
+    let (total, free) = unsafe { (*total.QuadPart(), *free.QuadPart()) };
+    Some((total - free, total))
+}
+#[cfg(test)]
+pub(crate) fn get_disk_space(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+// the number of links to a file is not available on stable rust here
+pub(crate) fn num_hardlinks(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+pub(crate) fn inode(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+// files have an access control list here rather than an owner, group and mode
+pub(crate) fn permission_bits(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+pub(crate) fn owner(_metadata: &Metadata) -> Option<String> {
+    None
+}
+
+pub(crate) fn group(_metadata: &Metadata) -> Option<String> {
+    None
+}
+
+pub(crate) fn open_in_pager(path: &Path) -> io::Result<()> {
+    // there's no pager to count on here, so we make do with an editor if there is one
+    let editor = env::var("EDITOR").map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Pager not available on this platform",
+        )
+    })?;
+    disable_raw_mode().map_err(io::Error::other)?;
+    let exited = Command::new(editor).arg(path).status();
+    enable_raw_mode().map_err(io::Error::other)?;
+    exited.map(|_| ())
+}
+
+pub(crate) fn run_shell_command(command: &str, interactive: bool) -> io::Result<ExitStatus> {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    if !interactive {
+        shell
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        return shell.status();
+    }
+    disable_raw_mode().map_err(io::Error::other)?;
+    let exited = shell.status();
+    enable_raw_mode().map_err(io::Error::other)?;
+    exited
+}
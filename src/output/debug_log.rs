@@ -0,0 +1,58 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+// what happened while we ran (every instruction and every error we came across
+// while scanning), for when a scan doesn't look the way it should. Lines are
+// written as they come rather than buffered, so that they're there even if we crash
+#[derive(Clone)]
+pub struct DebugLog {
+    file: Arc<Mutex<File>>,
+}
+
+impl DebugLog {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        // each run starts with an empty log
+        Ok(DebugLog {
+            file: Arc::new(Mutex::new(File::create(path)?)),
+        })
+    }
+    pub fn log(&self, message: fmt::Arguments) {
+        let time = chrono::Local::now().format("%H:%M:%S%.3f");
+        let mut file = self
+            .file
+            .lock()
+            .expect("could not acquire lock on debug log");
+        // there's nowhere to tell about failing to log, so we carry on without it
+        let _ = writeln!(file, "{} {}", time, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn each_run_starts_a_new_log() {
+        let log_path = env::temp_dir().join("diskonaut_debug_log_test.log");
+        fs::write(&log_path, "from the last run\n").expect("failed to create test log");
+        let debug_log = DebugLog::create(&log_path).expect("failed to create debug log");
+        debug_log.log(format_args!("read {}", "/tmp/file"));
+        debug_log
+            .clone()
+            .log(format_args!("failed to read {}", "/tmp/other"));
+        let logged = fs::read_to_string(&log_path).expect("failed to read debug log");
+        let _ = fs::remove_file(&log_path);
+
+        let lines: Vec<&str> = logged.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" read /tmp/file"));
+        assert!(lines[1].ends_with(" failed to read /tmp/other"));
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+#[cfg(not(target_os = "windows"))]
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    thread::park_timeout,
+};
+
+use crate::output::json_lines::json_string;
+
+// how often we check whether the app is still running while waiting on the socket
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// what can be asked about through the socket, the app updates this on every render
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IpcState {
+    pub current_path: PathBuf,
+    pub selected: Option<(String, u128)>, // name and size
+    pub total_size: u128,
+    pub total_descendants: u64,
+}
+
+// the size of a file or folder and, for folders, how many files are in it
+pub type PathSize = (u128, Option<u64>);
+
+fn parse_string_field(line: &str, key: &str) -> Option<String> {
+    // we only ever need a couple of string values, so this is far from a full json parser
+    let (_, after_key) = line.split_once(&format!("\"{}\"", key))?;
+    let value = after_key
+        .trim_start()
+        .strip_prefix(':')?
+        .trim_start()
+        .strip_prefix('"')?;
+    let mut parsed = String::new();
+    let mut chars = value.chars();
+    loop {
+        match chars.next()? {
+            '"' => return Some(parsed),
+            '\\' => parsed.push(chars.next()?),
+            c => parsed.push(c),
+        }
+    }
+}
+
+pub fn respond(
+    line: &str,
+    state: &IpcState,
+    size_at_path: &dyn Fn(&Path) -> Option<PathSize>,
+) -> String {
+    match parse_string_field(line, "cmd").as_deref() {
+        Some("get_current_path") => format!(
+            "{{\"path\":{}}}",
+            json_string(&state.current_path.to_string_lossy())
+        ),
+        Some("get_selected") => match &state.selected {
+            Some((name, size)) => format!("{{\"name\":{},\"size\":{}}}", json_string(name), size),
+            None => String::from("{\"name\":null,\"size\":null}"),
+        },
+        Some("get_total_size") => format!(
+            "{{\"size\":{},\"files\":{}}}",
+            state.total_size, state.total_descendants
+        ),
+        Some("get_size") => match parse_string_field(line, "path") {
+            Some(path) => match size_at_path(Path::new(&path)) {
+                Some((size, files)) => format!(
+                    "{{\"path\":{},\"size\":{},\"files\":{}}}",
+                    json_string(&path),
+                    size,
+                    files.map_or_else(|| String::from("null"), |files| files.to_string())
+                ),
+                None => format!(
+                    "{{\"error\":{}}}",
+                    json_string(&format!("not in the scanned folder: {}", path))
+                ),
+            },
+            None => String::from("{\"error\":\"get_size needs a \\\"path\\\"\"}"),
+        },
+        Some(command) => format!(
+            "{{\"error\":{}}}",
+            json_string(&format!("unknown command: {}", command))
+        ),
+        None => String::from(
+            "{\"error\":\"expected a line like {\\\"cmd\\\":\\\"get_current_path\\\"}\"}",
+        ),
+    }
+}
+
+pub struct IpcServer {
+    #[cfg(not(target_os = "windows"))]
+    listener: UnixListener,
+    socket_path: PathBuf,
+}
+
+impl IpcServer {
+    #[cfg(not(target_os = "windows"))]
+    pub fn bind(socket_path: &Path) -> io::Result<Self> {
+        let listener = UnixListener::bind(socket_path)?;
+        // so that we notice when the app is done even if nobody connects
+        listener.set_nonblocking(true)?;
+        Ok(IpcServer {
+            listener,
+            socket_path: socket_path.to_path_buf(),
+        })
+    }
+    #[cfg(target_os = "windows")]
+    pub fn bind(_socket_path: &Path) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "unix domain sockets are not supported on this platform",
+        ))
+    }
+    #[cfg(not(target_os = "windows"))]
+    pub fn serve(
+        self,
+        state: Arc<Mutex<IpcState>>,
+        running: Arc<AtomicBool>,
+        size_at_path: impl Fn(&Path) -> Option<PathSize>,
+    ) {
+        while running.load(Ordering::Acquire) {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    // a client that misbehaves only loses its own connection
+                    let _ = handle_connection(stream, &state, &running, &size_at_path);
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => park_timeout(POLL_INTERVAL),
+                Err(_) => break,
+            }
+        }
+    }
+    #[cfg(target_os = "windows")]
+    pub fn serve(
+        self,
+        _state: Arc<Mutex<IpcState>>,
+        _running: Arc<AtomicBool>,
+        _size_at_path: impl Fn(&Path) -> Option<PathSize>,
+    ) {
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn handle_connection(
+    stream: UnixStream,
+    state: &Mutex<IpcState>,
+    running: &AtomicBool,
+    size_at_path: &dyn Fn(&Path) -> Option<PathSize>,
+) -> io::Result<()> {
+    // clients are served one at a time, each one for as long as it keeps the connection open
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    while running.load(Ordering::Acquire) {
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                let response = respond(
+                    line.trim(),
+                    &state.lock().expect("could not acquire lock on ipc state"),
+                    size_at_path,
+                );
+                writeln!(writer, "{}", response)?;
+                line.clear();
+            }
+            Err(err)
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> IpcState {
+        IpcState {
+            current_path: PathBuf::from("/home/user/my \"folder\""),
+            selected: Some((String::from("file1"), 4096)),
+            total_size: 8192,
+            total_descendants: 3,
+        }
+    }
+
+    fn size_at_path(path: &Path) -> Option<PathSize> {
+        match path.to_str()? {
+            "/home/user/my \"folder\"" => Some((8192, Some(3))),
+            "/home/user/my \"folder\"/file1" => Some((4096, None)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn responses_to_commands() {
+        let state = state();
+        let respond = |line: &str, state: &IpcState| respond(line, state, &size_at_path);
+        assert_eq!(
+            respond("{\"cmd\":\"get_current_path\"}", &state),
+            "{\"path\":\"/home/user/my \\\"folder\\\"\"}"
+        );
+        assert_eq!(
+            respond("{ \"cmd\" : \"get_selected\" }", &state),
+            "{\"name\":\"file1\",\"size\":4096}"
+        );
+        assert_eq!(
+            respond("{\"cmd\":\"get_total_size\"}", &state),
+            "{\"size\":8192,\"files\":3}"
+        );
+        assert_eq!(
+            respond("{\"cmd\":\"delete_everything\"}", &state),
+            "{\"error\":\"unknown command: delete_everything\"}"
+        );
+        assert!(respond("hello", &state).starts_with("{\"error\":"));
+        assert_eq!(
+            respond(
+                "{\"cmd\":\"get_size\",\"path\":\"/home/user/my \\\"folder\\\"\"}",
+                &state
+            ),
+            "{\"path\":\"/home/user/my \\\"folder\\\"\",\"size\":8192,\"files\":3}"
+        );
+        assert_eq!(
+            respond(
+                "{\"cmd\":\"get_size\",\"path\":\"/home/user/my \\\"folder\\\"/file1\"}",
+                &state
+            ),
+            "{\"path\":\"/home/user/my \\\"folder\\\"/file1\",\"size\":4096,\"files\":null}"
+        );
+        assert_eq!(
+            respond("{\"cmd\":\"get_size\",\"path\":\"/elsewhere\"}", &state),
+            "{\"error\":\"not in the scanned folder: /elsewhere\"}"
+        );
+        assert!(respond("{\"cmd\":\"get_size\"}", &state).starts_with("{\"error\":"));
+        assert_eq!(
+            respond("{\"cmd\":\"get_selected\"}", &IpcState::default()),
+            "{\"name\":null,\"size\":null}"
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn serve_over_socket() {
+        let socket_path = std::env::temp_dir().join("diskonaut_ipc_test.sock");
+        let _ = fs::remove_file(&socket_path);
+        let server = IpcServer::bind(&socket_path).expect("could not bind socket");
+        let state = Arc::new(Mutex::new(state()));
+        let running = Arc::new(AtomicBool::new(true));
+        let server_thread = std::thread::spawn({
+            let running = running.clone();
+            move || server.serve(state, running, size_at_path)
+        });
+
+        let mut stream = UnixStream::connect(&socket_path).expect("could not connect");
+        writeln!(stream, "{{\"cmd\":\"get_total_size\"}}").unwrap();
+        let mut response = String::new();
+        BufReader::new(stream.try_clone().unwrap())
+            .read_line(&mut response)
+            .unwrap();
+        assert_eq!(response, "{\"size\":8192,\"files\":3}\n");
+        drop(stream);
+
+        running.store(false, Ordering::Release);
+        server_thread.join().unwrap();
+        assert!(!socket_path.exists(), "socket is removed once done");
+    }
+}
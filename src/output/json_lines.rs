@@ -0,0 +1,144 @@
+use std::{
+    io::{self, BufWriter, Stdout, Write},
+    path::Path,
+    time::Instant,
+};
+
+use crate::state::{SCAN_PROGRESS_INTERVAL, ScanProgress};
+
+pub fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub struct Writer<W: Write = Stdout> {
+    out: BufWriter<W>,
+    started_at: Instant,
+    progress: ScanProgress,
+    progress_written_at: Instant,
+    error_count: u64,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Writer::from_writer(io::stdout())
+    }
+}
+
+impl<W: Write> Writer<W> {
+    pub fn from_writer(out: W) -> Self {
+        Writer {
+            out: BufWriter::new(out),
+            started_at: Instant::now(),
+            progress: ScanProgress::default(),
+            progress_written_at: Instant::now(),
+            error_count: 0,
+        }
+    }
+    pub fn file(&mut self, path: &Path, size: u128) -> io::Result<()> {
+        self.progress.add_file(size);
+        self.entry(path, size, "file")
+    }
+    pub fn folder(&mut self, path: &Path) -> io::Result<()> {
+        // a folder's size is only known once it was fully scanned,
+        // so we leave summing it up to whoever is reading the stream
+        self.progress.add_directory();
+        self.entry(path, 0, "folder")
+    }
+    pub fn error(&mut self) {
+        self.error_count += 1;
+    }
+    fn entry(&mut self, path: &Path, size: u128, kind: &str) -> io::Result<()> {
+        writeln!(
+            self.out,
+            "{{\"type\":\"entry\",\"path\":{},\"size\":{},\"kind\":\"{}\"}}",
+            json_string(&path.to_string_lossy()),
+            size,
+            kind
+        )?;
+        if self.progress_written_at.elapsed() >= SCAN_PROGRESS_INTERVAL {
+            self.progress_written_at = Instant::now();
+            writeln!(
+                self.out,
+                "{{\"type\":\"progress\",\"files_scanned\":{},\"bytes_scanned\":{},\"directories_entered\":{}}}",
+                self.progress.files_scanned,
+                self.progress.bytes_scanned,
+                self.progress.directories_entered
+            )?;
+        }
+        // so that consumers get entries as they are scanned and not in big chunks
+        self.out.flush()
+    }
+    pub fn summary(mut self) -> io::Result<W> {
+        writeln!(
+            self.out,
+            "{{\"type\":\"summary\",\"total_size\":{},\"file_count\":{},\"directory_count\":{},\"error_count\":{},\"duration_ms\":{}}}",
+            self.progress.bytes_scanned,
+            self.progress.files_scanned,
+            self.progress.directories_entered,
+            self.error_count,
+            self.started_at.elapsed().as_millis()
+        )?;
+        self.out.into_inner().map_err(|err| err.into_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_string_escapes_special_characters() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(
+            json_string("quote\" backslash\\ newline\n bell\u{7}"),
+            "\"quote\\\" backslash\\\\ newline\\n bell\\u0007\""
+        );
+    }
+
+    #[test]
+    fn writer_emits_entries_and_summary() {
+        let mut writer = Writer::from_writer(vec![]);
+        writer.folder(Path::new("/foo")).unwrap();
+        writer.file(Path::new("/foo/bar"), 1234).unwrap();
+        writer.error();
+        let output = String::from_utf8(writer.summary().unwrap()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        // progress is written after every entry in tests, rather than every so often
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            "{\"type\":\"entry\",\"path\":\"/foo\",\"size\":0,\"kind\":\"folder\"}"
+        );
+        assert_eq!(
+            lines[1],
+            "{\"type\":\"progress\",\"files_scanned\":0,\"bytes_scanned\":0,\"directories_entered\":1}"
+        );
+        assert_eq!(
+            lines[2],
+            "{\"type\":\"entry\",\"path\":\"/foo/bar\",\"size\":1234,\"kind\":\"file\"}"
+        );
+        assert_eq!(
+            lines[3],
+            "{\"type\":\"progress\",\"files_scanned\":1,\"bytes_scanned\":1234,\"directories_entered\":1}"
+        );
+        assert!(lines[4].starts_with(
+            "{\"type\":\"summary\",\"total_size\":1234,\"file_count\":1,\"directory_count\":1,\"error_count\":1,\"duration_ms\":"
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{output::json_lines::json_string, ui::format::DisplaySize};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "unknown output format '{}', expected one of: text, json, csv",
+                s
+            )),
+        }
+    }
+}
+
+// keeps the largest files it was given so far, without holding on to all the others
+pub struct LargestFiles {
+    count: usize,
+    smallest_first: BinaryHeap<Reverse<(u128, PathBuf)>>,
+}
+
+impl LargestFiles {
+    pub fn new(count: usize) -> Self {
+        LargestFiles {
+            count,
+            smallest_first: BinaryHeap::with_capacity(count + 1),
+        }
+    }
+    pub fn add(&mut self, path: &Path, size: u128) {
+        if self.count == 0 {
+            return;
+        }
+        if self.smallest_first.len() == self.count {
+            match self.smallest_first.peek() {
+                Some(Reverse((smallest_size, _))) if *smallest_size < size => {
+                    self.smallest_first.pop();
+                }
+                _ => return,
+            }
+        }
+        self.smallest_first
+            .push(Reverse((size, path.to_path_buf())));
+    }
+    pub fn into_sorted(self) -> Vec<(PathBuf, u128)> {
+        // largest first
+        self.smallest_first
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((size, path))| (path, size))
+            .collect()
+    }
+}
+
+pub fn print_largest(
+    largest_files: &[(PathBuf, u128)],
+    output_format: OutputFormat,
+    out: impl Write,
+) -> io::Result<()> {
+    match output_format {
+        OutputFormat::Text => print_text(largest_files, out),
+        OutputFormat::Json => print_json(largest_files, out),
+        OutputFormat::Csv => print_csv(largest_files, out),
+    }
+}
+
+fn print_text(largest_files: &[(PathBuf, u128)], mut out: impl Write) -> io::Result<()> {
+    for (path, size) in largest_files {
+        writeln!(out, "{}\t{}", size, path.to_string_lossy())?;
+    }
+    out.flush()
+}
+
+fn print_json(largest_files: &[(PathBuf, u128)], mut out: impl Write) -> io::Result<()> {
+    let entries: Vec<String> = largest_files
+        .iter()
+        .map(|(path, size)| {
+            format!(
+                "{{\"size\":{},\"path\":{}}}",
+                size,
+                json_string(&path.to_string_lossy())
+            )
+        })
+        .collect();
+    writeln!(out, "[{}]", entries.join(","))?;
+    out.flush()
+}
+
+fn print_csv(largest_files: &[(PathBuf, u128)], out: impl Write) -> io::Result<()> {
+    // paths can have commas and quotes in them, which the csv writer quotes for us
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(["size_bytes", "size_human", "path"])?;
+    for (path, size) in largest_files {
+        writer.write_record([
+            size.to_string(),
+            DisplaySize(*size as f64).to_string(),
+            path.to_string_lossy().into_owned(),
+        ])?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn largest_files() -> Vec<(PathBuf, u128)> {
+        let mut largest_files = LargestFiles::new(2);
+        largest_files.add(Path::new("/tmp/small"), 10);
+        largest_files.add(Path::new("/tmp/large, \"quoted\""), 4096);
+        largest_files.add(Path::new("/tmp/medium"), 2048);
+        largest_files.add(Path::new("/tmp/tiny"), 1);
+        largest_files.into_sorted()
+    }
+
+    fn printed(output_format: OutputFormat) -> String {
+        let mut out = vec![];
+        print_largest(&largest_files(), output_format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn keep_only_the_largest_files() {
+        assert_eq!(
+            largest_files(),
+            vec![
+                (PathBuf::from("/tmp/large, \"quoted\""), 4096),
+                (PathBuf::from("/tmp/medium"), 2048),
+            ]
+        );
+    }
+
+    #[test]
+    fn print_largest_files_in_every_format() {
+        assert_eq!(
+            printed(OutputFormat::Text),
+            "4096\t/tmp/large, \"quoted\"\n2048\t/tmp/medium\n"
+        );
+        assert_eq!(
+            printed(OutputFormat::Json),
+            "[{\"size\":4096,\"path\":\"/tmp/large, \\\"quoted\\\"\"},{\"size\":2048,\"path\":\"/tmp/medium\"}]\n"
+        );
+        assert_eq!(
+            printed(OutputFormat::Csv),
+            "size_bytes,size_human,path\n4096,4.0K,\"/tmp/large, \"\"quoted\"\"\"\n2048,2.0K,/tmp/medium\n"
+        );
+    }
+}
@@ -0,0 +1,8 @@
+pub use shell::*;
+
+pub mod debug_log;
+pub mod ipc;
+pub mod json_lines;
+pub mod largest;
+mod shell;
+pub mod text_report;
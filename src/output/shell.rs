@@ -0,0 +1,32 @@
+#[cfg(not(target_os = "windows"))]
+pub fn shell_quote(path: &str) -> String {
+    // everything inside single quotes is taken literally by the shell
+    // (bash, zsh and fish alike), so the only thing we need to take care of
+    // is a single quote in the path itself: we close the quoted string, add
+    // an escaped quote and open a new quoted string
+    format!("'{}'", path.replace('\'', "'\\''"))
+}
+
+#[cfg(target_os = "windows")]
+pub fn shell_quote(path: &str) -> String {
+    // cmd has nothing like that, but paths can't contain double quotes here
+    format!("\"{}\"", path)
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_quote_plain_path() {
+        assert_eq!(
+            shell_quote("/home/user/my folder"),
+            "'/home/user/my folder'"
+        );
+    }
+
+    #[test]
+    fn shell_quote_path_with_single_quotes() {
+        assert_eq!(shell_quote("/home/user/it's"), "'/home/user/it'\\''s'");
+    }
+}
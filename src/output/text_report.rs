@@ -0,0 +1,195 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::ui::format::{DisplaySize, format_duration_precise};
+
+pub const LARGEST_FILES_IN_REPORT: usize = 10;
+
+// what we know about the session once the app is done, collected just before exiting
+#[derive(Clone, Debug)]
+pub struct AppReport {
+    pub scanned_at: SystemTime,
+    pub scanned_path: PathBuf,
+    pub total_size: u128,
+    pub total_files: u64,
+    pub scan_duration: Option<Duration>, // None if we quit before the scan was done
+    pub deleted: Vec<(PathBuf, u128)>,
+    pub largest_files: Vec<(PathBuf, u128)>,
+}
+
+pub fn write_report(app_state: &AppReport, path: &Path) -> io::Result<()> {
+    fs::write(path, format_report(app_state))
+}
+
+fn format_size(size: u128) -> String {
+    format!("{} ({} bytes)", DisplaySize(size as f64), size)
+}
+
+fn format_report(report: &AppReport) -> String {
+    let mut text = String::new();
+    // writing to a String can't fail, so the results of writeln! are ignored
+    let _ = writeln!(text, "diskonaut report");
+    let _ = writeln!(text);
+    let _ = writeln!(text, "Scanned at:    {}", format_utc(report.scanned_at));
+    let _ = writeln!(
+        text,
+        "Scanned path:  {}",
+        report.scanned_path.to_string_lossy()
+    );
+    let _ = writeln!(text, "Total size:    {}", format_size(report.total_size));
+    let _ = writeln!(text, "Total files:   {}", report.total_files);
+    let _ = writeln!(
+        text,
+        "Scan duration: {}",
+        match report.scan_duration {
+            Some(scan_duration) => format_duration_precise(scan_duration),
+            None => String::from("(scan was not completed)"),
+        }
+    );
+
+    let space_freed: u128 = report.deleted.iter().map(|(_, size)| size).sum();
+    let _ = writeln!(text);
+    let _ = writeln!(
+        text,
+        "Deleted ({} {}, {} freed):",
+        report.deleted.len(),
+        if report.deleted.len() == 1 {
+            "item"
+        } else {
+            "items"
+        },
+        format_size(space_freed)
+    );
+    if report.deleted.is_empty() {
+        let _ = writeln!(text, "  (nothing)");
+    }
+    for (path, size) in &report.deleted {
+        let _ = writeln!(
+            text,
+            "  {:>8}  {}",
+            DisplaySize(*size as f64).to_string(),
+            path.to_string_lossy()
+        );
+    }
+
+    let _ = writeln!(text);
+    let _ = writeln!(text, "Largest remaining files:");
+    if report.largest_files.is_empty() {
+        let _ = writeln!(text, "  (none)");
+    }
+    for (path, size) in &report.largest_files {
+        let _ = writeln!(
+            text,
+            "  {:>8}  {}",
+            DisplaySize(*size as f64).to_string(),
+            path.to_string_lossy()
+        );
+    }
+    text
+}
+
+fn format_utc(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, seconds_of_day) = (seconds / 86400, seconds % 86400);
+    // days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_utc_dates() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29 00:00:00 UTC"
+        );
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(1_791_552_896)),
+            "2026-10-09 13:34:56 UTC"
+        );
+    }
+
+    #[test]
+    fn report_lists_deleted_and_largest_files() {
+        let report = AppReport {
+            scanned_at: UNIX_EPOCH,
+            scanned_path: PathBuf::from("/home/user"),
+            total_size: 12288,
+            total_files: 2,
+            scan_duration: Some(Duration::from_millis(1500)),
+            deleted: vec![(PathBuf::from("/home/user/old.iso"), 16384)],
+            largest_files: vec![
+                (PathBuf::from("/home/user/file1"), 8192),
+                (PathBuf::from("/home/user/file2"), 4096),
+            ],
+        };
+        let text = format_report(&report);
+        assert_eq!(
+            text,
+            "diskonaut report
+
+Scanned at:    1970-01-01 00:00:00 UTC
+Scanned path:  /home/user
+Total size:    12.0K (12288 bytes)
+Total files:   2
+Scan duration: 1.500s
+
+Deleted (1 item, 16.0K (16384 bytes) freed):
+     16.0K  /home/user/old.iso
+
+Largest remaining files:
+      8.0K  /home/user/file1
+      4.0K  /home/user/file2
+"
+        );
+    }
+
+    #[test]
+    fn report_of_unfinished_scan_without_deletions() {
+        let report = AppReport {
+            scanned_at: UNIX_EPOCH,
+            scanned_path: PathBuf::from("/"),
+            total_size: 0,
+            total_files: 0,
+            scan_duration: None,
+            deleted: vec![],
+            largest_files: vec![],
+        };
+        let text = format_report(&report);
+        assert!(text.contains("Scan duration: (scan was not completed)\n"));
+        assert!(text.contains("  (nothing)\n"));
+        assert!(text.contains("  (none)\n"));
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    fs::{self, Metadata},
+    io,
+    path::Path,
+    sync::Arc,
+};
+
+use jwalk::{
+    DirEntry, DirEntryIter, Parallelism,
+    Parallelism::{RayonDefaultPool, RayonExistingPool, Serial},
+    WalkDirGeneric,
+};
+use rayon::ThreadPoolBuilder;
+
+pub use scan_filter::*;
+
+mod scan_filter;
+
+#[cfg(not(test))]
+const SHOULD_SCAN_HD_FILES_IN_MULTIPLE_THREADS: bool = true;
+#[cfg(test)]
+const SHOULD_SCAN_HD_FILES_IN_MULTIPLE_THREADS: bool = false;
+
+// the metadata of each entry is kept from when it was filtered, so that it's only read once
+type ScanState = ((), Option<io::Result<Metadata>>);
+pub type ScanEntry = DirEntry<ScanState>;
+
+// where the metadata of the entries we come across is read from, the filesystem
+// unless the tests say otherwise
+pub trait MetadataProvider: Send + Sync {
+    fn get_metadata(&self, path: &Path) -> io::Result<Metadata>;
+}
+
+impl<F> MetadataProvider for F
+where
+    F: Fn(&Path) -> io::Result<Metadata> + Send + Sync,
+{
+    fn get_metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self(path)
+    }
+}
+
+pub fn filesystem_metadata(follow_links: bool) -> Arc<dyn MetadataProvider> {
+    // unless we follow links, this is about the link itself
+    if follow_links {
+        Arc::new(|path: &Path| fs::metadata(path))
+    } else {
+        Arc::new(|path: &Path| fs::symlink_metadata(path))
+    }
+}
+
+fn parallelism(threads: Option<usize>) -> Parallelism {
+    if !SHOULD_SCAN_HD_FILES_IN_MULTIPLE_THREADS {
+        return Serial;
+    }
+    match threads {
+        None => RayonDefaultPool,
+        Some(0 | 1) => Serial,
+        // if we can't start the threads, we make do with this one
+        Some(threads) => ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_or(Serial, |pool| RayonExistingPool(Arc::new(pool))),
+    }
+}
+
+pub fn scan_folder(path: &Path, scan_filter: ScanFilter) -> DirEntryIter<ScanState> {
+    let metadata_provider = filesystem_metadata(scan_filter.follow_links);
+    scan_folder_with(path, scan_filter, metadata_provider)
+}
+
+pub fn scan_folder_with(
+    path: &Path,
+    scan_filter: ScanFilter,
+    metadata_provider: Arc<dyn MetadataProvider>,
+) -> DirEntryIter<ScanState> {
+    let follow_links = scan_filter.follow_links;
+    let parallelism = parallelism(scan_filter.threads);
+    // what's just past the maximum depth is read too, for the size of the folders at it
+    let max_depth = scan_filter
+        .max_depth
+        .map_or(usize::MAX, |max_depth| max_depth + 1);
+    let scan_filter = Arc::new(scan_filter);
+    WalkDirGeneric::<ScanState>::new(path)
+        .parallelism(parallelism)
+        // hidden files are left out by the scan filter along with everything else
+        .skip_hidden(false)
+        // jwalk tells us about links that lead back to where they are as errors
+        .follow_links(follow_links)
+        .max_depth(max_depth)
+        .process_read_dir(move |_, children| {
+            children.retain_mut(|entry| {
+                let Ok(entry) = entry else {
+                    // errors are counted by whoever reads the entries
+                    return true;
+                };
+                let metadata = match metadata_provider.get_metadata(&entry.path()) {
+                    Ok(metadata) => metadata,
+                    Err(err) => {
+                        // so that whoever reads the entry knows it failed
+                        entry.client_state = Some(Err(err));
+                        return true;
+                    }
+                };
+                let filter_result = scan_filter.should_include(entry, &metadata);
+                if filter_result.skip_subtree {
+                    entry.read_children_path = None;
+                }
+                entry.client_state = Some(Ok(metadata));
+                filter_result.include
+            });
+        })
+        .into_iter()
+}
+
+pub fn count_top_level_entries(path: &Path, skip_hidden: bool) -> Option<usize> {
+    // quick to do before scanning, since we don't go any deeper than that
+    let entries = fs::read_dir(path).ok()?;
+    Some(
+        entries
+            .filter_map(Result::ok)
+            .filter(|entry| !skip_hidden || !entry.file_name().to_string_lossy().starts_with('.'))
+            .count(),
+    )
+}
+
+pub fn entry_metadata(entry: &ScanEntry) -> io::Result<Metadata> {
+    match &entry.client_state {
+        Some(Ok(metadata)) => Ok(metadata.clone()),
+        Some(Err(err)) => Err(io::Error::new(err.kind(), err.to_string())),
+        // the scanned folder itself is not filtered
+        None => entry.metadata().map_err(io::Error::other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use super::*;
+    use crate::tests::fakes::MockFilesystem;
+
+    #[test]
+    fn entries_that_fail_to_read_are_reported_alongside_the_rest() {
+        let test_dir = env::temp_dir().join("diskonaut_scanner_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(test_dir.join("folder")).expect("failed to create test folder");
+        for name in ["file1", "file2", "folder/file3"] {
+            fs::write(test_dir.join(name), [0; 10]).expect("failed to create test file");
+        }
+        let mock_filesystem = MockFilesystem::new()
+            .fail_on(test_dir.join("file2"))
+            .fail_on(test_dir.join("folder/file3"));
+
+        let mut entries: Vec<(PathBuf, bool)> = scan_folder_with(
+            &test_dir,
+            ScanFilter::new(&test_dir),
+            mock_filesystem.metadata_provider(),
+        )
+        .map(|entry| {
+            let entry = entry.expect("failed to read test folder");
+            (entry.path(), entry_metadata(&entry).is_ok())
+        })
+        .collect();
+        entries.sort();
+        let paths = |read: bool| -> Vec<PathBuf> {
+            entries
+                .iter()
+                .filter(|(_, entry_read)| *entry_read == read)
+                .map(|(path, _)| path.clone())
+                .collect()
+        };
+        let _ = fs::remove_dir_all(&test_dir);
+
+        assert_eq!(
+            paths(false),
+            vec![test_dir.join("file2"), test_dir.join("folder/file3")]
+        );
+        assert_eq!(
+            paths(true),
+            vec![
+                test_dir.clone(),
+                test_dir.join("file1"),
+                test_dir.join("folder")
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_are_scanned_through_when_following_them() {
+        let test_dir = env::temp_dir().join("diskonaut_follow_links_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(test_dir.join("folder")).expect("failed to create test folder");
+        fs::write(test_dir.join("folder/file"), [0; 10]).expect("failed to create test file");
+        std::os::unix::fs::symlink(test_dir.join("folder"), test_dir.join("link"))
+            .expect("failed to create link");
+        std::os::unix::fs::symlink(&test_dir, test_dir.join("folder/loop"))
+            .expect("failed to create link");
+
+        let scan = |follow_links: bool| {
+            let scan_filter = ScanFilter::new(&test_dir).follow_links(follow_links);
+            let mut read = vec![];
+            let mut failed = 0;
+            for entry in scan_folder(&test_dir, scan_filter) {
+                match entry {
+                    Ok(entry) => {
+                        read.push(entry.path().strip_prefix(&test_dir).unwrap().to_owned())
+                    }
+                    Err(_) => failed += 1,
+                }
+            }
+            read.sort();
+            (read, failed)
+        };
+        let (read_without_following, _) = scan(false);
+        let (read_following, failed_following) = scan(true);
+        let _ = fs::remove_dir_all(&test_dir);
+
+        assert!(!read_without_following.contains(&PathBuf::from("link/file")));
+        assert!(read_following.contains(&PathBuf::from("link/file")));
+        // the link back to the scanned folder is not followed round and round
+        assert!(failed_following > 0);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fs::{self, Metadata},
+    path::{Path, PathBuf},
+};
+
+use globset::GlobSet;
+
+#[cfg(not(target_os = "windows"))]
+use crate::os::unix::inode;
+#[cfg(target_os = "windows")]
+use crate::os::windows::inode;
+use crate::scanner::ScanEntry;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FilterResult {
+    pub include: bool,
+    pub skip_subtree: bool, // only means something for folders
+}
+
+impl FilterResult {
+    fn included() -> Self {
+        FilterResult {
+            include: true,
+            skip_subtree: false,
+        }
+    }
+    fn excluded() -> Self {
+        // nothing under an excluded folder is included either
+        FilterResult {
+            include: false,
+            skip_subtree: true,
+        }
+    }
+    fn not_entered() -> Self {
+        // the folder itself is shown, but we don't look inside it
+        FilterResult {
+            include: true,
+            skip_subtree: true,
+        }
+    }
+}
+
+fn device(metadata: &Metadata) -> Option<u64> {
+    inode(metadata).map(|(device, _)| device)
+}
+
+// everything that decides which entries are left out of a scan
+#[derive(Clone, Debug)]
+pub struct ScanFilter {
+    pub skip_hidden: bool,
+    pub excluded_paths: Vec<PathBuf>,
+    pub excluded_patterns: GlobSet, // matched against both the full path and the file name
+    pub min_size_bytes: u128,       // smaller files are left out, folders are always kept
+    pub one_file_system: bool,
+    pub root_device: Option<u64>, // the device the scanned folder is on
+    pub max_depth: Option<usize>, // entries one level deeper are only read for their size
+    pub follow_links: bool,       // what links point to is scanned as if it was where they are
+    pub threads: Option<usize>,   // to scan with, as many as there are CPUs if None
+}
+
+impl ScanFilter {
+    pub fn new(root: &Path) -> Self {
+        ScanFilter {
+            skip_hidden: false,
+            excluded_paths: vec![],
+            excluded_patterns: GlobSet::empty(),
+            min_size_bytes: 0,
+            one_file_system: false,
+            root_device: fs::metadata(root).ok().as_ref().and_then(device),
+            max_depth: None,
+            follow_links: false,
+            threads: None,
+        }
+    }
+    pub fn skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.skip_hidden = skip_hidden;
+        self
+    }
+    pub fn excluded_patterns(mut self, excluded_patterns: GlobSet) -> Self {
+        self.excluded_patterns = excluded_patterns;
+        self
+    }
+    pub fn min_size_bytes(mut self, min_size_bytes: u128) -> Self {
+        self.min_size_bytes = min_size_bytes;
+        self
+    }
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.follow_links = follow_links;
+        self
+    }
+    pub fn threads(mut self, threads: Option<usize>) -> Self {
+        self.threads = threads;
+        self
+    }
+    pub fn one_file_system(mut self, one_file_system: bool) -> Self {
+        self.one_file_system = one_file_system;
+        self
+    }
+    pub fn is_mount_point(&self, metadata: &Metadata) -> bool {
+        // a folder on another file system than the scanned one, which we stay on
+        self.one_file_system
+            && metadata.is_dir()
+            && self.root_device.is_some()
+            && device(metadata) != self.root_device
+    }
+    pub fn is_past_max_depth(&self, depth: usize) -> bool {
+        self.max_depth.is_some_and(|max_depth| depth > max_depth)
+    }
+    pub fn should_include(&self, entry: &ScanEntry, metadata: &Metadata) -> FilterResult {
+        self.should_include_path(&entry.path(), metadata)
+    }
+    fn should_include_path(&self, path: &Path, metadata: &Metadata) -> FilterResult {
+        let file_name = path.file_name().unwrap_or_default();
+        if self.skip_hidden && file_name.to_string_lossy().starts_with('.') {
+            return FilterResult::excluded();
+        }
+        if self
+            .excluded_paths
+            .iter()
+            .any(|excluded_path| path.starts_with(excluded_path))
+        {
+            return FilterResult::excluded();
+        }
+        if !self.excluded_patterns.is_empty()
+            && (self.excluded_patterns.is_match(path) || self.excluded_patterns.is_match(file_name))
+        {
+            return FilterResult::excluded();
+        }
+        if self.is_mount_point(metadata) {
+            return FilterResult::not_entered();
+        }
+        if !metadata.is_dir() && (metadata.len() as u128) < self.min_size_bytes {
+            return FilterResult::excluded();
+        }
+        FilterResult::included()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use globset::{Glob, GlobSetBuilder};
+
+    use super::*;
+
+    fn scan_filter_test_dir() -> PathBuf {
+        let test_dir = env::temp_dir().join("diskonaut_scan_filter_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(test_dir.join("folder")).expect("failed to create test folder");
+        fs::write(test_dir.join("small.log"), [0; 10]).expect("failed to create test file");
+        fs::write(test_dir.join("large"), [0; 4096]).expect("failed to create test file");
+        fs::write(test_dir.join(".hidden"), [0; 4096]).expect("failed to create test file");
+        test_dir
+    }
+
+    fn included(scan_filter: &ScanFilter, path: &Path) -> bool {
+        let metadata = fs::metadata(path).expect("failed to read test file metadata");
+        scan_filter.should_include_path(path, &metadata).include
+    }
+
+    #[test]
+    fn hidden_files() {
+        let test_dir = scan_filter_test_dir();
+        let scan_filter = ScanFilter::new(&test_dir);
+        assert!(included(&scan_filter, &test_dir.join(".hidden")));
+        let scan_filter = scan_filter.skip_hidden(true);
+        assert!(!included(&scan_filter, &test_dir.join(".hidden")));
+        assert!(included(&scan_filter, &test_dir.join("large")));
+    }
+
+    #[test]
+    fn excluded_paths_and_patterns() {
+        let test_dir = scan_filter_test_dir();
+        let mut scan_filter = ScanFilter::new(&test_dir);
+        scan_filter.excluded_paths = vec![test_dir.join("folder")];
+        scan_filter.excluded_patterns = GlobSetBuilder::new()
+            .add(Glob::new("*.log").unwrap())
+            .build()
+            .unwrap();
+        let metadata = fs::metadata(test_dir.join("folder")).unwrap();
+        assert_eq!(
+            scan_filter.should_include_path(&test_dir.join("folder"), &metadata),
+            FilterResult {
+                include: false,
+                skip_subtree: true,
+            }
+        );
+        assert!(!included(&scan_filter, &test_dir.join("small.log")));
+        assert!(included(&scan_filter, &test_dir.join("large")));
+    }
+
+    #[test]
+    fn patterns_match_names_and_whole_paths() {
+        let test_dir = scan_filter_test_dir();
+        let folder = test_dir.join("folder").to_string_lossy().into_owned();
+        let mut patterns = GlobSetBuilder::new();
+        for pattern in ["large", "**/.hidden", folder.as_str()] {
+            patterns.add(Glob::new(pattern).unwrap());
+        }
+        let scan_filter = ScanFilter::new(&test_dir).excluded_patterns(patterns.build().unwrap());
+        assert!(!included(&scan_filter, &test_dir.join("large")));
+        assert!(!included(&scan_filter, &test_dir.join(".hidden")));
+        assert!(!included(&scan_filter, &test_dir.join("folder")));
+        assert!(included(&scan_filter, &test_dir.join("small.log")));
+    }
+
+    #[test]
+    fn min_size_leaves_out_files_only() {
+        let test_dir = scan_filter_test_dir();
+        let mut scan_filter = ScanFilter::new(&test_dir);
+        scan_filter.min_size_bytes = 1024;
+        assert!(!included(&scan_filter, &test_dir.join("small.log")));
+        assert!(included(&scan_filter, &test_dir.join("large")));
+        assert!(included(&scan_filter, &test_dir.join("folder")));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn one_file_system() {
+        let test_dir = scan_filter_test_dir();
+        let mut scan_filter = ScanFilter::new(&test_dir);
+        scan_filter.one_file_system = true;
+        assert!(included(&scan_filter, &test_dir.join("folder")));
+        // as if the folder was mounted from somewhere else
+        scan_filter.root_device = scan_filter.root_device.map(|device| device + 1);
+        let metadata = fs::metadata(test_dir.join("folder")).unwrap();
+        assert_eq!(
+            scan_filter.should_include_path(&test_dir.join("folder"), &metadata),
+            FilterResult {
+                include: true,
+                skip_subtree: true,
+            }
+        );
+        assert!(scan_filter.is_mount_point(&metadata));
+        assert!(included(&scan_filter, &test_dir.join("large")));
+    }
+}
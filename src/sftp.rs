@@ -0,0 +1,282 @@
+#[cfg(not(feature = "sftp"))]
+use std::convert::Infallible;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+#[cfg(feature = "sftp")]
+use std::{
+    net::TcpStream,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+#[cfg(feature = "sftp")]
+use ssh2::{Session, Sftp};
+
+pub const SFTP_PREFIX: &str = "sftp://";
+const DEFAULT_SSH_PORT: u16 = 22;
+
+// where to scan on the remote host, parsed from sftp://[user@]host[:port]:/path
+#[derive(Clone, Debug, PartialEq)]
+pub struct SftpLocation {
+    pub user: String,
+    pub host: String,
+    pub port: u16,
+    pub path: PathBuf,
+}
+
+impl SftpLocation {
+    pub fn parse(uri: &str) -> anyhow::Result<Self> {
+        let rest = uri
+            .strip_prefix(SFTP_PREFIX)
+            .context("expected it to start with sftp://")?;
+        // both sftp://host:/path (like scp) and sftp://host/path work
+        let path_start = rest
+            .find('/')
+            .context("missing the path to scan (eg. sftp://user@host:/path)")?;
+        let (authority, path) = rest.split_at(path_start);
+        let authority = authority.strip_suffix(':').unwrap_or(authority);
+        let (user, host_and_port) = match authority.rsplit_once('@') {
+            Some((user, host_and_port)) => (user.to_string(), host_and_port),
+            None => (
+                // like ssh, we log in with the local user name unless told otherwise
+                env::var("USER")
+                    .or_else(|_| env::var("USERNAME"))
+                    .context("missing the user to log in as (eg. sftp://user@host:/path)")?,
+                authority,
+            ),
+        };
+        let (host, port) = match host_and_port.split_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .with_context(|| format!("invalid port '{}'", port))?,
+            ),
+            None => (host_and_port, DEFAULT_SSH_PORT),
+        };
+        if host.is_empty() {
+            anyhow::bail!("missing the host to connect to");
+        }
+        Ok(SftpLocation {
+            user,
+            host: host.to_string(),
+            port,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoteEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u128, // what the server reports, which is always the apparent size
+    pub modified: Option<SystemTime>,
+}
+
+#[cfg(feature = "sftp")]
+pub struct SftpConnection {
+    location: SftpLocation,
+    authenticated_with: &'static str,
+    sftp: Mutex<Sftp>,
+    // the sftp channel can only be used as long as the session it was opened on is around
+    _session: Session,
+}
+
+#[cfg(feature = "sftp")]
+impl SftpConnection {
+    pub fn connect(location: SftpLocation, identity: Option<&Path>) -> anyhow::Result<Self> {
+        let tcp_stream = TcpStream::connect((location.host.as_str(), location.port))
+            .with_context(|| format!("could not reach {}:{}", location.host, location.port))?;
+        let mut session = Session::new().context("could not start an ssh session")?;
+        session.set_tcp_stream(tcp_stream);
+        session.handshake().context("ssh handshake failed")?;
+        let authenticated_with = if session.userauth_agent(&location.user).is_ok() {
+            "ssh agent"
+        } else if let Some(identity) = identity {
+            session
+                .userauth_pubkey_file(&location.user, None, identity, None)
+                .with_context(|| {
+                    format!(
+                        "could not log in as '{}' with '{}'",
+                        location.user,
+                        identity.to_string_lossy()
+                    )
+                })?;
+            "identity file"
+        } else {
+            anyhow::bail!(
+                "could not log in as '{}' with the ssh agent, try --ssh-identity",
+                location.user
+            );
+        };
+        if !session.authenticated() {
+            anyhow::bail!("the server did not accept the login of '{}'", location.user);
+        }
+        let sftp = session
+            .sftp()
+            .context("could not start sftp on the remote host")?;
+        Ok(SftpConnection {
+            location,
+            authenticated_with,
+            sftp: Mutex::new(sftp),
+            _session: session,
+        })
+    }
+    pub fn root(&self) -> &Path {
+        &self.location.path
+    }
+    pub fn user_and_host(&self) -> String {
+        format!("{}@{}", self.location.user, self.location.host)
+    }
+    pub fn authenticated_with(&self) -> &'static str {
+        self.authenticated_with
+    }
+    fn sftp(&self) -> MutexGuard<'_, Sftp> {
+        self.sftp
+            .lock()
+            .expect("could not acquire lock on sftp connection")
+    }
+    pub fn walk(
+        &self,
+        path: &Path,
+        skip_hidden: bool,
+        mut on_entry: impl FnMut(Result<RemoteEntry, ssh2::Error>) -> bool,
+    ) {
+        // every entry comes before what's inside it (the same order jwalk uses),
+        // so a folder is never added to the tree after its contents.
+        // the folder we start from is not an entry itself.
+        // stops as soon as on_entry returns false
+        let mut folders_to_read = vec![path.to_path_buf()];
+        while let Some(folder) = folders_to_read.pop() {
+            let entries = self.sftp().readdir(&folder);
+            match entries {
+                Ok(entries) => {
+                    for (entry_path, stat) in entries {
+                        let is_hidden = entry_path
+                            .file_name()
+                            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+                        if skip_hidden && is_hidden {
+                            continue;
+                        }
+                        // symlinks are not followed, they count as the small files they are
+                        let is_dir = stat.is_dir();
+                        if is_dir {
+                            folders_to_read.push(entry_path.clone());
+                        }
+                        let entry = RemoteEntry {
+                            path: entry_path,
+                            is_dir,
+                            size: if is_dir {
+                                0
+                            } else {
+                                stat.size.unwrap_or(0) as u128
+                            },
+                            modified: stat
+                                .mtime
+                                .map(|mtime| UNIX_EPOCH + Duration::from_secs(mtime)),
+                        };
+                        if !on_entry(Ok(entry)) {
+                            return;
+                        }
+                    }
+                }
+                Err(err) => {
+                    if !on_entry(Err(err)) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+    pub fn remove(&self, path: &Path) -> Result<(), ssh2::Error> {
+        let stat = self.sftp().lstat(path)?;
+        if stat.is_dir() {
+            // sftp can only remove empty folders
+            let entries = self.sftp().readdir(path)?;
+            for (entry_path, _) in entries {
+                self.remove(&entry_path)?;
+            }
+            self.sftp().rmdir(path)
+        } else {
+            self.sftp().unlink(path)
+        }
+    }
+}
+
+// built without ssh, there is nothing to connect with and so never a connection
+#[cfg(not(feature = "sftp"))]
+pub struct SftpConnection {
+    never: Infallible,
+}
+
+#[cfg(not(feature = "sftp"))]
+impl SftpConnection {
+    pub fn connect(_location: SftpLocation, _identity: Option<&Path>) -> anyhow::Result<Self> {
+        anyhow::bail!("this diskonaut was built without sftp support (the \"sftp\" feature)")
+    }
+    pub fn root(&self) -> &Path {
+        match self.never {}
+    }
+    pub fn user_and_host(&self) -> String {
+        match self.never {}
+    }
+    pub fn authenticated_with(&self) -> &'static str {
+        match self.never {}
+    }
+    pub fn walk(
+        &self,
+        _path: &Path,
+        _skip_hidden: bool,
+        _on_entry: impl FnMut(Result<RemoteEntry, Infallible>) -> bool,
+    ) {
+        match self.never {}
+    }
+    pub fn remove(&self, _path: &Path) -> Result<(), Infallible> {
+        match self.never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sftp_locations() {
+        assert_eq!(
+            SftpLocation::parse("sftp://admin@example.com:/var/log").unwrap(),
+            SftpLocation {
+                user: String::from("admin"),
+                host: String::from("example.com"),
+                port: 22,
+                path: PathBuf::from("/var/log"),
+            }
+        );
+        assert_eq!(
+            SftpLocation::parse("sftp://admin@example.com:2222/").unwrap(),
+            SftpLocation {
+                user: String::from("admin"),
+                host: String::from("example.com"),
+                port: 2222,
+                path: PathBuf::from("/"),
+            }
+        );
+        assert_eq!(
+            SftpLocation::parse("sftp://admin@10.0.0.1:2222:/srv")
+                .unwrap()
+                .port,
+            2222
+        );
+    }
+
+    #[test]
+    fn reject_invalid_sftp_locations() {
+        assert!(SftpLocation::parse("/home/user").is_err());
+        assert!(SftpLocation::parse("sftp://admin@example.com").is_err());
+        assert!(SftpLocation::parse("sftp://admin@:/srv").is_err());
+        assert!(SftpLocation::parse("sftp://admin@example.com:ssh/srv").is_err());
+    }
+}
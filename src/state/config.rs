@@ -0,0 +1,160 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{output::shell_quote, state::toml};
+
+pub fn config_dir() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(config_dir) => PathBuf::from(config_dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("diskonaut"))
+}
+
+// a key bound to a shell command in the config file, run on the selected file or folder
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalCommand {
+    pub key: char,
+    pub template: String,  // {path} is replaced with the selected path
+    pub interactive: bool, // gets the terminal to itself until it exits
+}
+
+pub fn expand_command(template: &str, path: &Path) -> String {
+    template.replace("{path}", &shell_quote(&path.to_string_lossy()))
+}
+
+// keys that always do what they do, since bindings are checked before the built-in ones
+const RESERVED_KEYS: [char; 2] = ['q', '?'];
+
+fn is_reserved_key(key: char) -> bool {
+    // control characters would be eg. ctrl+c or ENTER
+    RESERVED_KEYS.contains(&key) || key.is_control()
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub external_commands: Vec<ExternalCommand>,
+}
+
+fn parse_external_command(key: &str, value: &str) -> Option<ExternalCommand> {
+    // either 'o' = "xdg-open {path}"
+    // or 'e' = { command = "vim {path}", interactive = true }
+    let mut chars = key.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(key), None) => key,
+        _ => return None,
+    };
+    if let Some((template, _)) = toml::parse_string(value) {
+        return Some(ExternalCommand {
+            key,
+            template,
+            interactive: false,
+        });
+    }
+    let mut fields = value.strip_prefix('{')?.strip_suffix('}')?.trim();
+    let mut template = None;
+    let mut interactive = false;
+    while !fields.is_empty() {
+        let (name, rest) = fields.split_once('=')?;
+        let rest = rest.trim_start();
+        fields = match name.trim() {
+            "command" => {
+                let (command, rest) = toml::parse_string(rest)?;
+                template = Some(command);
+                rest
+            }
+            "interactive" => {
+                let (value, rest) = toml::parse_bool(rest)?;
+                interactive = value;
+                rest
+            }
+            _ => return None,
+        };
+        let rest = fields.trim_start();
+        fields = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    Some(ExternalCommand {
+        key,
+        template: template?,
+        interactive,
+    })
+}
+
+impl Config {
+    pub fn load() -> Self {
+        // a missing config file is the same as an empty one
+        config_dir()
+            .and_then(|config_dir| fs::read_to_string(config_dir.join("config.toml")).ok())
+            .map(|contents| Config::from_toml(&contents))
+            .unwrap_or_default()
+    }
+    fn from_toml(contents: &str) -> Self {
+        let mut config = Config::default();
+        for (section, key, value) in toml::key_values(contents) {
+            if section == "keybindings"
+                && let Some(external_command) = parse_external_command(&key, value)
+                && !is_reserved_key(external_command.key)
+            {
+                // the last binding for a key wins
+                config
+                    .external_commands
+                    .retain(|bound| bound.key != external_command.key);
+                config.external_commands.push(external_command);
+            }
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keybindings_from_toml() {
+        let config = Config::from_toml(
+            "# my bindings\n\
+             [other]\n\
+             'x' = \"not a keybinding\"\n\
+             [keybindings]\n\
+             'o' = \"xdg-open {path}\"\n\
+             e = { command = \"vim {path}\", interactive = true }\n\
+             \"m\" = { interactive = false, command = 'mv {path} ~/archive' }\n\
+             'long' = \"ignored\"\n\
+             'q' = \"reserved\"\n\
+             \"\u{3}\" = \"reserved\"\n\
+             'b' = { command = \"broken\"\n",
+        );
+        assert_eq!(
+            config.external_commands,
+            vec![
+                ExternalCommand {
+                    key: 'o',
+                    template: String::from("xdg-open {path}"),
+                    interactive: false,
+                },
+                ExternalCommand {
+                    key: 'e',
+                    template: String::from("vim {path}"),
+                    interactive: true,
+                },
+                ExternalCommand {
+                    key: 'm',
+                    template: String::from("mv {path} ~/archive"),
+                    interactive: false,
+                },
+            ]
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn paths_are_quoted_in_commands() {
+        assert_eq!(
+            expand_command("xdg-open {path}", Path::new("/tmp/it's here")),
+            "xdg-open '/tmp/it'\\''s here'"
+        );
+    }
+}
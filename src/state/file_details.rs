@@ -0,0 +1,81 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use filesize::PathExt;
+
+#[cfg(not(target_os = "windows"))]
+use crate::os::unix::{group, inode, num_hardlinks, owner, permission_bits};
+#[cfg(target_os = "windows")]
+use crate::os::windows::{group, inode, num_hardlinks, owner, permission_bits};
+
+// what's shown with 'i' about the selected file or folder, read once when it's opened
+#[derive(Clone, Debug)]
+pub struct FileDetails {
+    pub path: PathBuf, // relative to the scanned folder
+    pub is_dir: bool,
+    pub size: Option<(u64, u64)>, // apparent and on disk, only for files
+    pub permissions: Option<String>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub inode: Option<u64>,
+    pub hard_links: Option<u32>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    pub created: Option<SystemTime>, // not every file system keeps this
+}
+
+impl FileDetails {
+    pub fn read(path_in_filesystem: &Path, path: PathBuf) -> io::Result<Self> {
+        let metadata = fs::metadata(path_in_filesystem)?;
+        let size = if metadata.is_dir() {
+            None
+        } else {
+            let on_disk = path_in_filesystem
+                .size_on_disk_fast(&metadata)
+                .unwrap_or(metadata.len());
+            Some((metadata.len(), on_disk))
+        };
+        Ok(FileDetails {
+            path,
+            is_dir: metadata.is_dir(),
+            size,
+            permissions: permission_bits(&metadata).map(format_permissions),
+            owner: owner(&metadata),
+            group: group(&metadata),
+            inode: inode(&metadata).map(|(_device, inode)| inode),
+            hard_links: num_hardlinks(&metadata),
+            modified: metadata.modified().ok(),
+            accessed: metadata.accessed().ok(),
+            created: metadata.created().ok(),
+        })
+    }
+}
+
+pub fn format_permissions(mode: u32) -> String {
+    // rwxrwxrwx for the owner, the group and everyone else, the way ls -l shows them
+    let letters = ['r', 'w', 'x'];
+    (0..9)
+        .map(|bit| {
+            if mode & (0o400 >> bit) != 0 {
+                letters[bit % 3]
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_in_rwx_notation() {
+        assert_eq!(format_permissions(0o755), "rwxr-xr-x");
+        assert_eq!(format_permissions(0o100640), "rw-r-----");
+        assert_eq!(format_permissions(0), "---------");
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use tui::layout::Rect;
+
+use crate::state::{
+    files::{FileOrFolder, FileTree, Folder},
+    layout::LayoutAlgorithm,
+    tiles::{FileMetadata, FileType, Tile},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Grown(u128), // by this many bytes
+    Shrunk(u128),
+    Unchanged,
+}
+
+impl ChangeKind {
+    fn between(before: Option<u128>, after: Option<u128>) -> Self {
+        match (before, after) {
+            (None, _) => ChangeKind::Added,
+            (Some(_), None) => ChangeKind::Removed,
+            (Some(before), Some(after)) if after > before => ChangeKind::Grown(after - before),
+            (Some(before), Some(after)) if after < before => ChangeKind::Shrunk(before - after),
+            _ => ChangeKind::Unchanged,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DiffTile {
+    pub tile: Tile,
+    pub change: ChangeKind,
+}
+
+pub struct DiffFileTree<'a> {
+    pub before: &'a FileTree,
+    pub after: &'a FileTree,
+    pub before_scanned_at: SystemTime,
+    pub after_scanned_at: SystemTime,
+    pub layout_algorithm: LayoutAlgorithm,
+    area: Rect,
+}
+
+impl<'a> DiffFileTree<'a> {
+    pub fn new(
+        before: &'a FileTree,
+        after: &'a FileTree,
+        before_scanned_at: SystemTime,
+        after_scanned_at: SystemTime,
+    ) -> Self {
+        DiffFileTree {
+            before,
+            after,
+            before_scanned_at,
+            after_scanned_at,
+            layout_algorithm: LayoutAlgorithm::default(),
+            area: Rect::new(0, 0, 0, 0),
+        }
+    }
+    pub fn change_area(&mut self, area: &Rect) {
+        self.area = *area;
+    }
+    pub fn get_current_path(&self) -> PathBuf {
+        self.after.get_current_path()
+    }
+    pub fn net_size_change(&self) -> i128 {
+        self.after.get_total_size() as i128 - self.before.get_total_size() as i128
+    }
+    pub fn get_diff_tiles(&self, path: &Path) -> Vec<DiffTile> {
+        // the folder as it is in both scans: what was removed keeps the size it had
+        // so that it shows how much space it freed, everything else is as it is now
+        let before = self.before.folder_at_path(path);
+        let after = self.after.folder_at_path(path);
+        let mut sizes: HashMap<&OsString, (Option<u128>, Option<u128>)> = HashMap::new();
+        for (name, item) in before.into_iter().flat_map(|folder| &folder.contents) {
+            sizes.entry(name).or_default().0 = Some(item.size());
+        }
+        for (name, item) in after.into_iter().flat_map(|folder| &folder.contents) {
+            sizes.entry(name).or_default().1 = Some(item.size());
+        }
+        let total_size: u128 = sizes
+            .values()
+            .map(|(before_size, after_size)| after_size.or(*before_size).unwrap_or(0))
+            .sum();
+        let mut files: Vec<FileMetadata> = sizes
+            .iter()
+            .map(|(name, (before_size, after_size))| {
+                let item = after
+                    .and_then(|folder| folder.contents.get(*name))
+                    .or_else(|| before.and_then(|folder| folder.contents.get(*name)))
+                    .expect("could not find item in either scan");
+                diff_file_metadata(item, after_size.or(*before_size).unwrap_or(0), total_size)
+            })
+            .collect();
+        files.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| a.name.partial_cmp(&b.name).expect("could not compare name"))
+        });
+        let (tiles, _) = self.layout_algorithm.layout(&files, &self.area);
+        tiles
+            .into_iter()
+            .map(|tile| {
+                let (before_size, after_size) = sizes[&tile.name];
+                DiffTile {
+                    change: ChangeKind::between(before_size, after_size),
+                    tile,
+                }
+            })
+            .collect()
+    }
+}
+
+fn diff_file_metadata(item: &FileOrFolder, size: u128, total_size: u128) -> FileMetadata {
+    let (name, descendants, file_type, num_hardlinks, unexpanded, mount_point) = match item {
+        FileOrFolder::Folder(Folder {
+            name,
+            num_descendants,
+            unexpanded,
+            mount_point,
+            ..
+        }) => (
+            name.clone(),
+            Some(*num_descendants),
+            FileType::Folder,
+            None,
+            *unexpanded,
+            *mount_point,
+        ),
+        FileOrFolder::File(file) => (
+            file.name.clone(),
+            None,
+            FileType::File,
+            file.num_hardlinks,
+            false,
+            false,
+        ),
+    };
+    FileMetadata {
+        name,
+        size,
+        descendants,
+        percentage: if total_size == 0 {
+            0.0
+        } else {
+            size as f64 / total_size as f64
+        },
+        file_type,
+        zero_byte_files: None,
+        modified: item.modified(),
+        num_hardlinks,
+        unexpanded,
+        mount_point,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_tree(files: &[(&str, u128)]) -> FileTree {
+        let path_in_filesystem = PathBuf::from("/tmp/diskonaut_diff_test");
+        let mut base_folder = Folder::new(&path_in_filesystem);
+        for (name, size) in files {
+            base_folder.add_file(PathBuf::from(name), *size);
+        }
+        FileTree::new(base_folder, path_in_filesystem, false)
+    }
+
+    #[test]
+    fn tiles_show_what_changed_between_scans() {
+        let before = file_tree(&[
+            ("kept", 4096),
+            ("grew", 4096),
+            ("shrank", 8192),
+            ("gone", 2048),
+        ]);
+        let after = file_tree(&[
+            ("kept", 4096),
+            ("grew", 6144),
+            ("shrank", 1024),
+            ("new", 1024),
+        ]);
+        let mut diff_tree =
+            DiffFileTree::new(&before, &after, SystemTime::UNIX_EPOCH, SystemTime::now());
+        diff_tree.change_area(&Rect::new(0, 0, 100, 40));
+        let changes: HashMap<String, ChangeKind> = diff_tree
+            .get_diff_tiles(&after.path_in_filesystem)
+            .into_iter()
+            .map(|diff_tile| (diff_tile.tile.name.into_string().unwrap(), diff_tile.change))
+            .collect();
+        assert_eq!(changes["kept"], ChangeKind::Unchanged);
+        assert_eq!(changes["grew"], ChangeKind::Grown(2048));
+        assert_eq!(changes["shrank"], ChangeKind::Shrunk(7168));
+        assert_eq!(changes["gone"], ChangeKind::Removed);
+        assert_eq!(changes["new"], ChangeKind::Added);
+        assert_eq!(diff_tree.net_size_change(), 12288 - 18432);
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    hash::{DefaultHasher, Hasher},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+// groups of files with the same content, out of files that were grouped by size
+// (see FileTree::duplicate_candidates). The groups that take up the most space
+// for nothing come first. Files are read from the disk, so this takes a while
+pub fn find_duplicates(same_size_files: Vec<(u128, Vec<PathBuf>)>) -> Vec<Vec<PathBuf>> {
+    let mut duplicates: Vec<(u128, Vec<PathBuf>)> = vec![];
+    for (size, paths) in same_size_files {
+        // the hash tells apart most files without reading them side by side
+        let mut paths_by_hash: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for path in paths {
+            // files we can't read can't be told to be duplicates either
+            if let Ok(hash) = content_hash(&path) {
+                paths_by_hash.entry(hash).or_default().push(path);
+            }
+        }
+        for same_hash in paths_by_hash.into_values() {
+            for group in identical_groups(same_hash) {
+                if group.len() > 1 {
+                    let wasted = size * (group.len() as u128 - 1);
+                    duplicates.push((wasted, group));
+                }
+            }
+        }
+    }
+    for (_, group) in duplicates.iter_mut() {
+        group.sort();
+    }
+    duplicates.sort_by(|(wasted_a, group_a), (wasted_b, group_b)| {
+        wasted_b.cmp(wasted_a).then_with(|| group_a.cmp(group_b))
+    });
+    duplicates.into_iter().map(|(_, group)| group).collect()
+}
+
+fn content_hash(path: &Path) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0; READ_CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.write(&buffer[..read]);
+    }
+}
+
+fn identical_groups(paths: Vec<PathBuf>) -> Vec<Vec<PathBuf>> {
+    // files with the same hash are almost always the same, but we make sure of it
+    let mut groups: Vec<Vec<PathBuf>> = vec![];
+    for path in paths {
+        match groups
+            .iter_mut()
+            .find(|group| same_content(&group[0], &path).unwrap_or(false))
+        {
+            Some(group) => group.push(path),
+            None => groups.push(vec![path]),
+        }
+    }
+    groups
+}
+
+fn same_content(a: &Path, b: &Path) -> io::Result<bool> {
+    let (mut file_a, mut file_b) = (File::open(a)?, File::open(b)?);
+    let mut buffer_a = vec![0; READ_CHUNK_SIZE];
+    let mut buffer_b = vec![0; READ_CHUNK_SIZE];
+    loop {
+        let read_a = read_chunk(&mut file_a, &mut buffer_a)?;
+        let read_b = read_chunk(&mut file_b, &mut buffer_b)?;
+        if buffer_a[..read_a] != buffer_b[..read_b] {
+            return Ok(false);
+        }
+        if read_a == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+fn read_chunk(file: &mut File, buffer: &mut [u8]) -> io::Result<usize> {
+    // as much of the buffer as the file fills, so that both files are compared
+    // chunk for chunk however their reads happen to be split up
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let read = file.read(&mut buffer[filled..])?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn files_with_the_same_content_are_grouped() {
+        let test_dir = env::temp_dir().join("diskonaut_duplicates_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(test_dir.join("folder")).expect("failed to create test folder");
+        let files = [
+            ("small", vec![1; 10]),
+            ("folder/small_copy", vec![1; 10]),
+            ("small_different", vec![2; 10]),
+            ("large", vec![3; 100_000]),
+            ("large_copy", vec![3; 100_000]),
+            ("folder/large_copy", vec![3; 100_000]),
+        ];
+        for (name, content) in &files {
+            fs::write(test_dir.join(name), content).expect("failed to create test file");
+        }
+        let paths = |names: &[&str]| -> Vec<PathBuf> {
+            names.iter().map(|name| test_dir.join(name)).collect()
+        };
+
+        let duplicates = find_duplicates(vec![
+            (
+                10,
+                paths(&["small", "small_different", "folder/small_copy"]),
+            ),
+            (
+                100_000,
+                paths(&["large", "large_copy", "folder/large_copy", "missing"]),
+            ),
+        ]);
+        let _ = fs::remove_dir_all(&test_dir);
+
+        assert_eq!(
+            duplicates,
+            vec![
+                paths(&["folder/large_copy", "large", "large_copy"]),
+                paths(&["folder/small_copy", "small"]),
+            ]
+        );
+    }
+}
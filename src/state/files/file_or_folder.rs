@@ -2,11 +2,29 @@ use std::{
     collections::{HashMap, VecDeque},
     ffi::OsString,
     fs::Metadata,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use filesize::PathExt;
 
+#[cfg(not(target_os = "windows"))]
+use crate::os::unix::num_hardlinks;
+#[cfg(target_os = "windows")]
+use crate::os::windows::num_hardlinks;
+
+pub fn file_size(path: &Path, entry_metadata: &Metadata, show_apparent_size: bool) -> u128 {
+    // apparent_size (named after the flag of the same name in 'du')
+    // means "show the file size, rather than the actual space it takes on disk"
+    // these may differ (for example) in filesystems that use compression
+    if show_apparent_size {
+        entry_metadata.len() as u128
+    } else {
+        path.size_on_disk_fast(entry_metadata)
+            .unwrap_or(entry_metadata.len()) as u128
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum FileOrFolder {
     Folder(Folder),
@@ -20,12 +38,29 @@ impl FileOrFolder {
             FileOrFolder::File(file) => file.size,
         }
     }
+    pub fn modified(&self) -> Option<SystemTime> {
+        // for folders, this is the oldest modification time of anything inside them
+        match self {
+            FileOrFolder::Folder(folder) => folder.oldest_modified,
+            FileOrFolder::File(file) => file.modified,
+        }
+    }
+}
+
+fn older(a: Option<SystemTime>, b: Option<SystemTime>) -> Option<SystemTime> {
+    // a missing modification time (eg. not supported by the platform) is not older than anything
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct File {
     pub name: OsString,
     pub size: u128,
+    pub modified: Option<SystemTime>,
+    pub num_hardlinks: Option<u32>, // None where we can't tell (eg. remote files)
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +69,9 @@ pub struct Folder {
     pub contents: HashMap<OsString, FileOrFolder>,
     pub size: u128,
     pub num_descendants: u64,
+    pub oldest_modified: Option<SystemTime>,
+    pub unexpanded: bool, // at --depth: sized by its direct children, which are not in it
+    pub mount_point: bool, // on another file system, which --one-file-system doesn't go into
 }
 
 impl From<OsString> for Folder {
@@ -43,6 +81,9 @@ impl From<OsString> for Folder {
             contents: HashMap::new(),
             size: 0,
             num_descendants: 0,
+            oldest_modified: None,
+            unexpanded: false,
+            mount_point: false,
         }
     }
 }
@@ -54,6 +95,9 @@ impl Folder {
             contents: HashMap::new(),
             size: 0,
             num_descendants: 0,
+            oldest_modified: None,
+            unexpanded: false,
+            mount_point: false,
         }
     }
 
@@ -63,20 +107,16 @@ impl Folder {
         relative_path: PathBuf,
         show_apparent_size: bool,
     ) {
-        // apparent_size (named after the flag of the same name in 'du')
-        // means "show the file size, rather than the actual space it takes on disk"
-        // these may differ (for example) in filesystems that use compression
         if entry_metadata.is_dir() {
             self.add_folder(relative_path);
         } else {
-            let size = if show_apparent_size {
-                entry_metadata.len() as u128
-            } else {
-                relative_path
-                    .size_on_disk_fast(&entry_metadata)
-                    .unwrap_or(entry_metadata.len()) as u128
-            };
-            self.add_file(relative_path, size);
+            let size = file_size(&relative_path, entry_metadata, show_apparent_size);
+            self.add_modified_file(
+                relative_path,
+                size,
+                entry_metadata.modified().ok(),
+                num_hardlinks(entry_metadata),
+            );
         }
     }
 
@@ -111,7 +151,17 @@ impl Folder {
                 .insert(name.clone(), FileOrFolder::Folder(Folder::from(name)));
         }
     }
+    #[cfg(test)]
     pub fn add_file(&mut self, path: PathBuf, size: u128) {
+        self.add_modified_file(path, size, None, None);
+    }
+    pub fn add_modified_file(
+        &mut self,
+        path: PathBuf,
+        size: u128,
+        modified: Option<SystemTime>,
+        num_hardlinks: Option<u32>,
+    ) {
         let path_length = path.components().count();
         if path_length == 0 {
             return;
@@ -128,9 +178,15 @@ impl Folder {
                 .or_insert(FileOrFolder::Folder(Folder::from(name)));
             self.size += size;
             self.num_descendants += 1;
+            self.oldest_modified = older(self.oldest_modified, modified);
             match path_entry {
                 FileOrFolder::Folder(folder) => {
-                    folder.add_file(path.iter().skip(1).collect(), size);
+                    folder.add_modified_file(
+                        path.iter().skip(1).collect(),
+                        size,
+                        modified,
+                        num_hardlinks,
+                    );
                 }
                 _ => unreachable!("got a file in the middle of a path"),
             };
@@ -142,9 +198,50 @@ impl Folder {
                 .to_os_string();
             self.size += size;
             self.num_descendants += 1;
-            self.contents
-                .insert(name.clone(), FileOrFolder::File(File { name, size }));
+            self.oldest_modified = older(self.oldest_modified, modified);
+            self.contents.insert(
+                name.clone(),
+                FileOrFolder::File(File {
+                    name,
+                    size,
+                    modified,
+                    num_hardlinks,
+                }),
+            );
+        }
+    }
+    pub fn add_mount_point(&mut self, path: PathBuf) {
+        // an empty folder that stands in for everything mounted there
+        self.add_folder(path.clone());
+        let mut folder = self;
+        for name in &path {
+            folder = match folder.contents.get_mut(name) {
+                Some(FileOrFolder::Folder(folder)) => folder,
+                _ => unreachable!("mount point was not added as a folder"),
+            };
+        }
+        folder.mount_point = true;
+    }
+    pub fn add_unexpanded_entry(&mut self, path: PathBuf, size: u128) {
+        // an entry just past the depth we scan to: only its size is added, to the
+        // folders leading to it, and the folder it's in is marked as not showing it
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        self.size += size;
+        let mut folder = self;
+        for name in parent {
+            let entry = folder
+                .contents
+                .entry(name.to_os_string())
+                .or_insert_with(|| FileOrFolder::Folder(Folder::from(name.to_os_string())));
+            folder = match entry {
+                FileOrFolder::Folder(folder) => folder,
+                FileOrFolder::File(_) => unreachable!("got a file in the middle of a path"),
+            };
+            folder.size += size;
         }
+        folder.unexpanded = true;
     }
     pub fn path(&self, mut folder_names: Vec<OsString>) -> Option<&FileOrFolder> {
         let next_folder_name = folder_names.remove(0);
@@ -179,6 +276,7 @@ impl Folder {
             self.size -= removed_size;
             self.num_descendants -= removed_descendents;
             self.contents.remove(name);
+            self.recompute_oldest_modified();
         } else {
             let (removed_size, removed_descendents) = {
                 let item_to_remove = self
@@ -203,6 +301,7 @@ impl Folder {
                     self.size -= removed_size;
                     self.num_descendants -= removed_descendents;
                     folder.delete_path(&Vec::from(folders_to_traverse));
+                    self.recompute_oldest_modified();
                 }
                 FileOrFolder::File(_) => {
                     panic!("got a file in the middle of a path");
@@ -210,4 +309,117 @@ impl Folder {
             }
         }
     }
+    pub fn clear_path(&mut self, folder_names: &[OsString]) -> (u128, u64) {
+        // empties the folder at the end of the path and updates the folders
+        // leading to it, returns the size and descendants it used to have
+        match folder_names.split_first() {
+            None => {
+                let cleared = (self.size, self.num_descendants);
+                self.contents.clear();
+                self.size = 0;
+                self.num_descendants = 0;
+                self.oldest_modified = None;
+                self.unexpanded = false;
+                cleared
+            }
+            Some((name, rest)) => match self.contents.get_mut(name) {
+                Some(FileOrFolder::Folder(folder)) => {
+                    let (size, descendants) = folder.clear_path(rest);
+                    self.size -= size;
+                    self.num_descendants -= descendants;
+                    self.recompute_oldest_modified();
+                    (size, descendants)
+                }
+                _ => (0, 0),
+            },
+        }
+    }
+    pub fn insert_folder(&mut self, folder_names: &[OsString], mut folder: Folder) {
+        // folders leading to it are created as needed, anything already at the path
+        // is replaced. sizes and descendants are left for recompute_size and
+        // recompute_num_descendants
+        match folder_names.split_first() {
+            None => unreachable!("cannot insert a folder in place of itself"),
+            Some((name, [])) => {
+                folder.name = name.clone();
+                self.contents
+                    .insert(name.clone(), FileOrFolder::Folder(folder));
+            }
+            Some((name, rest)) => {
+                let next_item = self
+                    .contents
+                    .entry(name.clone())
+                    .or_insert_with(|| FileOrFolder::Folder(Folder::from(name.clone())));
+                if let FileOrFolder::File(_) = next_item {
+                    *next_item = FileOrFolder::Folder(Folder::from(name.clone()));
+                }
+                if let FileOrFolder::Folder(next_folder) = next_item {
+                    next_folder.insert_folder(rest, folder);
+                }
+            }
+        }
+        self.recompute_oldest_modified();
+    }
+    fn recompute_oldest_modified(&mut self) {
+        // only the direct children are looked at, their own times are already up to date
+        self.oldest_modified = self
+            .contents
+            .values()
+            .fold(None, |oldest, item| older(oldest, item.modified()));
+    }
+    pub fn recompute_size(&mut self) -> u128 {
+        // bottom-up: every folder's size becomes the sum of its children's sizes
+        if self.unexpanded {
+            // whose sizes are all we have
+            return self.size;
+        }
+        self.size = self
+            .contents
+            .values_mut()
+            .map(|item| match item {
+                FileOrFolder::Folder(folder) => folder.recompute_size(),
+                FileOrFolder::File(file) => file.size,
+            })
+            .sum();
+        self.size
+    }
+    pub fn recompute_num_descendants(&mut self) -> u64 {
+        // bottom-up, like recompute_size
+        self.num_descendants = self
+            .contents
+            .values_mut()
+            .map(|item| match item {
+                FileOrFolder::Folder(folder) => folder.recompute_num_descendants() + 1,
+                FileOrFolder::File(_) => 1,
+            })
+            .sum();
+        self.num_descendants
+    }
+    pub fn count_files_and_folders(&self) -> (u64, u64) {
+        // what num_descendants adds up, told apart
+        self.contents
+            .values()
+            .fold((0, 0), |(files, folders), item| match item {
+                FileOrFolder::Folder(folder) => {
+                    let (child_files, child_folders) = folder.count_files_and_folders();
+                    (files + child_files, folders + child_folders + 1)
+                }
+                FileOrFolder::File(_) => (files + 1, folders),
+            })
+    }
+    #[cfg(test)]
+    pub fn inconsistent_sizes(&self, path: PathBuf) -> Vec<PathBuf> {
+        let mut inconsistent_paths = vec![];
+        let mut size_of_children = 0;
+        for item in self.contents.values() {
+            size_of_children += item.size();
+            if let FileOrFolder::Folder(folder) = item {
+                inconsistent_paths.extend(folder.inconsistent_sizes(path.join(&folder.name)));
+            }
+        }
+        if self.size != size_of_children && !self.unexpanded {
+            inconsistent_paths.push(path);
+        }
+        inconsistent_paths
+    }
 }
@@ -1,21 +1,39 @@
 use std::{
+    collections::{HashMap, HashSet},
     ffi::{OsStr, OsString},
     fs::Metadata,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use crate::state::{
-    FileToDelete,
-    files::{FileOrFolder, Folder},
+use crate::{
+    output::largest::LargestFiles,
+    state::{
+        FileToDelete,
+        files::{
+            FileOrFolder, Folder, NodeRef, PostorderIter, PreorderIter, SpaceFreed, file_size,
+        },
+        tiles::{FileType, Tile},
+    },
 };
 
+// we only keep this many of the paths we failed to read, they're all counted though
+const MAX_FAILED_PATHS: usize = 100;
+
 pub struct FileTree {
     pub current_folder_names: Vec<OsString>,
-    pub space_freed: u128,
+    space_freed: SpaceFreed,
     pub failed_to_read: u64,
+    pub failed_paths: Vec<(PathBuf, String)>, // and why they failed
+    pub skip_hidden: bool,
+    pub zero_byte_count: u64,
+    // lowercase and without the dot, nothing is removed from the tree for it
+    extension_filter: Option<String>,
     pub path_in_filesystem: PathBuf,
     base_folder: Folder,
     show_apparent_size: bool,
+    num_deletions: u64,
+    max_depth_seen: usize,
 }
 
 impl FileTree {
@@ -24,17 +42,42 @@ impl FileTree {
             base_folder,
             current_folder_names: Vec::new(),
             path_in_filesystem,
-            space_freed: 0,
+            space_freed: SpaceFreed::default(),
             failed_to_read: 0,
+            failed_paths: Vec::new(),
+            skip_hidden: false,
+            zero_byte_count: 0,
+            extension_filter: None,
             show_apparent_size,
+            num_deletions: 0,
+            max_depth_seen: 0,
         }
     }
+    pub fn add_failed_to_read(&mut self, failed_path: Option<(PathBuf, String)>) {
+        // we don't always know which path it was
+        self.failed_to_read += 1;
+        if let Some(failed_path) = failed_path
+            && self.failed_paths.len() < MAX_FAILED_PATHS
+        {
+            self.failed_paths.push(failed_path);
+        }
+    }
+    pub fn get_space_freed(&self) -> u128 {
+        self.space_freed.get()
+    }
+    pub fn add_space_freed(&self, size: u128) {
+        self.space_freed.add(size);
+    }
     pub fn get_total_size(&self) -> u128 {
         self.base_folder.size
     }
     pub fn get_total_descendants(&self) -> u64 {
         self.base_folder.num_descendants
     }
+    pub fn get_top_level_count(&self) -> usize {
+        // the entries right inside the scanned folder
+        self.base_folder.contents.len()
+    }
     pub fn get_current_folder(&self) -> &Folder {
         if self.current_folder_names.is_empty() {
             &self.base_folder
@@ -62,6 +105,33 @@ impl FileTree {
         let current_folder = &self.get_current_folder();
         current_folder.path(vec![item_name.to_os_string()])
     }
+    pub fn show_apparent_size(&self) -> bool {
+        self.show_apparent_size
+    }
+    pub fn get_folder_by_path(&self, path: &Path) -> Option<&Folder> {
+        // None if the path is not a folder in the tree (eg. it was deleted)
+        let folder_names = self.names_relative_to_base(path)?;
+        if folder_names.is_empty() {
+            return Some(&self.base_folder);
+        }
+        match self.base_folder.path(folder_names) {
+            Some(FileOrFolder::Folder(folder)) => Some(folder),
+            _ => None,
+        }
+    }
+    pub fn enter_path(&mut self, path: &Path) -> bool {
+        // true => succeeded, false => no such folder
+        if self.get_folder_by_path(path).is_none() {
+            return false;
+        }
+        match self.names_relative_to_base(path) {
+            Some(folder_names) => {
+                self.current_folder_names = folder_names;
+                true
+            }
+            None => false,
+        }
+    }
     pub fn enter_folder(&mut self, folder_name: &OsStr) {
         self.current_folder_names.push(folder_name.to_os_string());
     }
@@ -69,17 +139,1020 @@ impl FileTree {
         // true => succeeded, false => at base folder
         self.current_folder_names.pop().is_some()
     }
+    pub fn navigate_to_root(&mut self) -> bool {
+        // true => succeeded, false => already at base folder
+        let was_at_root = self.current_folder_names.is_empty();
+        self.current_folder_names.clear();
+        !was_at_root
+    }
     pub fn delete_file(&mut self, file_to_delete: &FileToDelete) {
         let path_to_delete = &file_to_delete.path_to_file;
         self.base_folder.delete_path(&path_to_delete);
+        self.num_deletions += 1;
+        if self.num_deletions > 1 {
+            // each deletion updates the sizes along its path, once there have been
+            // a few of them we'd rather not trust the accumulated result
+            self.recompute_sizes_from_leaves();
+        }
+    }
+    pub fn replace_subtree(&mut self, subtree: FileTree) {
+        // what we had for the folder the subtree was scanned from is thrown away for
+        // what was found there now, eg. after it changed outside of diskonaut
+        let subtree_path = subtree.path_in_filesystem.clone();
+        // the counts kept while scanning include what we had for the folder, and
+        // the subtree comes with counts of its own for what is there now
+        let zero_byte_files = self
+            .walk_preorder_filtered(|path, _| {
+                path.starts_with(&subtree_path) || subtree_path.starts_with(path)
+            })
+            .filter(|(_, path, node)| {
+                path.starts_with(&subtree_path)
+                    && matches!(node, NodeRef::File(_))
+                    && node.size() == 0
+            })
+            .count();
+        self.zero_byte_count = self.zero_byte_count.saturating_sub(zero_byte_files as u64);
+        let failed_paths_count = self.failed_paths.len();
+        self.failed_paths
+            .retain(|(path, _)| !path.starts_with(&subtree_path));
+        let failed_paths_removed = failed_paths_count - self.failed_paths.len();
+        self.failed_to_read = self
+            .failed_to_read
+            .saturating_sub(failed_paths_removed as u64);
+        let names_of_subtree =
+            names_relative_to(&subtree.path_in_filesystem, &self.path_in_filesystem);
+        self.base_folder.clear_path(&names_of_subtree);
+        self.merge(subtree);
+    }
+    pub fn recompute_sizes_from_leaves(&mut self) {
+        // every folder comes after its contents, so their sizes are summed up by the time
+        // we get to it. The walk borrows the tree, so the new sizes are only set after it
+        let mut sizes_of_contents: HashMap<PathBuf, u128> = HashMap::new();
+        let mut folder_sizes = vec![];
+        for (_, path, node) in self.walk_postorder() {
+            let size = match node {
+                // sized by children it doesn't have, that size is all we have
+                NodeRef::Folder(folder) if !folder.unexpanded => {
+                    let size = sizes_of_contents.remove(&path).unwrap_or(0);
+                    folder_sizes.push((path.clone(), size));
+                    size
+                }
+                node => node.size(),
+            };
+            if let Some(parent) = path.parent() {
+                *sizes_of_contents.entry(parent.to_path_buf()).or_default() += size;
+            }
+        }
+        for (path, size) in folder_sizes {
+            if let Some(folder) = self.folder_at_path_mut(&path) {
+                folder.size = size;
+            }
+        }
+    }
+    fn folder_at_path_mut(&mut self, path: &Path) -> Option<&mut Folder> {
+        let folder_names = self.names_relative_to_base(path)?;
+        let mut folder = &mut self.base_folder;
+        for name in folder_names {
+            folder = match folder.contents.get_mut(&name)? {
+                FileOrFolder::Folder(next_folder) => next_folder,
+                FileOrFolder::File(_) => return None,
+            };
+        }
+        Some(folder)
+    }
+    #[cfg(test)]
+    pub fn verify_size_consistency(&self) -> Vec<PathBuf> {
+        // paths of folders whose size is not the sum of their children's sizes
+        self.base_folder
+            .inconsistent_sizes(self.path_in_filesystem.clone())
+    }
+    pub fn merge(&mut self, other: FileTree) {
+        // other's base folder is placed under the closest folder both trees share,
+        // which becomes our base folder if it wasn't already
+        let common_ancestor = self
+            .path_in_filesystem
+            .ancestors()
+            .find(|ancestor| other.path_in_filesystem.starts_with(ancestor))
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        if common_ancestor != self.path_in_filesystem {
+            let names_from_ancestor = names_relative_to(&self.path_in_filesystem, &common_ancestor);
+            let ancestor_name = common_ancestor.iter().next_back().unwrap_or_default();
+            let previous_base_folder = std::mem::replace(
+                &mut self.base_folder,
+                Folder::from(ancestor_name.to_os_string()),
+            );
+            self.base_folder
+                .insert_folder(&names_from_ancestor, previous_base_folder);
+            self.max_depth_seen += names_from_ancestor.len();
+            self.current_folder_names.splice(0..0, names_from_ancestor);
+            self.path_in_filesystem = common_ancestor;
+        }
+        let names_of_other = names_relative_to(&other.path_in_filesystem, &self.path_in_filesystem);
+        self.max_depth_seen = self
+            .max_depth_seen
+            .max(other.max_depth_seen + names_of_other.len());
+        if names_of_other.is_empty() {
+            // the same folder scanned twice, the other scan's entries win
+            self.base_folder.contents.extend(other.base_folder.contents);
+        } else {
+            self.base_folder
+                .insert_folder(&names_of_other, other.base_folder);
+        }
+        self.failed_to_read += other.failed_to_read;
+        let room_for_failed_paths = MAX_FAILED_PATHS.saturating_sub(self.failed_paths.len());
+        self.failed_paths
+            .extend(other.failed_paths.into_iter().take(room_for_failed_paths));
+        self.zero_byte_count += other.zero_byte_count;
+        self.base_folder.recompute_size();
+        self.base_folder.recompute_num_descendants();
+    }
+    pub fn ancestors_of(&self, path: &Path) -> Vec<PathBuf> {
+        // root first, the path itself is not included
+        let mut ancestors: Vec<PathBuf> = path
+            .ancestors()
+            .skip(1)
+            .filter(|ancestor| self.contains_path(ancestor))
+            .map(PathBuf::from)
+            .collect();
+        ancestors.reverse();
+        ancestors
+    }
+    pub fn depth_of(&self, path: &Path) -> Option<usize> {
+        // the base folder has a depth of 0
+        if self.contains_path(path) {
+            self.names_relative_to_base(path)
+                .map(|folder_names| folder_names.len())
+        } else {
+            None
+        }
+    }
+    fn contains_path(&self, path: &Path) -> bool {
+        match self.names_relative_to_base(path) {
+            Some(folder_names) if folder_names.is_empty() => true,
+            Some(folder_names) => self.base_folder.path(folder_names).is_some(),
+            None => false,
+        }
+    }
+    fn node_at_path(&self, path: &Path) -> Option<NodeRef<'_>> {
+        // None for paths outside of the scanned folder, or that are not (or no longer) in it
+        let mut node = NodeRef::Folder(&self.base_folder);
+        for name in self.names_relative_to_base(path)? {
+            node = match node {
+                NodeRef::Folder(folder) => NodeRef::from(folder.contents.get(&name)?),
+                NodeRef::File(_) => return None,
+            };
+        }
+        Some(node)
+    }
+    pub fn size_at_path(&self, path: &Path) -> Option<u128> {
+        self.node_at_path(path).map(|node| node.size())
+    }
+    pub fn folder_at_path(&self, path: &Path) -> Option<&Folder> {
+        match self.node_at_path(path)? {
+            NodeRef::Folder(folder) => Some(folder),
+            NodeRef::File(_) => None,
+        }
+    }
+    pub fn subfolders_at_path(&self, path: &Path) -> Vec<PathBuf> {
+        // the folders right inside the one at this path, by name
+        let Some(folder) = self.folder_at_path(path) else {
+            return vec![];
+        };
+        let mut subfolders: Vec<PathBuf> = folder
+            .contents
+            .iter()
+            .filter(|(_, file_or_folder)| matches!(file_or_folder, FileOrFolder::Folder(_)))
+            .map(|(name, _)| path.join(name))
+            .collect();
+        subfolders.sort();
+        subfolders
+    }
+    pub fn descendant_count_at_path(&self, path: &Path) -> Option<u64> {
+        // None for files as well
+        match self.node_at_path(path)? {
+            NodeRef::Folder(folder) => Some(folder.num_descendants),
+            NodeRef::File(_) => None,
+        }
+    }
+    fn names_relative_to_base(&self, path: &Path) -> Option<Vec<OsString>> {
+        let relative_path = path.strip_prefix(&self.path_in_filesystem).ok()?;
+        Some(
+            relative_path
+                .iter()
+                .map(|folder_name| folder_name.to_os_string())
+                .collect(),
+        )
+    }
+    pub fn walk_preorder(&self) -> PreorderIter<'_> {
+        // (depth, full path, node), every folder before its contents
+        PreorderIter::new(
+            self.path_in_filesystem.clone(),
+            &self.base_folder,
+            |_, _| true,
+        )
+    }
+    pub fn find_path_of_tile(&self, tile: &Tile) -> Option<PathBuf> {
+        // tiles only keep their name, so we look for the node they were made from.
+        // the current folder is where tiles come from, so it's checked first
+        let matches_tile = |path: &Path, node: &NodeRef| {
+            let is_folder = matches!(node, NodeRef::Folder(_));
+            path.file_name() == Some(tile.name.as_os_str())
+                && is_folder == (tile.file_type == FileType::Folder)
+                && node.size() == tile.size
+                && node.modified() == tile.modified
+        };
+        if let Some(item) = self.item_in_current_folder(&tile.name) {
+            let path = self.get_current_path().join(&tile.name);
+            if matches_tile(&path, &NodeRef::from(item)) {
+                return Some(path);
+            }
+        }
+        self.walk_preorder()
+            .skip(1) // the base folder is never a tile
+            .find(|(_, path, node)| matches_tile(path, node))
+            .map(|(_, path, _)| path)
+    }
+    pub fn walk_preorder_filtered<F>(&self, predicate: F) -> PreorderIter<'_, F>
+    where
+        F: Fn(&Path, &NodeRef) -> bool,
+    {
+        // nodes for which the predicate is false are skipped along with all their contents
+        PreorderIter::new(
+            self.path_in_filesystem.clone(),
+            &self.base_folder,
+            predicate,
+        )
+    }
+    pub fn walk_postorder(&self) -> PostorderIter<'_> {
+        // (depth, full path, node), every folder after its contents
+        PostorderIter::new(self.path_in_filesystem.clone(), &self.base_folder)
+    }
+    pub fn find_largest_files(&self, count: usize) -> Vec<(PathBuf, u128)> {
+        // anywhere in the tree rather than only in the current folder, largest first
+        let mut largest_files = LargestFiles::new(count);
+        for (_, path, node) in self.walk_preorder() {
+            if let NodeRef::File(_) = node {
+                largest_files.add(&path, node.size());
+            }
+        }
+        largest_files.into_sorted()
+    }
+    pub fn filter_by_extension(&mut self, extension: &str) {
+        // "log", ".log" and ".LOG" all make for the same filter
+        let extension = extension.trim().trim_start_matches('.').to_lowercase();
+        self.extension_filter = (!extension.is_empty()).then_some(extension);
+    }
+    pub fn clear_filter(&mut self) {
+        self.extension_filter = None;
+    }
+    pub fn is_filtered(&self) -> bool {
+        self.extension_filter.is_some()
+    }
+    pub fn extension_filter(&self) -> Option<&str> {
+        self.extension_filter.as_deref()
+    }
+    pub fn paths_shown_by_filter(&self) -> HashSet<PathBuf> {
+        // files with the extension and every folder they're in, so that they can be
+        // reached. Without a filter, nothing is hidden and this is left empty
+        let mut shown = HashSet::new();
+        let Some(extension) = &self.extension_filter else {
+            return shown;
+        };
+        for (_, path, node) in self.walk_preorder() {
+            let matches = path
+                .extension()
+                .is_some_and(|ext| ext.to_string_lossy().to_lowercase() == *extension);
+            if let NodeRef::File(_) = node
+                && matches
+            {
+                for ancestor in path.ancestors() {
+                    // once we get to a folder we have, we have all those above it too
+                    if !shown.insert(ancestor.to_path_buf()) || ancestor == self.path_in_filesystem
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+        shown
+    }
+    pub fn find_by_name(&self, query: &str) -> Vec<PathBuf> {
+        // files and folders anywhere in the tree with the query in their name,
+        // whatever the case
+        let query = query.to_lowercase();
+        self.walk_preorder()
+            .filter(|(depth, path, _)| {
+                *depth > 0
+                    && path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().to_lowercase().contains(&query))
+            })
+            .map(|(_, path, _)| path)
+            .collect()
+    }
+    pub fn duplicate_candidates(&self) -> Vec<(u128, Vec<PathBuf>)> {
+        // files can only have the same contents if they're the same size, so these
+        // are all that find_duplicates has to read. Empty files are all the same and
+        // take no space, so they're left out
+        let mut files_by_size: HashMap<u128, Vec<PathBuf>> = HashMap::new();
+        for (_, path, node) in self.walk_preorder() {
+            if let NodeRef::File(_) = node
+                && node.size() > 0
+            {
+                files_by_size.entry(node.size()).or_default().push(path);
+            }
+        }
+        files_by_size
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect()
+    }
+    pub fn max_depth(&self) -> usize {
+        // the root folder is at depth 0, its direct children at 1 and so on
+        self.max_depth_seen
     }
     pub fn add_entry(&mut self, entry_metadata: &Metadata, entry_full_path: &Path) {
+        let is_empty_file = !entry_metadata.is_dir() && entry_metadata.len() == 0;
+        let relative_path = self.track_new_entry(entry_full_path, is_empty_file);
+        self.base_folder
+            .add_entry(entry_metadata, relative_path, self.show_apparent_size);
+    }
+    pub fn add_mount_point(&mut self, entry_full_path: &Path) {
+        let relative_path = self.track_new_entry(entry_full_path, false);
+        self.base_folder.add_mount_point(relative_path);
+    }
+    pub fn add_unexpanded_entry(&mut self, entry_metadata: &Metadata, entry_full_path: &Path) {
+        // folders themselves take no space, only what's in them
+        let size = if entry_metadata.is_dir() {
+            0
+        } else {
+            file_size(entry_full_path, entry_metadata, self.show_apparent_size)
+        };
+        let relative_path = names_relative_to(entry_full_path, &self.path_in_filesystem)
+            .into_iter()
+            .collect();
+        self.base_folder.add_unexpanded_entry(relative_path, size);
+    }
+    pub fn add_remote_entry(
+        &mut self,
+        entry_full_path: &Path,
+        is_dir: bool,
+        size: u128,
+        modified: Option<SystemTime>,
+    ) {
+        // remote entries come without local metadata, so we take the server's word for their size
+        let relative_path = self.track_new_entry(entry_full_path, !is_dir && size == 0);
+        if is_dir {
+            self.base_folder.add_folder(relative_path);
+        } else {
+            self.base_folder
+                .add_modified_file(relative_path, size, modified, None);
+        }
+    }
+    fn track_new_entry(&mut self, entry_full_path: &Path, is_empty_file: bool) -> PathBuf {
         let base_path_length = self.path_in_filesystem.components().count();
         let mut relative_path = PathBuf::new();
         for dir in entry_full_path.components().skip(base_path_length) {
             relative_path.push(dir);
         }
-        self.base_folder
-            .add_entry(entry_metadata, relative_path, self.show_apparent_size);
+        // kept up to date while scanning so that we don't need to walk the tree for it
+        if is_empty_file {
+            self.zero_byte_count += 1;
+        }
+        let depth = relative_path.components().count();
+        if depth > self.max_depth_seen {
+            self.max_depth_seen = depth;
+        }
+        relative_path
+    }
+}
+
+fn names_relative_to(path: &Path, ancestor: &Path) -> Vec<OsString> {
+    // empty if the path is the ancestor itself (or not under it)
+    path.strip_prefix(ancestor)
+        .map(|relative_path| {
+            relative_path
+                .iter()
+                .map(|folder_name| folder_name.to_os_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_tree() -> FileTree {
+        let path_in_filesystem = PathBuf::from("/tmp/diskonaut_file_tree_test");
+        let mut base_folder = Folder::new(&path_in_filesystem);
+        base_folder.add_folder(PathBuf::from("subfolder_1"));
+        base_folder.add_folder(PathBuf::from("subfolder_1/subfolder_2"));
+        base_folder.add_file(PathBuf::from("subfolder_1/subfolder_2/file"), 4096);
+        FileTree::new(base_folder, path_in_filesystem, false)
+    }
+
+    #[test]
+    fn ancestors_of_nested_file() {
+        let file_tree = file_tree();
+        let ancestors = file_tree.ancestors_of(Path::new(
+            "/tmp/diskonaut_file_tree_test/subfolder_1/subfolder_2/file",
+        ));
+        assert_eq!(
+            ancestors,
+            vec![
+                PathBuf::from("/tmp/diskonaut_file_tree_test"),
+                PathBuf::from("/tmp/diskonaut_file_tree_test/subfolder_1"),
+                PathBuf::from("/tmp/diskonaut_file_tree_test/subfolder_1/subfolder_2"),
+            ]
+        );
+        assert!(
+            file_tree
+                .ancestors_of(Path::new("/tmp/diskonaut_file_tree_test"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn depth_of_paths() {
+        let file_tree = file_tree();
+        assert_eq!(
+            file_tree.depth_of(Path::new("/tmp/diskonaut_file_tree_test")),
+            Some(0)
+        );
+        assert_eq!(
+            file_tree.depth_of(Path::new(
+                "/tmp/diskonaut_file_tree_test/subfolder_1/subfolder_2/file"
+            )),
+            Some(3)
+        );
+        assert_eq!(
+            file_tree.depth_of(Path::new("/tmp/diskonaut_file_tree_test/no_such_file")),
+            None
+        );
+        assert_eq!(file_tree.depth_of(Path::new("/tmp")), None);
+    }
+    #[test]
+    fn sizes_at_paths() {
+        let file_tree = file_tree();
+        assert_eq!(
+            file_tree.size_at_path(Path::new("/tmp/diskonaut_file_tree_test/subfolder_1")),
+            Some(4096)
+        );
+        assert_eq!(
+            file_tree
+                .descendant_count_at_path(Path::new("/tmp/diskonaut_file_tree_test/subfolder_1")),
+            Some(2) // subfolder_2 and its file
+        );
+        let file = Path::new("/tmp/diskonaut_file_tree_test/subfolder_1/subfolder_2/file");
+        assert_eq!(file_tree.size_at_path(file), Some(4096));
+        assert_eq!(file_tree.descendant_count_at_path(file), None);
+        assert_eq!(file_tree.size_at_path(&file.join("nested")), None);
+        assert_eq!(file_tree.size_at_path(Path::new("/elsewhere")), None);
+        assert_eq!(file_tree.descendant_count_at_path(Path::new("/tmp")), None);
+    }
+    #[test]
+    fn merging_trees_of_sibling_folders() {
+        let mut file_tree = file_tree();
+        let other_path = PathBuf::from("/tmp/diskonaut_other_file_tree_test");
+        let mut other_folder = Folder::new(&other_path);
+        other_folder.add_folder(PathBuf::from("subfolder_3"));
+        other_folder.add_file(PathBuf::from("subfolder_3/file"), 8192);
+        other_folder.add_file(PathBuf::from("file"), 1024);
+        let mut other = FileTree::new(other_folder, other_path, false);
+        other.failed_to_read = 2;
+
+        file_tree.merge(other);
+
+        assert_eq!(file_tree.path_in_filesystem, PathBuf::from("/tmp"));
+        assert_eq!(file_tree.get_total_size(), 4096 + 8192 + 1024);
+        // the two base folders and everything in them
+        assert_eq!(file_tree.get_total_descendants(), 2 + 3 + 3);
+        assert_eq!(
+            file_tree.size_at_path(Path::new("/tmp/diskonaut_other_file_tree_test")),
+            Some(8192 + 1024)
+        );
+        assert_eq!(
+            file_tree.descendant_count_at_path(Path::new("/tmp/diskonaut_file_tree_test")),
+            Some(3)
+        );
+        assert_eq!(
+            file_tree.depth_of(Path::new("/tmp/diskonaut_file_tree_test")),
+            Some(1)
+        );
+        assert_eq!(file_tree.failed_to_read, 2);
+        assert!(file_tree.verify_size_consistency().is_empty());
+    }
+    #[test]
+    fn merging_a_tree_from_inside_our_folder() {
+        let mut file_tree = file_tree();
+        let other_path = PathBuf::from("/tmp/diskonaut_file_tree_test/subfolder_1/elsewhere");
+        let mut other_folder = Folder::new(&other_path);
+        other_folder.add_file(PathBuf::from("file"), 1024);
+        file_tree.merge(FileTree::new(other_folder, other_path, false));
+
+        assert_eq!(
+            file_tree.path_in_filesystem,
+            PathBuf::from("/tmp/diskonaut_file_tree_test")
+        );
+        assert_eq!(file_tree.get_total_size(), 4096 + 1024);
+        assert_eq!(
+            file_tree
+                .descendant_count_at_path(Path::new("/tmp/diskonaut_file_tree_test/subfolder_1")),
+            Some(4)
+        );
+    }
+    #[test]
+    fn recompute_sizes_from_leaves_fixes_inconsistent_sizes() {
+        let mut file_tree = file_tree();
+        assert!(file_tree.verify_size_consistency().is_empty());
+        file_tree.base_folder.size += 1;
+        if let Some(FileOrFolder::Folder(subfolder_1)) = file_tree
+            .base_folder
+            .contents
+            .get_mut(&OsString::from("subfolder_1"))
+        {
+            subfolder_1.size -= 1;
+        }
+        assert_eq!(
+            file_tree.verify_size_consistency(),
+            vec![
+                PathBuf::from("/tmp/diskonaut_file_tree_test/subfolder_1"),
+                PathBuf::from("/tmp/diskonaut_file_tree_test"),
+            ]
+        );
+        file_tree.recompute_sizes_from_leaves();
+        assert!(file_tree.verify_size_consistency().is_empty());
+        assert_eq!(file_tree.get_total_size(), 4096);
+    }
+
+    #[test]
+    fn sizes_are_consistent_after_multiple_deletions() {
+        let mut file_tree = file_tree();
+        file_tree
+            .base_folder
+            .add_file(PathBuf::from("subfolder_1/other_file"), 1024);
+        file_tree.base_folder.add_file(PathBuf::from("file"), 512);
+        for (path_to_file, size) in [
+            (vec!["subfolder_1", "subfolder_2", "file"], 4096),
+            (vec!["file"], 512),
+        ] {
+            file_tree.delete_file(&FileToDelete {
+                path_in_filesystem: file_tree.path_in_filesystem.clone(),
+                path_to_file: path_to_file.into_iter().map(OsString::from).collect(),
+                file_type: FileType::File,
+                num_descendants: None,
+                size,
+            });
+        }
+        assert!(file_tree.verify_size_consistency().is_empty());
+        assert_eq!(file_tree.get_total_size(), 1024);
+    }
+    fn walked_paths<'a>(
+        walk: impl Iterator<Item = (usize, PathBuf, NodeRef<'a>)>,
+    ) -> Vec<(usize, String)> {
+        walk.map(|(depth, path, _node)| (depth, path.to_string_lossy().into_owned()))
+            .collect()
+    }
+
+    fn walk_tree() -> FileTree {
+        let path_in_filesystem = PathBuf::from("/tmp/walk");
+        let mut base_folder = Folder::new(&path_in_filesystem);
+        base_folder.add_file(PathBuf::from("d"), 1024);
+        base_folder.add_folder(PathBuf::from("a"));
+        base_folder.add_file(PathBuf::from("a/c"), 1024);
+        base_folder.add_file(PathBuf::from("a/b"), 1024);
+        FileTree::new(base_folder, path_in_filesystem, false)
+    }
+
+    #[test]
+    fn walk_preorder_visits_folders_before_contents() {
+        let file_tree = walk_tree();
+        assert_eq!(
+            walked_paths(file_tree.walk_preorder()),
+            vec![
+                (0, String::from("/tmp/walk")),
+                (1, String::from("/tmp/walk/a")),
+                (2, String::from("/tmp/walk/a/b")),
+                (2, String::from("/tmp/walk/a/c")),
+                (1, String::from("/tmp/walk/d")),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_postorder_visits_contents_before_folders() {
+        let file_tree = walk_tree();
+        assert_eq!(
+            walked_paths(file_tree.walk_postorder()),
+            vec![
+                (2, String::from("/tmp/walk/a/b")),
+                (2, String::from("/tmp/walk/a/c")),
+                (1, String::from("/tmp/walk/a")),
+                (1, String::from("/tmp/walk/d")),
+                (0, String::from("/tmp/walk")),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_preorder_filtered_prunes_subtrees() {
+        let file_tree = walk_tree();
+        let walk = file_tree.walk_preorder_filtered(|path, _node| !path.ends_with("a"));
+        assert_eq!(
+            walked_paths(walk),
+            vec![
+                (0, String::from("/tmp/walk")),
+                (1, String::from("/tmp/walk/d"))
+            ]
+        );
+    }
+
+    #[test]
+    fn find_path_of_tiles() {
+        let file_tree = walk_tree();
+        let file_tile = |name: &str, size: u128| {
+            Tile::builder()
+                .name(name)
+                .size(size)
+                .file_type(FileType::File)
+                .build()
+        };
+        assert_eq!(
+            file_tree.find_path_of_tile(&file_tile("d", 1024)),
+            Some(PathBuf::from("/tmp/walk/d"))
+        );
+        // not in the current folder, but still somewhere in the tree
+        assert_eq!(
+            file_tree.find_path_of_tile(&file_tile("c", 1024)),
+            Some(PathBuf::from("/tmp/walk/a/c"))
+        );
+        assert_eq!(file_tree.find_path_of_tile(&file_tile("c", 2048)), None);
+        assert_eq!(file_tree.find_path_of_tile(&file_tile("a", 2048)), None);
+        let folder_tile = Tile::builder()
+            .name("a")
+            .size(2048)
+            .file_type(FileType::Folder)
+            .build();
+        assert_eq!(
+            file_tree.find_path_of_tile(&folder_tile),
+            Some(PathBuf::from("/tmp/walk/a"))
+        );
+    }
+
+    #[test]
+    fn walk_handles_deeply_nested_trees() {
+        let path_in_filesystem = PathBuf::from("/tmp/walk");
+        let mut base_folder = Folder::new(&path_in_filesystem);
+        let mut deep_path = PathBuf::new();
+        for _ in 0..5000 {
+            deep_path.push("f");
+        }
+        deep_path.push("file");
+        let mut folder = &mut base_folder;
+        for _ in 0..5000 {
+            folder.contents.insert(
+                OsString::from("f"),
+                FileOrFolder::Folder(Folder::from(OsString::from("f"))),
+            );
+            folder = match folder.contents.get_mut(&OsString::from("f")) {
+                Some(FileOrFolder::Folder(next)) => next,
+                _ => unreachable!(),
+            };
+        }
+        folder.add_file(PathBuf::from("file"), 1024);
+        let file_tree = FileTree::new(base_folder, path_in_filesystem, false);
+        assert_eq!(file_tree.walk_preorder().count(), 5002);
+        assert_eq!(
+            file_tree.walk_postorder().last().map(|(depth, _, _)| depth),
+            Some(0)
+        );
+        // dropping the tree itself is recursive, so we let it leak rather than
+        // overflow the test thread's stack
+        std::mem::forget(file_tree);
+    }
+
+    #[test]
+    fn largest_files_in_the_whole_tree() {
+        let mut file_tree = file_tree();
+        let base_path = file_tree.path_in_filesystem.clone();
+        let mut other_folder = Folder::new(&base_path.join("subfolder_3"));
+        other_folder.add_file(PathBuf::from("small_file"), 1024);
+        other_folder.add_file(PathBuf::from("large_file"), 8192);
+        file_tree.merge(FileTree::new(
+            other_folder,
+            base_path.join("subfolder_3"),
+            false,
+        ));
+        file_tree.enter_folder(OsStr::new("subfolder_3"));
+
+        assert_eq!(
+            file_tree.find_largest_files(2),
+            vec![
+                (base_path.join("subfolder_3/large_file"), 8192),
+                (base_path.join("subfolder_1/subfolder_2/file"), 4096),
+            ]
+        );
+        assert_eq!(file_tree.find_largest_files(10).len(), 3);
+        assert!(file_tree.find_largest_files(0).is_empty());
+    }
+
+    #[test]
+    fn duplicate_candidates_are_files_of_the_same_size() {
+        let mut file_tree = file_tree();
+        let base_path = file_tree.path_in_filesystem.clone();
+        let mut other_folder = Folder::new(&base_path.join("subfolder_3"));
+        other_folder.add_file(PathBuf::from("same_size"), 4096);
+        other_folder.add_file(PathBuf::from("other_size"), 1024);
+        other_folder.add_file(PathBuf::from("empty"), 0);
+        other_folder.add_file(PathBuf::from("also_empty"), 0);
+        file_tree.merge(FileTree::new(
+            other_folder,
+            base_path.join("subfolder_3"),
+            false,
+        ));
+
+        let mut candidates = file_tree.duplicate_candidates();
+        for (_, paths) in candidates.iter_mut() {
+            paths.sort();
+        }
+        assert_eq!(
+            candidates,
+            vec![(
+                4096,
+                vec![
+                    base_path.join("subfolder_1/subfolder_2/file"),
+                    base_path.join("subfolder_3/same_size"),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn filter_by_extension_keeps_the_tree_intact() {
+        let mut file_tree = file_tree();
+        let base_path = file_tree.path_in_filesystem.clone();
+        let mut other_folder = Folder::new(&base_path.join("subfolder_3"));
+        other_folder.add_file(PathBuf::from("app.LOG"), 1024);
+        other_folder.add_file(PathBuf::from("app.txt"), 2048);
+        file_tree.merge(FileTree::new(
+            other_folder,
+            base_path.join("subfolder_3"),
+            false,
+        ));
+        let total_size = file_tree.get_total_size();
+
+        file_tree.filter_by_extension(".log");
+        assert!(file_tree.is_filtered());
+        assert_eq!(file_tree.extension_filter(), Some("log"));
+        assert_eq!(
+            file_tree.paths_shown_by_filter(),
+            HashSet::from([
+                base_path.clone(),
+                base_path.join("subfolder_3"),
+                base_path.join("subfolder_3/app.LOG"),
+            ])
+        );
+        assert_eq!(file_tree.get_total_size(), total_size);
+
+        file_tree.clear_filter();
+        assert!(!file_tree.is_filtered());
+        assert!(file_tree.paths_shown_by_filter().is_empty());
+    }
+
+    #[test]
+    fn subfolders_are_sorted_by_name() {
+        let mut file_tree = file_tree();
+        let base_path = file_tree.path_in_filesystem.clone();
+        file_tree
+            .base_folder
+            .add_folder(PathBuf::from("subfolder_0"));
+        file_tree.base_folder.add_file(PathBuf::from("file"), 1024);
+        assert_eq!(
+            file_tree.subfolders_at_path(&base_path),
+            vec![base_path.join("subfolder_0"), base_path.join("subfolder_1")]
+        );
+        assert!(
+            file_tree
+                .subfolders_at_path(&base_path.join("file"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn find_by_name_ignores_case() {
+        let file_tree = file_tree();
+        let base_path = file_tree.path_in_filesystem.clone();
+        assert_eq!(
+            file_tree.find_by_name("SUBFOLDER"),
+            vec![
+                base_path.join("subfolder_1"),
+                base_path.join("subfolder_1/subfolder_2"),
+            ]
+        );
+        assert_eq!(
+            file_tree.find_by_name("fil"),
+            vec![base_path.join("subfolder_1/subfolder_2/file")]
+        );
+        assert!(file_tree.find_by_name("diskonaut").is_empty());
+    }
+
+    #[test]
+    fn max_depth_of_scanned_entries() {
+        let path_in_filesystem = PathBuf::from("/tmp/diskonaut_max_depth_test");
+        let mut file_tree = FileTree::new(
+            Folder::new(&path_in_filesystem),
+            path_in_filesystem.clone(),
+            false,
+        );
+        assert_eq!(file_tree.max_depth(), 0);
+        let folder_metadata = std::fs::metadata(std::env::temp_dir()).unwrap();
+        for relative_path in ["a", "a/b", "a/b/c", "d"] {
+            file_tree.add_entry(&folder_metadata, &path_in_filesystem.join(relative_path));
+        }
+        assert_eq!(file_tree.max_depth(), 3);
+    }
+
+    #[test]
+    fn folder_by_path() {
+        let file_tree = file_tree();
+        let base_path = Path::new("/tmp/diskonaut_file_tree_test");
+        let subfolder_2 = file_tree
+            .get_folder_by_path(&base_path.join("subfolder_1/subfolder_2"))
+            .expect("subfolder_2 is in the tree");
+        assert_eq!(subfolder_2.num_descendants, 1);
+        assert_eq!(subfolder_2.size, 4096);
+        assert!(file_tree.get_folder_by_path(base_path).is_some());
+        assert!(
+            file_tree
+                .get_folder_by_path(&base_path.join("subfolder_1/subfolder_2/file"))
+                .is_none()
+        );
+        assert!(
+            file_tree
+                .get_folder_by_path(&base_path.join("no_such_folder"))
+                .is_none()
+        );
+        assert!(
+            file_tree
+                .get_folder_by_path(Path::new("/somewhere/else"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn replace_subtree() {
+        let mut file_tree = file_tree();
+        file_tree.enter_folder(OsStr::new("subfolder_1"));
+        file_tree.enter_folder(OsStr::new("subfolder_2"));
+        let subtree_path = PathBuf::from("/tmp/diskonaut_file_tree_test/subfolder_1/subfolder_2");
+        let mut subtree_folder = Folder::new(&subtree_path);
+        subtree_folder.add_file(PathBuf::from("other_file"), 1024);
+        file_tree.replace_subtree(FileTree::new(subtree_folder, subtree_path, false));
+        let current_folder = file_tree.get_current_folder();
+        assert!(
+            current_folder
+                .contents
+                .contains_key(OsStr::new("other_file"))
+        );
+        assert!(!current_folder.contents.contains_key(OsStr::new("file")));
+        assert_eq!(file_tree.get_total_size(), 1024);
+        assert_eq!(file_tree.get_total_descendants(), 3);
+        assert!(file_tree.verify_size_consistency().is_empty());
+
+        // the scanned folder itself
+        let root_path = PathBuf::from("/tmp/diskonaut_file_tree_test");
+        file_tree.navigate_to_root();
+        file_tree.replace_subtree(FileTree::new(Folder::new(&root_path), root_path, false));
+        assert_eq!(file_tree.get_current_folder().contents.len(), 0);
+        assert_eq!(file_tree.get_total_size(), 0);
+        assert_eq!(file_tree.get_total_descendants(), 0);
+    }
+
+    #[test]
+    fn replace_subtree_twice_keeps_counts() {
+        let path_in_filesystem = PathBuf::from("/tmp/diskonaut_file_tree_test");
+        let subtree_path = path_in_filesystem.join("subfolder_1");
+        let rescan = || {
+            let mut subtree =
+                FileTree::new(Folder::new(&subtree_path), subtree_path.clone(), false);
+            subtree.base_folder.add_file(PathBuf::from("empty"), 0);
+            subtree.zero_byte_count = 1;
+            subtree.add_failed_to_read(Some((
+                subtree_path.join("unreadable"),
+                String::from("permission denied"),
+            )));
+            subtree
+        };
+        let mut file_tree = file_tree();
+        // outside of what is rescanned, so it stays
+        file_tree.add_failed_to_read(Some((
+            path_in_filesystem.join("elsewhere"),
+            String::from("permission denied"),
+        )));
+        file_tree.replace_subtree(rescan());
+        assert_eq!(file_tree.failed_to_read, 2);
+        assert_eq!(file_tree.failed_paths.len(), 2);
+        assert_eq!(file_tree.zero_byte_count, 1);
+
+        file_tree.replace_subtree(rescan());
+        assert_eq!(file_tree.failed_to_read, 2);
+        assert_eq!(file_tree.failed_paths.len(), 2);
+        assert_eq!(file_tree.zero_byte_count, 1);
+    }
+
+    #[test]
+    fn add_remote_entries() {
+        let path_in_filesystem = PathBuf::from("/srv");
+        let mut file_tree = FileTree::new(
+            Folder::new(&path_in_filesystem),
+            path_in_filesystem.clone(),
+            false,
+        );
+        file_tree.add_remote_entry(&path_in_filesystem.join("logs"), true, 0, None);
+        file_tree.add_remote_entry(
+            &path_in_filesystem.join("logs/old.log"),
+            false,
+            4096,
+            Some(SystemTime::UNIX_EPOCH),
+        );
+        file_tree.add_remote_entry(&path_in_filesystem.join("logs/empty.log"), false, 0, None);
+        assert_eq!(file_tree.get_total_size(), 4096);
+        assert_eq!(file_tree.get_total_descendants(), 3);
+        assert_eq!(file_tree.zero_byte_count, 1);
+        assert_eq!(file_tree.max_depth(), 2);
+        assert_eq!(
+            file_tree.get_current_folder().oldest_modified,
+            Some(SystemTime::UNIX_EPOCH)
+        );
+        assert!(file_tree.verify_size_consistency().is_empty());
+    }
+
+    #[test]
+    fn oldest_modified_is_kept_up_to_date_after_deleting() {
+        let mut file_tree = file_tree();
+        let (old, new) = (
+            SystemTime::UNIX_EPOCH,
+            SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(60),
+        );
+        file_tree.base_folder.add_modified_file(
+            PathBuf::from("subfolder_1/old_file"),
+            1024,
+            Some(old),
+            None,
+        );
+        file_tree.base_folder.add_modified_file(
+            PathBuf::from("subfolder_1/new_file"),
+            1024,
+            Some(new),
+            None,
+        );
+        assert_eq!(file_tree.base_folder.oldest_modified, Some(old));
+        file_tree.delete_file(&FileToDelete {
+            path_in_filesystem: file_tree.path_in_filesystem.clone(),
+            path_to_file: vec![OsString::from("subfolder_1"), OsString::from("old_file")],
+            file_type: FileType::File,
+            num_descendants: None,
+            size: 1024,
+        });
+        assert_eq!(file_tree.base_folder.oldest_modified, Some(new));
+    }
+
+    #[test]
+    fn unexpanded_entries_only_add_their_size() {
+        let mut file_tree = file_tree();
+        file_tree
+            .base_folder
+            .add_folder(PathBuf::from("subfolder_1/opaque"));
+        file_tree
+            .base_folder
+            .add_unexpanded_entry(PathBuf::from("subfolder_1/opaque/file"), 1024);
+        let opaque = file_tree
+            .folder_at_path(Path::new(
+                "/tmp/diskonaut_file_tree_test/subfolder_1/opaque",
+            ))
+            .unwrap();
+        assert!(opaque.unexpanded);
+        assert!(opaque.contents.is_empty());
+        assert_eq!(opaque.size, 1024);
+        assert_eq!(file_tree.get_total_size(), 5120);
+        assert_eq!(file_tree.get_total_descendants(), 4);
+        file_tree.recompute_sizes_from_leaves();
+        assert_eq!(file_tree.get_total_size(), 5120);
+        assert!(file_tree.verify_size_consistency().is_empty());
+    }
+
+    #[test]
+    fn mount_points_are_empty_folders() {
+        let mut file_tree = file_tree();
+        file_tree.add_mount_point(Path::new("/tmp/diskonaut_file_tree_test/subfolder_1/mnt"));
+        let mount_point = file_tree
+            .folder_at_path(Path::new("/tmp/diskonaut_file_tree_test/subfolder_1/mnt"))
+            .unwrap();
+        assert!(mount_point.mount_point);
+        assert_eq!(mount_point.size, 0);
+        assert_eq!(file_tree.get_total_size(), 4096);
+        assert_eq!(file_tree.get_total_descendants(), 4);
     }
 }
@@ -1,5 +1,15 @@
+#[cfg(test)]
+pub use diff_file_tree::*;
+pub use duplicates::*;
 pub use file_or_folder::*;
 pub use file_tree::*;
+pub use space_freed::*;
+pub use walk::*;
 
+#[cfg(test)]
+mod diff_file_tree;
+mod duplicates;
 mod file_or_folder;
 mod file_tree;
+mod space_freed;
+mod walk;
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+#[cfg(not(target_has_atomic = "64"))]
+use std::sync::Mutex;
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// how much deleting has freed so far, shared by whoever holds a clone of it
+// (without a lock where the platform has 64 bit atomics)
+#[derive(Clone, Debug, Default)]
+pub struct SpaceFreed {
+    #[cfg(target_has_atomic = "64")]
+    bytes: Arc<AtomicU64>,
+    #[cfg(not(target_has_atomic = "64"))]
+    bytes: Arc<Mutex<u128>>,
+}
+
+impl SpaceFreed {
+    #[cfg(target_has_atomic = "64")]
+    pub fn get(&self) -> u128 {
+        self.bytes.load(Ordering::Relaxed) as u128
+    }
+    #[cfg(target_has_atomic = "64")]
+    pub fn add(&self, size: u128) {
+        // 16 EiB is more than anyone will delete, but we'd rather stop there than wrap around
+        let size = u64::try_from(size).unwrap_or(u64::MAX);
+        let _ = self
+            .bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bytes| {
+                Some(bytes.saturating_add(size))
+            });
+    }
+    #[cfg(not(target_has_atomic = "64"))]
+    pub fn get(&self) -> u128 {
+        *self
+            .bytes
+            .lock()
+            .expect("could not acquire lock on space freed")
+    }
+    #[cfg(not(target_has_atomic = "64"))]
+    pub fn add(&self, size: u128) {
+        let mut bytes = self
+            .bytes
+            .lock()
+            .expect("could not acquire lock on space freed");
+        *bytes = bytes.saturating_add(size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn clones_share_the_total() {
+        let space_freed = SpaceFreed::default();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let space_freed = space_freed.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        space_freed.add(4096);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("failed to join thread");
+        }
+        assert_eq!(space_freed.get(), 4 * 1000 * 4096);
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::state::files::{File, FileOrFolder, Folder};
+
+#[derive(Debug, Clone, Copy)]
+pub enum NodeRef<'a> {
+    Folder(&'a Folder),
+    File(&'a File),
+}
+
+impl<'a> NodeRef<'a> {
+    pub fn size(&self) -> u128 {
+        match self {
+            NodeRef::Folder(folder) => folder.size,
+            NodeRef::File(file) => file.size,
+        }
+    }
+    pub fn modified(&self) -> Option<SystemTime> {
+        match self {
+            NodeRef::Folder(folder) => folder.oldest_modified,
+            NodeRef::File(file) => file.modified,
+        }
+    }
+    fn children(&self) -> Vec<(PathBuf, NodeRef<'a>)> {
+        // sorted by name so that every walk over the same tree has the same order
+        match self {
+            NodeRef::Folder(folder) => {
+                let mut children: Vec<(PathBuf, NodeRef<'a>)> = folder
+                    .contents
+                    .iter()
+                    .map(|(name, item)| (PathBuf::from(name), NodeRef::from(item)))
+                    .collect();
+                children.sort_by(|(a, _), (b, _)| a.cmp(b));
+                children
+            }
+            NodeRef::File(_) => vec![],
+        }
+    }
+}
+
+impl<'a> From<&'a FileOrFolder> for NodeRef<'a> {
+    fn from(file_or_folder: &'a FileOrFolder) -> Self {
+        match file_or_folder {
+            FileOrFolder::Folder(folder) => NodeRef::Folder(folder),
+            FileOrFolder::File(file) => NodeRef::File(file),
+        }
+    }
+}
+
+type WalkPredicate = fn(&Path, &NodeRef) -> bool;
+
+// an explicit stack rather than recursion, so that very deep trees
+// do not overflow the call stack
+pub struct PreorderIter<'a, F = WalkPredicate> {
+    stack: Vec<(usize, PathBuf, NodeRef<'a>)>,
+    predicate: F,
+}
+
+impl<'a, F> PreorderIter<'a, F>
+where
+    F: Fn(&Path, &NodeRef) -> bool,
+{
+    pub fn new(root_path: PathBuf, root: &'a Folder, predicate: F) -> Self {
+        PreorderIter {
+            stack: vec![(0, root_path, NodeRef::Folder(root))],
+            predicate,
+        }
+    }
+}
+
+impl<'a, F> Iterator for PreorderIter<'a, F>
+where
+    F: Fn(&Path, &NodeRef) -> bool,
+{
+    type Item = (usize, PathBuf, NodeRef<'a>);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (depth, path, node) = self.stack.pop()?;
+            if !(self.predicate)(&path, &node) {
+                // prune this node along with everything under it
+                continue;
+            }
+            for (name, child) in node.children().into_iter().rev() {
+                self.stack.push((depth + 1, path.join(name), child));
+            }
+            return Some((depth, path, node));
+        }
+    }
+}
+
+pub struct PostorderIter<'a> {
+    // the bool is whether this node's children were already pushed
+    stack: Vec<(usize, PathBuf, NodeRef<'a>, bool)>,
+}
+
+impl<'a> PostorderIter<'a> {
+    pub fn new(root_path: PathBuf, root: &'a Folder) -> Self {
+        PostorderIter {
+            stack: vec![(0, root_path, NodeRef::Folder(root), false)],
+        }
+    }
+}
+
+impl<'a> Iterator for PostorderIter<'a> {
+    type Item = (usize, PathBuf, NodeRef<'a>);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (depth, path, node, children_pushed) = self.stack.pop()?;
+            let children = node.children();
+            if children_pushed || children.is_empty() {
+                return Some((depth, path, node));
+            }
+            self.stack.push((depth, path.clone(), node, true));
+            for (name, child) in children.into_iter().rev() {
+                self.stack.push((depth + 1, path.join(name), child, false));
+            }
+        }
+    }
+}
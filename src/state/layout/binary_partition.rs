@@ -0,0 +1,64 @@
+use crate::state::tiles::{HEIGHT_WIDTH_RATIO, RectFloat};
+
+fn split_index(weights: &[f64]) -> usize {
+    // the index that splits the items into two groups of the closest total size
+    let total: f64 = weights.iter().sum();
+    let mut best_index = 1;
+    let mut best_difference = f64::INFINITY;
+    let mut first_half_total = 0.0;
+    for (index, weight) in weights.iter().enumerate().take(weights.len() - 1) {
+        first_half_total += weight;
+        let difference = (total - 2.0 * first_half_total).abs();
+        if difference < best_difference {
+            best_difference = difference;
+            best_index = index + 1;
+        }
+    }
+    best_index
+}
+
+pub fn layout(weights: &[f64], area: &RectFloat) -> Vec<RectFloat> {
+    // the items are split in two groups of roughly the same size, each
+    // getting its share of the area (split along its longer side), and
+    // so on until every group has a single item
+    if weights.len() <= 1 {
+        return weights.iter().map(|_| area.clone()).collect();
+    }
+    let (first_half, second_half) = weights.split_at(split_index(weights));
+    let total: f64 = weights.iter().sum();
+    let fraction = if total > 0.0 {
+        first_half.iter().sum::<f64>() / total
+    } else {
+        first_half.len() as f64 / weights.len() as f64
+    };
+    let (first_area, second_area) = if area.width >= area.height * HEIGHT_WIDTH_RATIO {
+        let first_width = area.width * fraction;
+        (
+            RectFloat {
+                width: first_width,
+                ..area.clone()
+            },
+            RectFloat {
+                x: area.x + first_width,
+                width: area.width - first_width,
+                ..area.clone()
+            },
+        )
+    } else {
+        let first_height = area.height * fraction;
+        (
+            RectFloat {
+                height: first_height,
+                ..area.clone()
+            },
+            RectFloat {
+                y: area.y + first_height,
+                height: area.height - first_height,
+                ..area.clone()
+            },
+        )
+    };
+    let mut rects = layout(first_half, &first_area);
+    rects.extend(layout(second_half, &second_area));
+    rects
+}
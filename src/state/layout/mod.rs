@@ -0,0 +1,139 @@
+use std::{fmt, str::FromStr};
+
+use tui::layout::Rect;
+
+use crate::state::tiles::{
+    FileMetadata, HEIGHT_WIDTH_RATIO, MINIMUM_HEIGHT, MINIMUM_WIDTH, RectFloat, Tile, TreeMap,
+};
+
+mod binary_partition;
+mod slice_and_dice;
+mod strip;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum LayoutAlgorithm {
+    #[default]
+    Squarified,
+    SliceAndDice,
+    Strip,
+    BinaryPartition,
+}
+
+impl LayoutAlgorithm {
+    pub fn next(self) -> Self {
+        match self {
+            LayoutAlgorithm::Squarified => LayoutAlgorithm::SliceAndDice,
+            LayoutAlgorithm::SliceAndDice => LayoutAlgorithm::Strip,
+            LayoutAlgorithm::Strip => LayoutAlgorithm::BinaryPartition,
+            LayoutAlgorithm::BinaryPartition => LayoutAlgorithm::Squarified,
+        }
+    }
+    pub fn layout(self, files: &[FileMetadata], area: &Rect) -> (Vec<Tile>, Option<(u16, u16)>) {
+        match self {
+            LayoutAlgorithm::Squarified => {
+                let mut tree_map = TreeMap::new(area);
+                tree_map.populate_tiles(files.iter().collect());
+                (tree_map.tiles, tree_map.unrenderable_tile_coordinates)
+            }
+            LayoutAlgorithm::SliceAndDice => layout_with(slice_and_dice::layout, files, area),
+            LayoutAlgorithm::Strip => layout_with(strip::layout, files, area),
+            LayoutAlgorithm::BinaryPartition => layout_with(binary_partition::layout, files, area),
+        }
+    }
+}
+
+impl fmt::Display for LayoutAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutAlgorithm::Squarified => write!(f, "squarified"),
+            LayoutAlgorithm::SliceAndDice => write!(f, "slice-and-dice"),
+            LayoutAlgorithm::Strip => write!(f, "strip"),
+            LayoutAlgorithm::BinaryPartition => write!(f, "binary-partition"),
+        }
+    }
+}
+
+impl FromStr for LayoutAlgorithm {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "squarified" => Ok(LayoutAlgorithm::Squarified),
+            "slice-and-dice" => Ok(LayoutAlgorithm::SliceAndDice),
+            "strip" => Ok(LayoutAlgorithm::Strip),
+            "binary-partition" => Ok(LayoutAlgorithm::BinaryPartition),
+            _ => Err(format!(
+                "unknown layout '{}', expected one of: squarified, slice-and-dice, strip, binary-partition",
+                s
+            )),
+        }
+    }
+}
+
+fn is_renderable(tile: &Tile) -> bool {
+    tile.height >= MINIMUM_HEIGHT && tile.width >= MINIMUM_WIDTH
+}
+
+fn visual_aspect_ratio(width: f64, height: f64) -> f64 {
+    // a character is about HEIGHT_WIDTH_RATIO times as high as it is wide,
+    // 1.0 is a perfect square and anything above it is worse
+    let visual_height = height * HEIGHT_WIDTH_RATIO;
+    if width <= 0.0 || visual_height <= 0.0 {
+        f64::INFINITY
+    } else if width > visual_height {
+        width / visual_height
+    } else {
+        visual_height / width
+    }
+}
+
+fn layout_with(
+    layout: fn(&[f64], &RectFloat) -> Vec<RectFloat>,
+    files: &[FileMetadata],
+    area: &Rect,
+) -> (Vec<Tile>, Option<(u16, u16)>) {
+    // files are sorted by size, so each time one of them is too small to
+    // render, it and all the files after it are laid out together as the
+    // last item, which becomes the small files area. Since every algorithm
+    // ends its layout at the bottom right corner, this is where that area
+    // will always be
+    let area = RectFloat::new(area);
+    let mut renderable_count = files.len();
+    loop {
+        let (renderable_files, small_files) = files.split_at(renderable_count);
+        let mut weights: Vec<f64> = renderable_files
+            .iter()
+            .map(|file_metadata| file_metadata.percentage)
+            .collect();
+        if !small_files.is_empty() {
+            weights.push(
+                small_files
+                    .iter()
+                    .map(|file_metadata| file_metadata.percentage)
+                    .sum(),
+            );
+        }
+        let rects = layout(&weights, &area);
+        let tiles: Vec<Tile> = rects
+            .iter()
+            .zip(renderable_files)
+            .map(|(rect, file_metadata)| Tile::new(rect, file_metadata))
+            .collect();
+        match tiles.iter().position(|tile| !is_renderable(tile)) {
+            Some(first_unrenderable) => renderable_count = first_unrenderable,
+            None => {
+                let unrenderable_tile_coordinates = if small_files.is_empty() {
+                    None
+                } else {
+                    rects
+                        .last()
+                        .map(|rect| rect.round())
+                        // if the small files are so small that they don't
+                        // get any room at all, there's nothing to draw
+                        .filter(|rounded| rounded.width > 0 && rounded.height > 0)
+                        .map(|rounded| (rounded.x, rounded.y))
+                };
+                return (tiles, unrenderable_tile_coordinates);
+            }
+        }
+    }
+}
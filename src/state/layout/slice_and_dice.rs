@@ -0,0 +1,36 @@
+use crate::state::tiles::{HEIGHT_WIDTH_RATIO, RectFloat};
+
+pub fn layout(weights: &[f64], area: &RectFloat) -> Vec<RectFloat> {
+    // one level of slice-and-dice: every item gets a slice of the whole area
+    // along its longer side, which is simple but quickly produces thin tiles
+    let total: f64 = weights.iter().sum();
+    let slice_horizontally = area.width >= area.height * HEIGHT_WIDTH_RATIO;
+    let mut progress = 0.0;
+    weights
+        .iter()
+        .map(|weight| {
+            let fraction = if total > 0.0 {
+                weight / total
+            } else {
+                1.0 / weights.len() as f64
+            };
+            let rect = if slice_horizontally {
+                RectFloat {
+                    x: area.x + progress * area.width,
+                    y: area.y,
+                    width: fraction * area.width,
+                    height: area.height,
+                }
+            } else {
+                RectFloat {
+                    x: area.x,
+                    y: area.y + progress * area.height,
+                    width: area.width,
+                    height: fraction * area.height,
+                }
+            };
+            progress += fraction;
+            rect
+        })
+        .collect()
+}
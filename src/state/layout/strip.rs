@@ -0,0 +1,70 @@
+use crate::state::{layout::visual_aspect_ratio, tiles::RectFloat};
+
+fn average_aspect_ratio(row: &[f64], total: f64, area: &RectFloat) -> f64 {
+    let row_total: f64 = row.iter().sum();
+    let row_height = row_total / total * area.height;
+    let sum_of_ratios: f64 = row
+        .iter()
+        .map(|weight| visual_aspect_ratio(weight / row_total * area.width, row_height))
+        .sum();
+    sum_of_ratios / row.len() as f64
+}
+
+fn layout_row(row: &[f64], y: f64, height: f64, area: &RectFloat) -> Vec<RectFloat> {
+    let row_total: f64 = row.iter().sum();
+    let mut x = area.x;
+    row.iter()
+        .map(|weight| {
+            let width = if row_total > 0.0 {
+                weight / row_total * area.width
+            } else {
+                area.width / row.len() as f64
+            };
+            let rect = RectFloat {
+                x,
+                y,
+                width,
+                height,
+            };
+            x += width;
+            rect
+        })
+        .collect()
+}
+
+pub fn layout(weights: &[f64], area: &RectFloat) -> Vec<RectFloat> {
+    // items keep their order and are placed left to right in rows (strips)
+    // stacked from top to bottom. An item joins the current row as long as
+    // that improves the average aspect ratio of the row
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return layout_row(weights, area.y, area.height, area);
+    }
+    let mut rects = Vec::with_capacity(weights.len());
+    let mut y = area.y;
+    let mut row_start = 0;
+    while row_start < weights.len() {
+        let mut row_end = row_start + 1;
+        let mut best_ratio = average_aspect_ratio(&weights[row_start..row_end], total, area);
+        while row_end < weights.len() {
+            let ratio = average_aspect_ratio(&weights[row_start..=row_end], total, area);
+            if ratio > best_ratio {
+                break;
+            }
+            best_ratio = ratio;
+            row_end += 1;
+        }
+        let row = &weights[row_start..row_end];
+        let row_height = if row_end == weights.len() {
+            // the last row takes whatever is left, so that we always
+            // end at the bottom of the area
+            area.y + area.height - y
+        } else {
+            row.iter().sum::<f64>() / total * area.height
+        };
+        rects.extend(layout_row(row, y, row_height, area));
+        y += row_height;
+        row_start = row_end;
+    }
+    rects
+}
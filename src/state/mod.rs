@@ -1,7 +1,19 @@
+pub use config::*;
+pub use file_details::*;
 pub use file_to_delete::*;
+pub use scan_progress::*;
+pub use session::*;
 pub use ui_effects::*;
+pub use warnings_shown::*;
 
+pub mod config;
+pub mod file_details;
 pub mod file_to_delete;
 pub mod files;
+pub mod layout;
+pub mod scan_progress;
+pub mod session;
 pub mod tiles;
+mod toml;
 pub mod ui_effects;
+pub mod warnings_shown;
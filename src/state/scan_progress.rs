@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+// how often the scanner reports its progress, rather than doing so for every entry
+#[cfg(not(test))]
+pub const SCAN_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+#[cfg(test)]
+pub const SCAN_PROGRESS_INTERVAL: Duration = Duration::from_millis(0);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScanProgress {
+    pub files_scanned: u64,
+    pub bytes_scanned: u128,
+    pub directories_entered: u64,
+    pub hardlinked_files: u64, // each file with more than one link, counted once
+}
+
+impl ScanProgress {
+    pub fn add_file(&mut self, size: u128) {
+        self.files_scanned += 1;
+        self.bytes_scanned += size;
+    }
+    pub fn add_directory(&mut self) {
+        self.directories_entered += 1;
+    }
+    pub fn add_hardlinked_file(&mut self) {
+        self.hardlinked_files += 1;
+    }
+    pub fn estimate_remaining(
+        &self,
+        scan_rate: f64,
+        top_level_reached: usize,
+        top_level_total: usize,
+    ) -> Option<Duration> {
+        // going by how many of the scanned folder's own entries the scan got to, out
+        // of how many there are. They're scanned one after the other, so the last one
+        // reached is taken to be half done. This is rough, since some are much larger
+        // than others, but it gets better the further along we are
+        if scan_rate < 1.0 || top_level_reached == 0 || top_level_total == 0 {
+            return None;
+        }
+        let share_done = ((top_level_reached as f64 - 0.5) / top_level_total as f64).min(1.0);
+        let estimated_total = self.files_scanned as f64 / share_done;
+        let files_left = (estimated_total - self.files_scanned as f64).max(0.0);
+        Some(Duration::from_secs_f64(files_left / scan_rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_time_from_the_share_of_entries_reached() {
+        let progress = ScanProgress {
+            files_scanned: 1000,
+            ..ScanProgress::default()
+        };
+        // half way through the second of four, so 1000 files are 3/8 of them all
+        let remaining = progress.estimate_remaining(100.0, 2, 4);
+        assert_eq!(remaining.map(|remaining| remaining.as_secs()), Some(16));
+        // more entries than we counted at the start (eg. created since)
+        let remaining = progress.estimate_remaining(100.0, 5, 4);
+        assert_eq!(remaining, Some(Duration::ZERO));
+        assert_eq!(progress.estimate_remaining(0.0, 2, 4), None);
+        assert_eq!(progress.estimate_remaining(100.0, 0, 4), None);
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::state::{tiles::SortOrder, toml};
+
+// where we were when we last quit, so that we can pick up from there
+// the next time diskonaut is started on the same folder
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionState {
+    pub root_path: PathBuf,
+    pub current_path: PathBuf,
+    pub zoom_level: usize,
+    pub apparent_size: bool,
+    pub sort_order: SortOrder,
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    let cache_dir = match env::var_os("XDG_CACHE_HOME") {
+        Some(cache_dir) => PathBuf::from(cache_dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(cache_dir.join("diskonaut").join("session.toml"))
+}
+
+impl SessionState {
+    pub fn load(root_path: &Path) -> Option<Self> {
+        // anything wrong with the session file means we start from scratch
+        let contents = fs::read_to_string(session_file_path()?).ok()?;
+        let session_state = SessionState::from_toml(&contents)?;
+        if session_state.root_path == root_path && session_state.current_path.is_dir() {
+            Some(session_state)
+        } else {
+            None
+        }
+    }
+    pub fn save(&self) -> io::Result<()> {
+        let session_file_path = session_file_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory"))?;
+        if let Some(session_dir) = session_file_path.parent() {
+            fs::create_dir_all(session_dir)?;
+        }
+        fs::write(session_file_path, self.to_toml())
+    }
+    fn to_toml(&self) -> String {
+        format!(
+            "root_path = {}\ncurrent_path = {}\nzoom_level = {}\napparent_size = {}\nsort_order = {}\n",
+            toml::quote(&self.root_path.to_string_lossy()),
+            toml::quote(&self.current_path.to_string_lossy()),
+            self.zoom_level,
+            self.apparent_size,
+            toml::quote(&self.sort_order.to_string()),
+        )
+    }
+    fn from_toml(contents: &str) -> Option<Self> {
+        let (mut root_path, mut current_path, mut zoom_level, mut apparent_size) =
+            (None, None, None, None);
+        let mut sort_order = None;
+        let path = |value: &str| toml::parse_string(value).map(|(path, _)| PathBuf::from(path));
+        for (_, key, value) in toml::key_values(contents) {
+            match key.as_str() {
+                "root_path" => root_path = path(value),
+                "current_path" => current_path = path(value),
+                "zoom_level" => zoom_level = value.parse().ok(),
+                "apparent_size" => apparent_size = toml::parse_bool(value).map(|(value, _)| value),
+                "sort_order" => {
+                    sort_order = toml::parse_string(value).and_then(|(name, _)| name.parse().ok())
+                }
+                _ => (),
+            }
+        }
+        Some(SessionState {
+            root_path: root_path?,
+            current_path: current_path?,
+            zoom_level: zoom_level?,
+            apparent_size: apparent_size?,
+            // sessions saved before this was remembered
+            sort_order: sort_order.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_state_round_trip() {
+        let session_state = SessionState {
+            root_path: PathBuf::from("/home/user"),
+            current_path: PathBuf::from("/home/user/my \"quoted\" \\ folder"),
+            zoom_level: 2,
+            apparent_size: true,
+            sort_order: SortOrder::ByCount,
+        };
+        assert_eq!(
+            SessionState::from_toml(&session_state.to_toml()),
+            Some(session_state)
+        );
+    }
+
+    #[test]
+    fn incomplete_session_state_is_ignored() {
+        assert_eq!(
+            SessionState::from_toml("root_path = \"/home/user\"\nzoom_level = 2\n"),
+            None
+        );
+    }
+}
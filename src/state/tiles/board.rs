@@ -1,21 +1,77 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
 use tui::layout::Rect;
 
 use crate::state::{
     files::Folder,
+    layout::LayoutAlgorithm,
     tiles::{
-        FileMetadata, Tile, TreeMap,
-        files_in_folder::{FileType, files_in_folder},
+        FileMetadata, MINIMUM_HEIGHT, MINIMUM_WIDTH, RectFloat, SortOrder, Tile,
+        files_in_folder::{FileType, aggregate_zero_byte_files, files_in_folder},
     },
 };
 
+// the smallest tile that still gets drawn, in cells
+const MIN_TILE_AREA: f64 = MINIMUM_WIDTH as f64 * MINIMUM_HEIGHT as f64;
+
+// in scroll mode, how many screens' worth of rows the tiles are laid out on at most
+const MAX_SCROLL_SCREENS: u16 = 8;
+
+type TileFilter = Box<dyn Fn(&Path, &Tile) -> bool>;
+
+fn files_with_a_share(files: &[FileMetadata]) -> usize {
+    // files that take up none of the area never get a tile, however far we zoom in
+    files.iter().filter(|file| file.percentage > 0.0).count()
+}
+
+fn scroll_to_show(offset: u16, start: u16, length: u16, viewport_length: u16) -> u16 {
+    // the smallest change to the offset that brings start..=start + length into view,
+    // or as much of it as fits starting from its beginning
+    if start < offset {
+        start
+    } else if start + length >= offset + viewport_length {
+        (start + length + 1)
+            .saturating_sub(viewport_length)
+            .min(start)
+    } else {
+        offset
+    }
+}
+
 pub struct Board {
     pub tiles: Vec<Tile>,
     pub unrenderable_tile_coordinates: Option<(u16, u16)>,
     pub selected_index: Option<usize>, // None means nothing is selected
     pub previous_indices_and_zoom_level: Vec<(Option<usize>, usize)>, // Stack of previous stats
     pub zoom_level: usize,
+    max_zoom_level: usize,
+    pub layout_algorithm: LayoutAlgorithm,
+    pub sort_order: SortOrder,
+    pub show_gradient: bool,
+    pub show_mime_category: bool,
+    pub color_by_age: bool,
+    pub show_zero_byte_files: bool,
+    pub max_zoom: Option<u8>, // as set by the user, on top of where zooming stops being useful
+    // tiles are laid out on more rows than fit on screen, and we scroll to the selected one
+    pub scroll_mode: bool,
+    pub viewport_offset: (u16, u16), // of the part of the tiles shown on screen, in cells
     area: Rect,
     files: Vec<FileMetadata>,
+    tile_ranks: Vec<usize>, // 0 is the largest tile, same order as tiles
+    current_path: PathBuf,
+    // tiles are recreated whenever the layout changes, so we keep the counts
+    // here rather than only on the tiles themselves
+    access_counts: HashMap<PathBuf, u32>,
+    // hides tiles without touching the folder they come from, see apply_filter
+    filter: Option<TileFilter>,
+    filter_indices: Option<Vec<usize>>, // of the files still shown, among all those in the folder
+    // unlike the filter, tiles that don't match are still shown, only dimmed
+    highlight: Option<TileFilter>,
+    matching_indices: Option<HashSet<usize>>, // of the tiles the highlight matches
 }
 
 impl Board {
@@ -27,6 +83,23 @@ impl Board {
             selected_index: None,
             previous_indices_and_zoom_level: vec![],
             zoom_level: 0,
+            max_zoom_level: 0,
+            layout_algorithm: LayoutAlgorithm::default(),
+            sort_order: SortOrder::default(),
+            show_gradient: false,
+            show_mime_category: false,
+            color_by_age: false,
+            show_zero_byte_files: false,
+            max_zoom: None,
+            scroll_mode: false,
+            viewport_offset: (0, 0),
+            tile_ranks: vec![],
+            current_path: PathBuf::new(),
+            access_counts: HashMap::new(),
+            filter: None,
+            filter_indices: None,
+            highlight: None,
+            matching_indices: None,
             area: Rect {
                 x: 0,
                 y: 0,
@@ -35,8 +108,116 @@ impl Board {
             },
         }
     }
-    pub fn change_files(&mut self, folder: &Folder) {
-        self.files = files_in_folder(folder, self.zoom_level);
+    pub fn change_files(&mut self, folder: &Folder, current_path: &Path) {
+        self.current_path = current_path.to_path_buf();
+        self.update_files(folder);
+        self.fill();
+    }
+    fn update_files(&mut self, folder: &Folder) {
+        let files = files_in_folder(folder, self.zoom_level);
+        let files = if self.show_zero_byte_files {
+            aggregate_zero_byte_files(files)
+        } else {
+            files
+        };
+        let Some(filter) = &self.filter else {
+            self.files = files;
+            self.filter_indices = None;
+            return;
+        };
+        let no_area = RectFloat {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        };
+        let filter_indices: Vec<usize> = files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| {
+                filter(
+                    &self.current_path.join(&file.name),
+                    &Tile::new(&no_area, file),
+                )
+            })
+            .map(|(index, _)| index)
+            .collect();
+        // the files that are left take up the whole area between them
+        let visible_percentage: f64 = filter_indices
+            .iter()
+            .map(|index| files[*index].percentage)
+            .sum();
+        self.files = filter_indices
+            .iter()
+            .map(|index| FileMetadata {
+                percentage: files[*index].percentage / visible_percentage,
+                ..files[*index].clone()
+            })
+            .collect();
+        self.filter_indices = Some(filter_indices);
+    }
+    pub fn apply_filter(
+        &mut self,
+        folder: &Folder,
+        predicate: impl Fn(&Path, &Tile) -> bool + 'static,
+    ) {
+        // the filter stays in place when the folder changes (eg. while scanning)
+        // until clear_filter
+        self.filter = Some(Box::new(predicate));
+        self.reset_selected_index();
+        self.update_files(folder);
+        self.fill();
+    }
+    pub fn clear_filter(&mut self, folder: &Folder) {
+        self.filter = None;
+        self.reset_selected_index();
+        self.update_files(folder);
+        self.fill();
+    }
+    pub fn highlight_matching(&mut self, predicate: impl Fn(&Path, &Tile) -> bool + 'static) {
+        // like the filter, this stays in place when the tiles change until clear_highlight
+        self.highlight = Some(Box::new(predicate));
+        self.match_tiles();
+    }
+    pub fn clear_highlight(&mut self) {
+        self.highlight = None;
+        self.matching_indices = None;
+    }
+    pub fn matching_indices(&self) -> Option<&HashSet<usize>> {
+        self.matching_indices.as_ref()
+    }
+    fn match_tiles(&mut self) {
+        self.matching_indices = self.highlight.as_ref().map(|highlight| {
+            self.tiles
+                .iter()
+                .enumerate()
+                .filter(|(_, tile)| highlight(&self.current_path.join(&tile.name), tile))
+                .map(|(index, _)| index)
+                .collect()
+        });
+    }
+    pub fn is_filtered(&self) -> bool {
+        self.filter_indices.is_some()
+    }
+    pub fn filtered_stats(&self) -> Option<(u128, u64)> {
+        // the size and number of descendants of only the files that are shown
+        self.filter_indices.as_ref()?;
+        let size = self.files.iter().map(|file| file.size).sum();
+        let descendants = self
+            .files
+            .iter()
+            .map(|file| match (file.zero_byte_files, file.descendants) {
+                (Some(zero_byte_files), _) => zero_byte_files,
+                (None, Some(descendants)) => descendants + 1,
+                (None, None) => 1,
+            })
+            .sum();
+        Some((size, descendants))
+    }
+    pub fn toggle_zero_byte_files(&mut self, folder: &Folder) {
+        self.show_zero_byte_files = !self.show_zero_byte_files;
+        self.reset_selected_index();
+        self.update_files(folder);
         self.fill();
     }
     pub fn change_area(&mut self, area: &Rect) {
@@ -45,33 +226,279 @@ impl Board {
             self.fill();
         }
     }
+    pub fn toggle_scroll_mode(&mut self) {
+        self.scroll_mode = !self.scroll_mode;
+        self.viewport_offset = (0, 0);
+        self.fill();
+        self.scroll_to_selected_index();
+    }
+    pub fn layout_area(&self) -> Rect {
+        // in scroll mode, tall enough for the smallest file to get a tile of its own
+        // as long as that's not too many screens down
+        if !self.scroll_mode || self.area.width == 0 {
+            return self.area;
+        }
+        let smallest_share = self
+            .files
+            .iter()
+            .map(|file| file.percentage)
+            .filter(|percentage| *percentage > 0.0)
+            .fold(1.0, f64::min);
+        let rows_needed = (MIN_TILE_AREA / smallest_share / self.area.width as f64).ceil();
+        let max_rows = self.area.height.saturating_mul(MAX_SCROLL_SCREENS);
+        Rect {
+            height: rows_needed.clamp(self.area.height as f64, max_rows as f64) as u16,
+            ..self.area
+        }
+    }
+    pub fn scroll_to_selected_index(&mut self) {
+        // otherwise all tiles are on screen anyway
+        if !self.scroll_mode {
+            return;
+        }
+        let Some(tile) = self.tile_at_cursor() else {
+            return;
+        };
+        // the right and bottom borders are drawn one cell past the area
+        let (offset_x, offset_y) = self.viewport_offset;
+        self.viewport_offset = (
+            scroll_to_show(
+                offset_x,
+                tile.x - self.area.x,
+                tile.width,
+                self.area.width + 1,
+            ),
+            scroll_to_show(
+                offset_y,
+                tile.y - self.area.y,
+                tile.height,
+                self.area.height + 1,
+            ),
+        );
+        self.clamp_viewport_offset();
+    }
+    fn clamp_viewport_offset(&mut self) {
+        // so that we don't scroll past the last row after the tiles change
+        let layout_area = self.layout_area();
+        let (offset_x, offset_y) = self.viewport_offset;
+        self.viewport_offset = (
+            offset_x.min(layout_area.width - self.area.width),
+            offset_y.min(layout_area.height - self.area.height),
+        );
+    }
+    pub fn cycle_layout_algorithm(&mut self) {
+        self.layout_algorithm = self.layout_algorithm.next();
+        self.reset_selected_index();
+        self.fill();
+    }
+    pub fn cycle_sort_order(&mut self) {
+        self.sort_order = self.sort_order.next();
+        self.reset_selected_index();
+        self.fill();
+    }
     fn fill(&mut self) {
-        let mut tree_map = TreeMap::new(&self.area);
-        tree_map.populate_tiles(self.files.iter().collect());
-        self.tiles = tree_map.tiles;
-        self.unrenderable_tile_coordinates = tree_map.unrenderable_tile_coordinates;
+        let layout_area = self.layout_area();
+        let (tiles, unrenderable_tile_coordinates) =
+            self.layout_algorithm.layout(&self.files, &layout_area);
+        let (tiles, unrenderable_tile_coordinates) = match self.sort_order {
+            SortOrder::BySize => (tiles, unrenderable_tile_coordinates),
+            sort_order => {
+                // the files that got a tile by size are laid out again in the sort order,
+                // unless that leaves some of them too small for a tile of their own
+                let mut sorted_files = self.files.clone();
+                sort_order.sort(&mut sorted_files[..tiles.len()]);
+                let (sorted_tiles, sorted_unrenderable_tile_coordinates) =
+                    self.layout_algorithm.layout(&sorted_files, &layout_area);
+                if sorted_tiles.len() == tiles.len() {
+                    (sorted_tiles, sorted_unrenderable_tile_coordinates)
+                } else {
+                    (tiles, unrenderable_tile_coordinates)
+                }
+            }
+        };
+        self.tiles = tiles;
+        self.unrenderable_tile_coordinates = unrenderable_tile_coordinates;
+        self.max_zoom_level = self.zoom_level + self.zoom_levels_until_all_tiles_fit();
+        self.rank_tiles();
+        self.match_tiles();
+        self.clamp_viewport_offset();
+        for tile in self.tiles.iter_mut() {
+            tile.access_count = self
+                .access_counts
+                .get(&self.current_path.join(&tile.name))
+                .copied()
+                .unwrap_or(0);
+        }
+    }
+    fn rank_tiles(&mut self) {
+        let mut indices_by_size: Vec<usize> = (0..self.tiles.len()).collect();
+        indices_by_size.sort_by(|a, b| self.tiles[*b].size.cmp(&self.tiles[*a].size));
+        self.tile_ranks = vec![0; self.tiles.len()];
+        for (rank, index) in indices_by_size.into_iter().enumerate() {
+            self.tile_ranks[index] = rank;
+        }
+    }
+    pub fn tile_ranks(&self) -> Option<&[usize]> {
+        if self.show_gradient {
+            Some(&self.tile_ranks)
+        } else {
+            None
+        }
     }
     pub fn get_selected_index(&self) -> Option<usize> {
         self.selected_index
     }
     pub fn set_selected_index(&mut self, next_index: &usize) {
         self.selected_index = Some(*next_index);
+        self.count_access_to_selected();
+        self.scroll_to_selected_index();
+    }
+    pub fn count_access_to_selected(&mut self) {
+        let Some(path) = self.cursor_path(&self.current_path) else {
+            return;
+        };
+        if let Some(tile) = self.tile_at_cursor_mut() {
+            tile.access_count += 1;
+            let access_count = tile.access_count;
+            self.access_counts.insert(path, access_count);
+        }
+    }
+    pub fn most_accessed(&self, count: usize) -> Vec<(PathBuf, u32)> {
+        let mut most_accessed: Vec<(PathBuf, u32)> = self
+            .access_counts
+            .iter()
+            .map(|(path, access_count)| (path.clone(), *access_count))
+            .collect();
+        most_accessed.sort_by(|(path_a, count_a), (path_b, count_b)| {
+            count_b.cmp(count_a).then_with(|| path_a.cmp(path_b))
+        });
+        most_accessed.truncate(count);
+        most_accessed
     }
     pub fn has_selected_index(&self) -> bool {
         self.selected_index.is_some()
     }
+    pub fn select_by_name(&mut self, name: &OsStr) -> bool {
+        // false if there's no tile by that name, eg. it's among the small files
+        match self.tiles.iter().position(|tile| tile.name == *name) {
+            Some(index) => {
+                self.set_selected_index(&index);
+                true
+            }
+            None => false,
+        }
+    }
     pub fn reset_selected_index(&mut self) {
         self.selected_index = None;
     }
-    pub fn currently_selected(&self) -> Option<&Tile> {
-        match &self.selected_index {
-            Some(selected_index) => self.tiles.get(*selected_index),
-            None => None,
+    pub fn tile_at_cursor(&self) -> Option<&Tile> {
+        self.tiles.get(self.selected_index?)
+    }
+    pub fn tile_at_cursor_mut(&mut self) -> Option<&mut Tile> {
+        self.tiles.get_mut(self.selected_index?)
+    }
+    pub fn cursor_path(&self, current_dir: &Path) -> Option<PathBuf> {
+        self.tile_at_cursor()
+            .map(|tile| current_dir.join(&tile.name))
+    }
+    pub fn tiles_visible_count(&self) -> usize {
+        self.tiles.len()
+    }
+    pub fn estimate_unrenderable_count(&self, area: Rect) -> u64 {
+        // a rough guess that doesn't need a layout: files worth less than
+        // one cell of the area are unlikely to get a tile of their own
+        let cells = area.width as u128 * area.height as u128;
+        if cells == 0 {
+            return self.files.len() as u64;
+        }
+        let total_size: u128 = self.files.iter().map(|file| file.size).sum();
+        let cell_size = total_size / cells;
+        self.files
+            .iter()
+            .filter(|file| file.size < cell_size)
+            .count() as u64
+    }
+    pub fn max_zoom_level(&self) -> u8 {
+        // past this level, zooming in would not show anything that isn't already shown
+        let max_zoom = self.max_zoom.unwrap_or(u8::MAX);
+        self.max_zoom_level.min(max_zoom as usize) as u8
+    }
+    fn zoom_levels_until_all_tiles_fit(&self) -> usize {
+        if self.tiles.len() >= files_with_a_share(&self.files) {
+            return 0;
         }
+        // each zoom level leaves out the largest remaining file. A file needs at least
+        // MIN_TILE_AREA cells to get a tile, which tells us the first level worth trying
+        let layout_area = self.layout_area();
+        let cells = layout_area.width as f64 * layout_area.height as f64;
+        let Some(smallest_size) = self
+            .files
+            .iter()
+            .map(|file| file.size)
+            .filter(|size| *size > 0)
+            .min()
+        else {
+            return 0;
+        };
+        let mut remaining_size: u128 = self.files.iter().map(|file| file.size).sum();
+        let mut first_level = 0;
+        for file in &self.files {
+            if smallest_size as f64 / remaining_size as f64 * cells >= MIN_TILE_AREA {
+                break;
+            }
+            remaining_size -= file.size;
+            first_level += 1;
+        }
+        // the shape of the tiles might still leave some of them out, so we lay them out to make sure
+        for level in first_level.max(1)..self.files.len() {
+            let remaining_files = &self.files[level..];
+            let remaining_percentage: f64 =
+                remaining_files.iter().map(|file| file.percentage).sum();
+            let zoomed_files: Vec<FileMetadata> = remaining_files
+                .iter()
+                .map(|file| FileMetadata {
+                    percentage: file.percentage / remaining_percentage,
+                    ..file.clone()
+                })
+                .collect();
+            let (tiles, _) = self.layout_algorithm.layout(&zoomed_files, &layout_area);
+            if tiles.len() >= files_with_a_share(&zoomed_files) {
+                return level;
+            }
+        }
+        self.files.len().saturating_sub(1)
+    }
+    pub fn tiles_hidden_count(&self) -> usize {
+        // files that did not get a tile of their own are drawn together
+        // in the "small files" area
+        self.files.len().saturating_sub(self.tiles.len())
     }
     pub fn pop_previous_index_and_zoom_level(&mut self) -> Option<(Option<usize>, usize)> {
         self.previous_indices_and_zoom_level.pop()
     }
+    pub fn select_tile_at_position(&mut self, column: u16, row: u16) -> bool {
+        // column and row are on screen, where the tiles are shown scrolled
+        // by the viewport offset in scroll mode
+        let area = self.area;
+        if column < area.x || column >= area.x + area.width {
+            return false;
+        }
+        if row < area.y || row >= area.y + area.height {
+            return false;
+        }
+        let (offset_x, offset_y) = self.viewport_offset;
+        let (x, y) = (column + offset_x, row + offset_y);
+        let clicked_index = self.tiles.iter().position(|tile| {
+            x >= tile.x && x < tile.x + tile.width && y >= tile.y && y < tile.y + tile.height
+        });
+        match clicked_index {
+            Some(index) => {
+                self.set_selected_index(&index);
+                true
+            }
+            None => false,
+        }
+    }
     pub fn move_to_largest_folder(&mut self) {
         let next_index = self
             .tiles
@@ -86,7 +513,7 @@ impl Board {
         }
     }
     pub fn move_selected_right(&mut self) {
-        match self.currently_selected() {
+        match self.tile_at_cursor() {
             Some(currently_selected) => {
                 let next_index = self
                     .tiles
@@ -108,7 +535,7 @@ impl Board {
         }
     }
     pub fn move_selected_left(&mut self) {
-        match self.currently_selected() {
+        match self.tile_at_cursor() {
             Some(currently_selected) => {
                 let next_index = self
                     .tiles
@@ -130,7 +557,7 @@ impl Board {
         }
     }
     pub fn move_selected_down(&mut self) {
-        match self.currently_selected() {
+        match self.tile_at_cursor() {
             Some(currently_selected) => {
                 let next_index = self
                     .tiles
@@ -152,7 +579,7 @@ impl Board {
         }
     }
     pub fn move_selected_up(&mut self) {
-        match self.currently_selected() {
+        match self.tile_at_cursor() {
             Some(currently_selected) => {
                 let next_index = self
                     .tiles
@@ -173,23 +600,26 @@ impl Board {
             None => self.set_selected_index(&0),
         }
     }
-    pub fn zoom_in(&mut self, folder: &Folder) {
-        if self.zoom_level < self.files.len() {
-            self.zoom_level += 1;
-            self.files = files_in_folder(folder, self.zoom_level);
-            self.fill();
-        }
-    }
-    pub fn zoom_out(&mut self, folder: &Folder) {
-        if self.zoom_level > 0 {
-            self.zoom_level -= 1;
-            self.files = files_in_folder(folder, self.zoom_level);
-            self.fill();
+    pub fn change_zoom(&mut self, folder: &Folder, delta: i8) -> bool {
+        // returns false if we're already as far in or out as we can go.
+        // there's always at least one file left to show
+        let max_zoom_level = (self.max_zoom_level() as usize)
+            .min(self.zoom_level + self.files.len().saturating_sub(1));
+        let zoom_level = self
+            .zoom_level
+            .saturating_add_signed(delta as isize)
+            .min(max_zoom_level.max(self.zoom_level));
+        if zoom_level == self.zoom_level {
+            return false;
         }
+        self.zoom_level = zoom_level;
+        self.update_files(folder);
+        self.fill();
+        true
     }
     pub fn reset_zoom(&mut self, folder: &Folder) {
         self.zoom_level = 0;
-        self.files = files_in_folder(folder, self.zoom_level);
+        self.update_files(folder);
         self.fill();
     }
     pub fn reset_zoom_index(&mut self) {
@@ -198,8 +628,375 @@ impl Board {
     pub fn set_zoom_index(&mut self, index: usize) {
         self.zoom_level = index;
     }
+    pub fn take_root_index(&mut self) -> Option<usize> {
+        // what was selected in the base folder before we left it, forgetting
+        // everything recorded in the folders since
+        self.previous_indices_and_zoom_level
+            .drain(..)
+            .next()
+            .and_then(|(index, _)| index)
+    }
     pub fn record_current_index_and_zoom_level(&mut self) {
         self.previous_indices_and_zoom_level
             .push((self.get_selected_index(), self.zoom_level));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsString, path::PathBuf};
+
+    use super::*;
+
+    fn board_with_files(file_sizes: &[u128]) -> (Board, Folder) {
+        let mut folder = Folder::new(&PathBuf::from("/tmp/diskonaut_board_test"));
+        for (index, size) in file_sizes.iter().enumerate() {
+            folder.add_file(PathBuf::from(format!("file{}", index)), *size);
+        }
+        let mut board = Board::new(&folder);
+        board.change_area(&Rect {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 40,
+        });
+        (board, folder)
+    }
+
+    #[test]
+    fn tile_counts_without_small_files() {
+        let (board, _) = board_with_files(&[4096, 4096, 4096]);
+        assert_eq!(board.tiles_visible_count(), 3);
+        assert_eq!(board.tiles_hidden_count(), 0);
+        assert!(board.unrenderable_tile_coordinates.is_none());
+    }
+
+    #[test]
+    fn zero_byte_files_tile() {
+        let (mut board, folder) = board_with_files(&[8192, 0, 4096, 0]);
+        // on their own, zero-byte files are too small to get a tile
+        assert_eq!(board.tiles.len(), 2);
+        board.toggle_zero_byte_files(&folder);
+        assert_eq!(board.tiles.len(), 3);
+        let zero_byte_tile = board
+            .tiles
+            .iter()
+            .find(|tile| tile.zero_byte_files.is_some())
+            .expect("zero-byte files tile");
+        assert_eq!(zero_byte_tile.zero_byte_files, Some(2));
+        assert_eq!(zero_byte_tile.name, "2 zero-byte files (0 B)");
+        let total_percentage: f64 = board.tiles.iter().map(|tile| tile.percentage).sum();
+        assert!((total_percentage - 1.0).abs() < 0.0001);
+        board.toggle_zero_byte_files(&folder);
+        assert_eq!(board.tiles.len(), 2);
+    }
+
+    #[test]
+    fn tile_at_cursor() {
+        let (mut board, _) = board_with_files(&[]);
+        assert!(board.tile_at_cursor().is_none());
+        assert!(board.tile_at_cursor_mut().is_none());
+
+        let (mut board_with_tiles, _) = board_with_files(&[8192, 4096]);
+        assert!(board_with_tiles.tile_at_cursor().is_none());
+        assert!(board_with_tiles.cursor_path(Path::new("/tmp")).is_none());
+        board_with_tiles.set_selected_index(&1);
+        assert_eq!(board_with_tiles.tile_at_cursor().unwrap().size, 4096);
+        assert_eq!(
+            board_with_tiles.cursor_path(Path::new("/tmp")),
+            Some(PathBuf::from("/tmp/file1"))
+        );
+        board_with_tiles.tile_at_cursor_mut().unwrap().access_count = 5;
+        assert_eq!(board_with_tiles.tiles[1].access_count, 5);
+
+        // selecting an index with no tile behind it
+        board.set_selected_index(&3);
+        assert!(board.tile_at_cursor_mut().is_none());
+    }
+
+    #[test]
+    fn sort_order_changes_tile_order_only() {
+        let mut folder = Folder::new(&PathBuf::from("/tmp/diskonaut_board_test"));
+        for (name, size) in [("c_file", 8192), ("a_file", 4096), ("B_file", 2048)] {
+            folder.add_file(PathBuf::from(name), size);
+        }
+        let mut board = Board::new(&folder);
+        board.change_area(&Rect::new(0, 0, 100, 40));
+        let tile_names = |board: &Board| -> Vec<String> {
+            board
+                .tiles
+                .iter()
+                .map(|tile| tile.name.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(tile_names(&board), ["c_file", "a_file", "B_file"]);
+
+        board.set_selected_index(&1);
+        board.cycle_sort_order();
+        assert_eq!(board.sort_order, SortOrder::ByName);
+        assert_eq!(tile_names(&board), ["a_file", "B_file", "c_file"]);
+        assert_eq!(board.selected_index, None);
+
+        board.cycle_sort_order();
+        board.cycle_sort_order();
+        assert_eq!(board.sort_order, SortOrder::BySize);
+        assert_eq!(tile_names(&board), ["c_file", "a_file", "B_file"]);
+    }
+
+    #[test]
+    fn select_tile_by_position() {
+        let (mut board, _) = board_with_files(&[8192, 4096]);
+        let tile = board.tiles[1].clone();
+        assert!(board.select_tile_at_position(tile.x + tile.width / 2, tile.y + tile.height / 2));
+        assert_eq!(board.selected_index, Some(1));
+
+        // outside the board, nothing changes
+        assert!(!board.select_tile_at_position(150, 10));
+        assert!(!board.select_tile_at_position(10, 50));
+        assert_eq!(board.selected_index, Some(1));
+    }
+
+    #[test]
+    fn estimated_unrenderable_count() {
+        let mut file_sizes = vec![1_000_000];
+        file_sizes.extend(std::iter::repeat_n(1, 50));
+        let (board, _) = board_with_files(&file_sizes);
+        let area = Rect::new(0, 0, 100, 40);
+        assert_eq!(board.estimate_unrenderable_count(area), 50);
+        assert_eq!(board.estimate_unrenderable_count(Rect::new(0, 0, 0, 0)), 51);
+
+        let (board, _) = board_with_files(&[4096, 4096, 4096]);
+        assert_eq!(board.estimate_unrenderable_count(area), 0);
+    }
+
+    #[test]
+    fn tile_counts_with_small_files() {
+        let mut file_sizes = vec![1_000_000];
+        file_sizes.extend(std::iter::repeat_n(1, 50));
+        let (board, _) = board_with_files(&file_sizes);
+        assert_eq!(board.tiles_visible_count(), board.tiles.len());
+        assert_eq!(
+            board.tiles_visible_count() + board.tiles_hidden_count(),
+            file_sizes.len()
+        );
+        assert!(board.tiles_hidden_count() > 0);
+    }
+
+    #[test]
+    fn max_zoom_level() {
+        let (board, _) = board_with_files(&[4096, 4096, 4096]);
+        assert_eq!(board.max_zoom_level(), 0, "everything fits already");
+
+        let (mut board, folder) = board_with_files(&[1_000_000, 100_000, 10_000, 1_000, 10]);
+        let max_zoom_level = board.max_zoom_level();
+        assert!(max_zoom_level > 0);
+        for _ in 0..max_zoom_level {
+            assert!(board.tiles_hidden_count() > 0);
+            assert!(board.change_zoom(&folder, 1));
+            assert_eq!(
+                board.max_zoom_level(),
+                max_zoom_level,
+                "does not change when zooming"
+            );
+        }
+        assert_eq!(board.zoom_level, max_zoom_level as usize);
+        assert_eq!(board.tiles_hidden_count(), 0);
+        assert!(!board.change_zoom(&folder, 1), "already at maximum zoom");
+    }
+
+    #[test]
+    fn change_zoom_within_bounds() {
+        let (mut board, folder) = board_with_files(&[1_000_000, 100_000, 10_000, 1_000, 10]);
+        assert!(!board.change_zoom(&folder, -1), "already at minimum zoom");
+        board.max_zoom = Some(1);
+        assert_eq!(board.max_zoom_level(), 1);
+        assert!(board.change_zoom(&folder, 1));
+        assert!(!board.change_zoom(&folder, 1));
+        assert_eq!(board.zoom_level, 1);
+        assert!(board.change_zoom(&folder, -1));
+        assert_eq!(board.zoom_level, 0);
+    }
+
+    #[test]
+    fn tile_counts_after_zoom() {
+        let mut file_sizes = vec![1_000_000, 500_000];
+        file_sizes.extend(std::iter::repeat_n(1, 50));
+        let (mut board, folder) = board_with_files(&file_sizes);
+        let hidden_before_zoom = board.tiles_hidden_count();
+
+        assert!(board.change_zoom(&folder, 1));
+        assert_eq!(board.tiles_visible_count(), board.tiles.len());
+        assert_eq!(
+            board.tiles_visible_count() + board.tiles_hidden_count(),
+            file_sizes.len() - 1
+        );
+
+        assert!(board.change_zoom(&folder, 1));
+        assert_eq!(
+            board.tiles_visible_count() + board.tiles_hidden_count(),
+            file_sizes.len() - 2
+        );
+        assert!(board.tiles_hidden_count() < hidden_before_zoom);
+
+        board.reset_zoom(&folder);
+        assert_eq!(board.tiles_hidden_count(), hidden_before_zoom);
+        assert_eq!(
+            board.tiles_visible_count() + board.tiles_hidden_count(),
+            file_sizes.len()
+        );
+    }
+
+    #[test]
+    fn access_counts_survive_layout_changes() {
+        let (mut board, folder) = board_with_files(&[4096, 16384, 8192]);
+        board.change_files(&folder, Path::new("/tmp/diskonaut_board_test"));
+        board.set_selected_index(&0);
+        board.move_selected_right();
+        board.set_selected_index(&0);
+        let selected_name = board.tiles[0].name.clone();
+        assert_eq!(board.tiles[0].access_count, 2);
+
+        board.change_area(&Rect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 30,
+        });
+        let tile = board
+            .tiles
+            .iter()
+            .find(|tile| tile.name == selected_name)
+            .expect("tile should still be there");
+        assert_eq!(tile.access_count, 2);
+        assert_eq!(
+            board.most_accessed(1),
+            vec![(
+                Path::new("/tmp/diskonaut_board_test").join(&selected_name),
+                2
+            )]
+        );
+    }
+
+    #[test]
+    fn tile_ranks_by_size() {
+        let (mut board, _) = board_with_files(&[4096, 16384, 8192]);
+        assert!(board.tile_ranks().is_none());
+
+        board.show_gradient = true;
+        let tile_ranks = board.tile_ranks().expect("gradient should be shown");
+        assert_eq!(tile_ranks.len(), board.tiles.len());
+        for (tile, rank) in board.tiles.iter().zip(tile_ranks) {
+            let expected_rank = match tile.size {
+                16384 => 0,
+                8192 => 1,
+                _ => 2,
+            };
+            assert_eq!(*rank, expected_rank);
+        }
+    }
+
+    #[test]
+    fn filter_hides_tiles_until_cleared() {
+        let (mut board, folder) = board_with_files(&[8192, 4096, 4096]);
+        board.change_files(&folder, Path::new("/tmp/diskonaut_board_test"));
+        board.apply_filter(&folder, |path, tile| {
+            tile.size == 4096 && path.starts_with("/tmp/diskonaut_board_test")
+        });
+        assert!(board.is_filtered());
+        assert_eq!(board.tiles.len(), 2);
+        assert!(board.tiles.iter().all(|tile| tile.size == 4096));
+        assert_eq!(board.filter_indices, Some(vec![1, 2]));
+        assert_eq!(board.filtered_stats(), Some((8192, 2)));
+        // still filtered once the files are read again
+        board.change_files(&folder, Path::new("/tmp/diskonaut_board_test"));
+        assert_eq!(board.tiles.len(), 2);
+        board.clear_filter(&folder);
+        assert!(!board.is_filtered());
+        assert_eq!(board.tiles.len(), 3);
+        assert_eq!(board.filtered_stats(), None);
+    }
+
+    #[test]
+    fn highlight_matches_tiles_until_cleared() {
+        let (mut board, _) = board_with_files(&[4096, 8192, 16384]);
+        assert_eq!(board.matching_indices(), None);
+        board.highlight_matching(|_, tile| tile.size >= 8192);
+        assert_eq!(
+            board.matching_indices().map(|indices| indices.len()),
+            Some(2)
+        );
+        assert!(
+            board.tiles.len() == 3,
+            "tiles that don't match are still shown"
+        );
+        board.change_area(&Rect {
+            x: 0,
+            y: 0,
+            width: 60,
+            height: 30,
+        });
+        let matching_sizes: HashSet<u128> = board
+            .matching_indices()
+            .unwrap()
+            .iter()
+            .map(|index| board.tiles[*index].size)
+            .collect();
+        assert_eq!(matching_sizes, HashSet::from([8192, 16384]));
+        board.clear_highlight();
+        assert_eq!(board.matching_indices(), None);
+    }
+    #[test]
+    fn select_tile_by_name() {
+        let (mut board, _) = board_with_files(&[4096, 8192]);
+        assert!(board.select_by_name(OsStr::new("file0")));
+        assert_eq!(
+            board.tile_at_cursor().map(|tile| tile.name.clone()),
+            Some(OsString::from("file0"))
+        );
+        assert!(!board.select_by_name(OsStr::new("no_such_file")));
+        assert_eq!(
+            board.tile_at_cursor().map(|tile| tile.name.clone()),
+            Some(OsString::from("file0"))
+        );
+    }
+    #[test]
+    fn scroll_to_show_moves_as_little_as_needed() {
+        // already in view
+        assert_eq!(scroll_to_show(0, 5, 10, 40), 0);
+        // above and below the viewport
+        assert_eq!(scroll_to_show(20, 5, 10, 40), 5);
+        assert_eq!(scroll_to_show(0, 50, 10, 40), 21);
+        // taller than the viewport, so we show its beginning
+        assert_eq!(scroll_to_show(0, 50, 60, 40), 50);
+    }
+    #[test]
+    fn scroll_mode_keeps_the_selected_tile_in_view() {
+        let mut file_sizes = vec![2_000_000];
+        file_sizes.extend([4096; 40]);
+        let (mut board, _) = board_with_files(&file_sizes);
+        let tiles_when_zooming = board.tiles.len();
+
+        board.toggle_scroll_mode();
+        assert!(board.layout_area().height > 40);
+        assert!(board.tiles.len() > tiles_when_zooming);
+        assert_eq!(board.viewport_offset, (0, 0));
+
+        let lowest_index = board
+            .tiles
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, tile)| tile.y)
+            .map(|(index, _)| index)
+            .unwrap();
+        board.set_selected_index(&lowest_index);
+        let (_, offset_y) = board.viewport_offset;
+        let selected = board.tile_at_cursor().unwrap();
+        assert!(offset_y > 0);
+        assert!(selected.y >= offset_y);
+        assert!(selected.y + selected.height <= offset_y + 40);
+
+        board.toggle_scroll_mode();
+        assert_eq!(board.layout_area().height, 40);
+        assert_eq!(board.viewport_offset, (0, 0));
+    }
+}
@@ -1,4 +1,4 @@
-use std::ffi::OsString;
+use std::{ffi::OsString, time::SystemTime};
 
 use crate::state::files::{FileOrFolder, Folder};
 
@@ -15,8 +15,17 @@ pub struct FileMetadata {
     pub descendants: Option<u64>,
     pub percentage: f64, // 1.0 is 100% (0.5 is 50%, etc.)
     pub file_type: FileType,
+    pub zero_byte_files: Option<u64>, // only set on the tile standing in for all zero-byte files
+    pub modified: Option<SystemTime>, // the oldest of anything inside, for folders
+    pub num_hardlinks: Option<u32>,
+    pub unexpanded: bool, // for folders at --depth, see Folder::unexpanded
+    pub mount_point: bool,
 }
 
+// how much of the folder the zero-byte files tile takes up, since they
+// have no size of their own to speak of
+const ZERO_BYTE_FILES_SHARE: f64 = 0.05;
+
 fn calculate_percentage(size: u128, total_size: u128, total_files_in_parent: usize) -> f64 {
     if size == 0 && total_size == 0 {
         // if all files in the folder are of size 0, we'll want to display them all as
@@ -34,10 +43,19 @@ pub fn files_in_folder(folder: &Folder, offset: usize) -> Vec<FileMetadata> {
         files.push({
             let size = file_or_folder.size();
             let name = name.clone();
-            let (descendants, file_type) = match file_or_folder {
-                FileOrFolder::Folder(folder) => (Some(folder.num_descendants), FileType::Folder),
-                FileOrFolder::File(_file) => (None, FileType::File),
-            };
+            let (descendants, file_type, num_hardlinks, unexpanded, mount_point) =
+                match file_or_folder {
+                    FileOrFolder::Folder(folder) => (
+                        Some(folder.num_descendants),
+                        FileType::Folder,
+                        None,
+                        folder.unexpanded,
+                        folder.mount_point,
+                    ),
+                    FileOrFolder::File(file) => {
+                        (None, FileType::File, file.num_hardlinks, false, false)
+                    }
+                };
             let percentage = calculate_percentage(size, total_size, folder.contents.len());
             FileMetadata {
                 size,
@@ -45,6 +63,11 @@ pub fn files_in_folder(folder: &Folder, offset: usize) -> Vec<FileMetadata> {
                 descendants,
                 percentage,
                 file_type,
+                zero_byte_files: None,
+                modified: file_or_folder.modified(),
+                num_hardlinks,
+                unexpanded,
+                mount_point,
             }
         });
     }
@@ -72,3 +95,43 @@ pub fn files_in_folder(folder: &Folder, offset: usize) -> Vec<FileMetadata> {
     }
     files
 }
+
+pub fn aggregate_zero_byte_files(files: Vec<FileMetadata>) -> Vec<FileMetadata> {
+    let (zero_byte_files, mut files): (Vec<FileMetadata>, Vec<FileMetadata>) = files
+        .into_iter()
+        .partition(|file| file.file_type == FileType::File && file.size == 0);
+    if zero_byte_files.is_empty() {
+        return files;
+    }
+    let count = zero_byte_files.len() as u64;
+    let removed_share: f64 = zero_byte_files.iter().map(|file| file.percentage).sum();
+    let share = if files.is_empty() {
+        1.0
+    } else {
+        removed_share.max(ZERO_BYTE_FILES_SHARE)
+    };
+    if removed_share < 1.0 {
+        for file in files.iter_mut() {
+            file.percentage *= (1.0 - share) / (1.0 - removed_share);
+        }
+    }
+    let zero_byte_tile = FileMetadata {
+        name: OsString::from(format!("{} zero-byte files (0 B)", count)),
+        size: 0,
+        descendants: None,
+        percentage: share,
+        file_type: FileType::File,
+        zero_byte_files: Some(count),
+        modified: None,
+        num_hardlinks: None,
+        unexpanded: false,
+        mount_point: false,
+    };
+    // the layout expects files to be sorted from largest to smallest
+    let position = files
+        .iter()
+        .position(|file| file.percentage < share)
+        .unwrap_or(files.len());
+    files.insert(position, zero_byte_tile);
+    files
+}
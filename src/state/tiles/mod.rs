@@ -1,11 +1,13 @@
 pub use board::*;
 pub use files_in_folder::*;
 pub use rect_float::*;
+pub use sort_order::*;
 pub use tile::*;
 pub use treemap::*;
 
 pub mod board;
 pub mod files_in_folder;
 pub mod rect_float;
+pub mod sort_order;
 pub mod tile;
 pub mod treemap;
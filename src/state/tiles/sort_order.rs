@@ -0,0 +1,126 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::state::tiles::FileMetadata;
+
+// the order in which tiles are laid out, starting from the top left. Which files
+// get a tile of their own is always decided by size
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum SortOrder {
+    #[default]
+    BySize,
+    ByName,
+    ByCount, // of the items inside, for folders
+}
+
+impl SortOrder {
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::BySize => SortOrder::ByName,
+            SortOrder::ByName => SortOrder::ByCount,
+            SortOrder::ByCount => SortOrder::BySize,
+        }
+    }
+    pub fn sort(self, files: &mut [FileMetadata]) {
+        // files come sorted by size, which is what ties are left in
+        match self {
+            SortOrder::BySize => {}
+            SortOrder::ByName => files.sort_by(compare_names),
+            SortOrder::ByCount => files.sort_by(|a, b| {
+                let count = |file: &FileMetadata| file.descendants.unwrap_or(0);
+                count(b).cmp(&count(a))
+            }),
+        }
+    }
+}
+
+fn compare_names(a: &FileMetadata, b: &FileMetadata) -> Ordering {
+    let a = a.name.to_string_lossy();
+    let b = b.name.to_string_lossy();
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(&b))
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortOrder::BySize => write!(f, "size"),
+            SortOrder::ByName => write!(f, "name"),
+            SortOrder::ByCount => write!(f, "count"),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "size" => Ok(SortOrder::BySize),
+            "name" => Ok(SortOrder::ByName),
+            "count" => Ok(SortOrder::ByCount),
+            _ => Err(format!(
+                "unknown sort order '{}', expected one of: size, name, count",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::*;
+    use crate::state::tiles::FileType;
+
+    fn file(name: &str, size: u128, descendants: Option<u64>) -> FileMetadata {
+        FileMetadata {
+            name: OsString::from(name),
+            size,
+            descendants,
+            percentage: 0.0,
+            file_type: if descendants.is_some() {
+                FileType::Folder
+            } else {
+                FileType::File
+            },
+            zero_byte_files: None,
+            modified: None,
+            num_hardlinks: None,
+            unexpanded: false,
+            mount_point: false,
+        }
+    }
+
+    fn sorted_names(sort_order: SortOrder) -> Vec<String> {
+        let mut files = vec![
+            file("b_folder", 4096, Some(3)),
+            file("C_file", 2048, None),
+            file("a_folder", 1024, Some(10)),
+            file("B_file", 1024, None),
+        ];
+        sort_order.sort(&mut files);
+        files
+            .iter()
+            .map(|file| file.name.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn sort_orders() {
+        assert_eq!(
+            sorted_names(SortOrder::BySize),
+            ["b_folder", "C_file", "a_folder", "B_file"]
+        );
+        // regardless of case
+        assert_eq!(
+            sorted_names(SortOrder::ByName),
+            ["a_folder", "B_file", "b_folder", "C_file"]
+        );
+        // files have nothing inside, so they keep their order by size
+        assert_eq!(
+            sorted_names(SortOrder::ByCount),
+            ["a_folder", "b_folder", "C_file", "B_file"]
+        );
+    }
+}
@@ -1,8 +1,8 @@
-use std::ffi::OsString;
+use std::{ffi::OsString, time::SystemTime};
 
 use crate::state::tiles::{FileMetadata, FileType, RectFloat};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Tile {
     pub x: u16,
     pub y: u16,
@@ -13,6 +13,12 @@ pub struct Tile {
     pub descendants: Option<u64>,
     pub percentage: f64,
     pub file_type: FileType,
+    pub access_count: u32, // times this was selected or entered in this session
+    pub zero_byte_files: Option<u64>,
+    pub modified: Option<SystemTime>,
+    pub num_hardlinks: Option<u32>,
+    pub unexpanded: bool,
+    pub mount_point: bool,
 }
 
 impl Tile {
@@ -28,6 +34,12 @@ impl Tile {
             descendants: file_metadata.descendants,
             percentage: file_metadata.percentage,
             file_type: file_metadata.file_type,
+            access_count: 0,
+            zero_byte_files: file_metadata.zero_byte_files,
+            modified: file_metadata.modified,
+            num_hardlinks: file_metadata.num_hardlinks,
+            unexpanded: file_metadata.unexpanded,
+            mount_point: file_metadata.mount_point,
         }
     }
     pub fn is_directly_right_of(&self, other: &Tile) -> bool {
@@ -65,7 +77,142 @@ impl Tile {
         std::cmp::min(self.x + self.width, other.x + other.width) - std::cmp::max(self.x, other.x)
     }
 
+    pub fn is_hardlinked(&self) -> bool {
+        self.num_hardlinks
+            .is_some_and(|num_hardlinks| num_hardlinks > 1)
+    }
+
     pub fn get_horizontal_overlap_with(&self, other: &Tile) -> u16 {
         std::cmp::min(self.y + self.height, other.y + other.height) - std::cmp::max(self.y, other.y)
     }
 }
+
+#[cfg(test)]
+pub struct TileBuilder {
+    tile: Tile,
+}
+
+#[cfg(test)]
+impl TileBuilder {
+    pub fn x(mut self, x: u16) -> Self {
+        self.tile.x = x;
+        self
+    }
+    pub fn y(mut self, y: u16) -> Self {
+        self.tile.y = y;
+        self
+    }
+    pub fn width(mut self, width: u16) -> Self {
+        self.tile.width = width;
+        self
+    }
+    pub fn height(mut self, height: u16) -> Self {
+        self.tile.height = height;
+        self
+    }
+    pub fn name(mut self, name: impl Into<OsString>) -> Self {
+        self.tile.name = name.into();
+        self
+    }
+    pub fn size(mut self, size: u128) -> Self {
+        self.tile.size = size;
+        self
+    }
+    pub fn descendants(mut self, descendants: u64) -> Self {
+        self.tile.descendants = Some(descendants);
+        self
+    }
+    pub fn percentage(mut self, percentage: f64) -> Self {
+        self.tile.percentage = percentage;
+        self
+    }
+    pub fn file_type(mut self, file_type: FileType) -> Self {
+        self.tile.file_type = file_type;
+        if file_type == FileType::Folder && self.tile.descendants.is_none() {
+            // folder tiles are always expected to have descendants
+            self.tile.descendants = Some(0);
+        }
+        self
+    }
+    pub fn modified(mut self, modified: SystemTime) -> Self {
+        self.tile.modified = Some(modified);
+        self
+    }
+    pub fn num_hardlinks(mut self, num_hardlinks: u32) -> Self {
+        self.tile.num_hardlinks = Some(num_hardlinks);
+        self
+    }
+    pub fn unexpanded(mut self) -> Self {
+        self.tile.unexpanded = true;
+        self
+    }
+    pub fn mount_point(mut self) -> Self {
+        self.tile.mount_point = true;
+        self
+    }
+    pub fn build(self) -> Tile {
+        self.tile
+    }
+}
+
+#[cfg(test)]
+impl Tile {
+    pub fn builder() -> TileBuilder {
+        TileBuilder {
+            tile: Tile {
+                x: 0,
+                y: 0,
+                width: 8,
+                height: 3,
+                name: OsString::new(),
+                size: 0,
+                descendants: None,
+                percentage: 0.0,
+                file_type: FileType::File,
+                access_count: 0,
+                zero_byte_files: None,
+                modified: None,
+                num_hardlinks: None,
+                unexpanded: false,
+                mount_point: false,
+            },
+        }
+    }
+    pub fn for_testing(name: &str, size: u128) -> Tile {
+        Tile::builder().name(name).size(size).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_defaults() {
+        let tile = Tile::for_testing("file", 4096);
+        assert_eq!(tile.name, OsString::from("file"));
+        assert_eq!(tile.size, 4096);
+        assert_eq!(tile.file_type, FileType::File);
+        assert_eq!(tile.descendants, None);
+
+        let folder = Tile::builder()
+            .name("folder")
+            .file_type(FileType::Folder)
+            .build();
+        assert_eq!(folder.descendants, Some(0));
+    }
+
+    #[test]
+    fn adjacent_tiles() {
+        let left = Tile::builder().x(0).y(0).width(10).height(5).build();
+        let right = Tile::builder().x(10).y(2).width(10).height(5).build();
+        let below = Tile::builder().x(5).y(5).width(10).height(5).build();
+        assert!(right.is_directly_right_of(&left));
+        assert!(left.is_directly_left_of(&right));
+        assert!(below.is_directly_below(&left));
+        assert!(left.is_directly_above(&below));
+        assert!(left.horizontally_overlaps_with(&right));
+        assert_eq!(left.get_horizontal_overlap_with(&right), 3);
+        assert_eq!(left.get_vertical_overlap_with(&below), 5);
+    }
+}
@@ -2,9 +2,9 @@ use tui::layout::Rect;
 
 use crate::state::tiles::{FileMetadata, RectFloat, Tile};
 
-const HEIGHT_WIDTH_RATIO: f64 = 2.5;
-const MINIMUM_HEIGHT: u16 = 3;
-const MINIMUM_WIDTH: u16 = 8;
+pub const HEIGHT_WIDTH_RATIO: f64 = 2.5;
+pub const MINIMUM_HEIGHT: u16 = 3;
+pub const MINIMUM_WIDTH: u16 = 8;
 
 pub struct TreeMap {
     pub tiles: Vec<Tile>,
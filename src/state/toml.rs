@@ -0,0 +1,88 @@
+// only as much toml as the files we keep need: [sections], key = value lines with
+// "strings", 'strings', booleans and numbers, anything we don't understand is skipped
+
+pub fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub fn parse_string(value: &str) -> Option<(String, &str)> {
+    // basic "strings" with \" and \\ escapes, or literal 'strings', and whatever comes after
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let mut parsed = String::new();
+    let mut chars = value[1..].char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            c if c == quote => return Some((parsed, &value[index + 2..])),
+            '\\' if quote == '"' => parsed.push(chars.next()?.1),
+            c => parsed.push(c),
+        }
+    }
+    None
+}
+
+pub fn parse_bool(value: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = value.strip_prefix("true") {
+        Some((true, rest))
+    } else {
+        value.strip_prefix("false").map(|rest| (false, rest))
+    }
+}
+
+pub fn key_values(contents: &str) -> impl Iterator<Item = (&str, String, &str)> {
+    // the section each line is in ("" before the first one), its unquoted key and its value
+    let mut section = "";
+    contents.lines().filter_map(move |line| {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim();
+            return None;
+        }
+        let (key, value) = match parse_string(line) {
+            Some((key, rest)) => (key, rest.trim_start().strip_prefix('=')?),
+            None => {
+                let (key, value) = line.split_once('=')?;
+                (key.trim().to_string(), value)
+            }
+        };
+        Some((section, key, value.trim()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_strings_round_trip() {
+        let text = "my \"quoted\" \\ folder";
+        assert_eq!(parse_string(&quote(text)), Some((String::from(text), "")));
+        assert_eq!(
+            parse_string("'C:\\literal' # comment"),
+            Some((String::from("C:\\literal"), " # comment"))
+        );
+        assert_eq!(parse_string("\"unterminated"), None);
+    }
+
+    #[test]
+    fn key_values_by_section() {
+        let key_values: Vec<_> = key_values(
+            "# a comment\n\
+             top = 1\n\
+             \n\
+             [keybindings]\n\
+             'a=b' = \"c\"\n\
+             not a key value\n",
+        )
+        .collect();
+        assert_eq!(
+            key_values,
+            vec![
+                ("", String::from("top"), "1"),
+                ("keybindings", String::from("a=b"), "\"c\""),
+            ]
+        );
+    }
+}
@@ -1,4 +1,18 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+pub const PATH_HIGHLIGHT_DURATION: Duration = Duration::from_millis(300);
+pub const COPIED_TO_CLIPBOARD_DURATION: Duration = Duration::from_secs(2);
+pub const NOTICE_DURATION: Duration = Duration::from_secs(2);
+pub const INFO_FLASH_DURATION: Duration = Duration::from_secs(2);
+// files per second are counted over at least this long, so that the rate doesn't jump around
+pub const SCAN_RATE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+#[cfg(not(test))]
+pub const AUTO_CONFIRM_TICK: Duration = Duration::from_secs(1);
+#[cfg(test)]
+pub const AUTO_CONFIRM_TICK: Duration = Duration::from_millis(100);
 
 pub struct UiEffects {
     pub flash_space_freed: bool,
@@ -6,6 +20,21 @@ pub struct UiEffects {
     pub deletion_in_progress: bool,
     pub loading_progress_indicator: u64,
     pub last_read_path: Option<PathBuf>,
+    pub current_scan_dir: Option<PathBuf>, // shown instead of last_read_path once we have it
+    pub path_highlight_active: bool,
+    pub path_highlight_end: Option<Instant>,
+    pub path_highlight_duration: Duration, // no highlight at all if this is zero
+    pub copied_to_clipboard: Option<String>,
+    pub copied_to_clipboard_end: Option<Instant>,
+    pub notice: Option<String>,
+    pub notice_end: Option<Instant>,
+    pub info_flash_message: Option<(String, Instant)>, // with when it goes away
+    pub session_restored: bool,
+    pub auto_confirm_countdown: Option<u64>,
+    pub scan_timed_out: bool,
+    pub scan_rate: f64, // files scanned per second, as of the last sample
+    pub scan_eta: Option<Duration>, // how much longer the scan should take, roughly
+    scan_rate_sample: Option<(Instant, u64)>, // when it was taken and how many files there were
 }
 
 impl UiEffects {
@@ -16,6 +45,26 @@ impl UiEffects {
             deletion_in_progress: false,
             loading_progress_indicator: 0,
             last_read_path: None,
+            current_scan_dir: None,
+            path_highlight_active: false,
+            path_highlight_end: None,
+            // the ui tests count renders, and the highlight going away would be one more
+            path_highlight_duration: if cfg!(test) {
+                Duration::ZERO
+            } else {
+                PATH_HIGHLIGHT_DURATION
+            },
+            copied_to_clipboard: None,
+            copied_to_clipboard_end: None,
+            notice: None,
+            notice_end: None,
+            info_flash_message: None,
+            session_restored: false,
+            scan_timed_out: false,
+            auto_confirm_countdown: None,
+            scan_rate: 0.0,
+            scan_eta: None,
+            scan_rate_sample: None,
         }
     }
     pub fn increment_loading_progress_indicator(&mut self) {
@@ -23,4 +72,152 @@ impl UiEffects {
         // the scanning text animation speed
         self.loading_progress_indicator += 3;
     }
+    pub fn sample_scan_rate(&mut self, files_scanned: u64) {
+        // called more often than we sample, eg. on every tick while loading
+        let now = Instant::now();
+        match self.scan_rate_sample {
+            Some((sampled_at, files_then)) => {
+                let elapsed = now.duration_since(sampled_at);
+                if elapsed >= SCAN_RATE_SAMPLE_INTERVAL {
+                    let files_since = files_scanned.saturating_sub(files_then);
+                    self.scan_rate = files_since as f64 / elapsed.as_secs_f64();
+                    self.scan_rate_sample = Some((now, files_scanned));
+                }
+            }
+            None => self.scan_rate_sample = Some((now, files_scanned)),
+        }
+    }
+    pub fn highlight_path(&mut self) {
+        if !self.path_highlight_duration.is_zero() {
+            self.path_highlight_active = true;
+            self.path_highlight_end = Some(Instant::now() + self.path_highlight_duration);
+        }
+    }
+    fn clear_expired_path_highlight(&mut self) -> bool {
+        if let Some(path_highlight_end) = self.path_highlight_end
+            && Instant::now() > path_highlight_end
+        {
+            self.path_highlight_active = false;
+            self.path_highlight_end = None;
+            return true;
+        }
+        false
+    }
+    pub fn show_copied_to_clipboard(&mut self, copied_text: String) {
+        self.copied_to_clipboard = Some(copied_text);
+        self.copied_to_clipboard_end = Some(Instant::now() + COPIED_TO_CLIPBOARD_DURATION);
+    }
+    fn clear_expired_copied_to_clipboard(&mut self) -> bool {
+        if let Some(copied_to_clipboard_end) = self.copied_to_clipboard_end
+            && Instant::now() > copied_to_clipboard_end
+        {
+            self.copied_to_clipboard = None;
+            self.copied_to_clipboard_end = None;
+            return true;
+        }
+        false
+    }
+    pub fn show_notice(&mut self, notice: &str) {
+        // a short message in place of the selected tile's details
+        self.notice = Some(String::from(notice));
+        self.notice_end = Some(Instant::now() + NOTICE_DURATION);
+    }
+    pub fn clear_notice(&mut self) {
+        self.notice = None;
+        self.notice_end = None;
+    }
+    fn clear_expired_notice(&mut self) -> bool {
+        if let Some(notice_end) = self.notice_end
+            && Instant::now() > notice_end
+        {
+            self.notice = None;
+            self.notice_end = None;
+            return true;
+        }
+        false
+    }
+    pub fn show_info_flash(&mut self, message: String) {
+        // a small box in the middle of the screen, rather than a line at the bottom
+        self.info_flash_message = Some((message, Instant::now() + INFO_FLASH_DURATION));
+    }
+    fn clear_expired_info_flash(&mut self) -> bool {
+        if let Some((_, info_flash_end)) = self.info_flash_message
+            && Instant::now() > info_flash_end
+        {
+            self.info_flash_message = None;
+            return true;
+        }
+        false
+    }
+    pub fn clear_expired(&mut self) -> bool {
+        // returns whether anything went away, so that we know to render again
+        let path_highlight_cleared = self.clear_expired_path_highlight();
+        let copied_to_clipboard_cleared = self.clear_expired_copied_to_clipboard();
+        let notice_cleared = self.clear_expired_notice();
+        let info_flash_cleared = self.clear_expired_info_flash();
+        path_highlight_cleared
+            || copied_to_clipboard_cleared
+            || notice_cleared
+            || info_flash_cleared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_expired_effects() {
+        let mut ui_effects = UiEffects::new();
+        assert!(!ui_effects.clear_expired());
+        ui_effects.show_notice("still showing");
+        assert!(!ui_effects.clear_expired());
+        assert!(ui_effects.notice.is_some());
+        ui_effects.notice_end = Some(Instant::now() - Duration::from_millis(1));
+        assert!(ui_effects.clear_expired());
+        assert!(ui_effects.notice.is_none());
+        assert!(!ui_effects.clear_expired());
+    }
+
+    #[test]
+    fn clear_expired_path_highlight() {
+        let mut ui_effects = UiEffects::new();
+        ui_effects.highlight_path();
+        assert!(!ui_effects.path_highlight_active);
+        ui_effects.path_highlight_duration = PATH_HIGHLIGHT_DURATION;
+        ui_effects.highlight_path();
+        assert!(ui_effects.path_highlight_active);
+        assert!(!ui_effects.clear_expired());
+        ui_effects.path_highlight_end = Some(Instant::now() - Duration::from_millis(1));
+        assert!(ui_effects.clear_expired());
+        assert!(!ui_effects.path_highlight_active);
+    }
+
+    #[test]
+    fn clear_expired_info_flash() {
+        let mut ui_effects = UiEffects::new();
+        ui_effects.show_info_flash(String::from(
+            "Current folder: 1 items (1 files, 0 subdirectories)",
+        ));
+        assert!(!ui_effects.clear_expired());
+        assert!(ui_effects.info_flash_message.is_some());
+        ui_effects.info_flash_message = Some((
+            String::from("Current folder: 1 items (1 files, 0 subdirectories)"),
+            Instant::now() - Duration::from_millis(1),
+        ));
+        assert!(ui_effects.clear_expired());
+        assert!(ui_effects.info_flash_message.is_none());
+    }
+
+    #[test]
+    fn scan_rate_is_sampled_at_an_interval() {
+        let mut ui_effects = UiEffects::new();
+        ui_effects.sample_scan_rate(0);
+        // too soon after the first sample to tell
+        ui_effects.sample_scan_rate(100);
+        assert_eq!(ui_effects.scan_rate, 0.0);
+        ui_effects.scan_rate_sample = Some((Instant::now() - Duration::from_secs(2), 100));
+        ui_effects.sample_scan_rate(2100);
+        assert!((ui_effects.scan_rate - 1000.0).abs() < 10.0);
+    }
 }
@@ -0,0 +1,66 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::state::{config_dir, toml};
+
+// warnings we only show the first time something is used, rather than every time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WarningsShown {
+    pub disable_delete_confirmation_warned: bool,
+}
+
+fn warnings_shown_file_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("warnings_shown"))
+}
+
+impl WarningsShown {
+    pub fn load() -> Self {
+        // if we can't tell, we'd rather warn again than not at all
+        fs::read_to_string(warnings_shown_file_path().unwrap_or_default())
+            .map(|contents| WarningsShown::from_toml(&contents))
+            .unwrap_or_default()
+    }
+    pub fn save(&self) -> io::Result<()> {
+        let warnings_shown_file_path = warnings_shown_file_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(config_dir) = warnings_shown_file_path.parent() {
+            fs::create_dir_all(config_dir)?;
+        }
+        fs::write(warnings_shown_file_path, self.to_toml())
+    }
+    fn to_toml(&self) -> String {
+        format!(
+            "disable_delete_confirmation_warned = {}\n",
+            self.disable_delete_confirmation_warned
+        )
+    }
+    fn from_toml(contents: &str) -> Self {
+        let mut warnings_shown = WarningsShown::default();
+        for (_, key, value) in toml::key_values(contents) {
+            if key == "disable_delete_confirmation_warned" {
+                warnings_shown.disable_delete_confirmation_warned =
+                    toml::parse_bool(value).is_some_and(|(warned, _)| warned);
+            }
+        }
+        warnings_shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warnings_shown_round_trip() {
+        let warnings_shown = WarningsShown {
+            disable_delete_confirmation_warned: true,
+        };
+        assert_eq!(
+            WarningsShown::from_toml(&warnings_shown.to_toml()),
+            warnings_shown
+        );
+        assert_eq!(
+            WarningsShown::from_toml("disable_delete_confirmation_warned = maybe\nother = 1\n"),
+            WarningsShown::default()
+        );
+    }
+}
@@ -1,11 +1,8 @@
-use std::{
-    iter,
-    sync::{Arc, Mutex},
-};
+use std::sync::{Arc, Barrier, Mutex, atomic::AtomicUsize};
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 
-use crate::tests::fakes::{TerminalEvent, TerminalEvents, TestBackend};
+use crate::tests::fakes::{TerminalEvent, TerminalEvents, TestBackend, TimedTerminalEvents};
 
 macro_rules! key {
     (char $x:expr) => {
@@ -28,15 +25,37 @@ macro_rules! key {
     };
 }
 
-pub fn sleep_and_quit_events(sleep_num: usize, quit_after_confirm: bool) -> Box<TerminalEvents> {
-    let collect = iter::repeat(None).take(sleep_num).collect();
-    let mut events: Vec<Option<Event>> = collect;
-    events.push(Some(key!(ctrl 'c')));
+pub fn wait_for_scan_and_quit_events(
+    quit_after_confirm: bool,
+) -> (Box<TerminalEvents>, Arc<Barrier>) {
+    // the barrier should be given to the app, so that these events
+    // will only be sent once it's done scanning
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let mut events: Vec<Option<Event>> = vec![Some(key!(ctrl 'c'))];
     if quit_after_confirm {
         events.push(None);
         events.push(Some(key!(char 'y')));
     }
-    Box::new(TerminalEvents::new(events))
+    let terminal_events =
+        TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone());
+    (Box::new(terminal_events), scan_complete_barrier)
+}
+
+pub fn timed_wait_for_scan_and_quit_events(
+    quit_after_confirm: bool,
+) -> (Box<TimedTerminalEvents>, Arc<Barrier>, Arc<AtomicUsize>) {
+    // same as above, with a step rather than a wait between quitting and confirming.
+    // the step count tells the test whether all events were sent
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let mut events: Vec<Option<Event>> = vec![Some(key!(ctrl 'c'))];
+    if quit_after_confirm {
+        events.push(None);
+        events.push(Some(key!(char 'y')));
+    }
+    let terminal_events =
+        TimedTerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone());
+    let step_count = terminal_events.step_count();
+    (Box::new(terminal_events), scan_complete_barrier, step_count)
 }
 
 type BackendWithStreams = (
@@ -4,18 +4,25 @@ use std::{
     io::prelude::*,
     iter,
     path::{Path, PathBuf},
+    sync::{Arc, Barrier, atomic::Ordering},
+    time::Duration,
 };
 
 use crossterm::event::KeyModifiers;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent};
 use insta::assert_snapshot;
 
 use crate::{
+    StartOptions,
+    app::AppOptions,
+    scanner::ScanFilter,
     start,
+    state::{ExternalCommand, SessionState, layout::LayoutAlgorithm, tiles::SortOrder},
     tests::{
         cases::test_utils::*,
-        fakes::{TerminalEvent::*, TerminalEvents},
+        fakes::{MockFilesystem, TerminalEvent::*, TerminalEvents},
     },
+    ui::SpinnerStyle,
 };
 
 macro_rules! key {
@@ -44,9 +51,24 @@ macro_rules! key {
 // on filesystems with and without compression
 const SHOW_APPARENT_SIZE: bool = true;
 
-// This leaves delete confirmations enabled (The default behaviour).
-const DELETE_CONFIRMATION_ENABLED: bool = false;
-const DELETE_CONFIRMATION_DISABLED: bool = true;
+// everything else is left at its default: delete confirmations are enabled, hidden files
+// are included, and the clock, clipboard, session file and such are all off (the time of
+// day would make every snapshot different, and the tests should not touch the user's session)
+fn app_options() -> AppOptions {
+    AppOptions {
+        show_apparent_size: SHOW_APPARENT_SIZE,
+        ..AppOptions::default()
+    }
+}
+
+fn start_options() -> StartOptions {
+    StartOptions {
+        app: app_options(),
+        // the snapshots were taken with the title highlight running along
+        spinner_style: SpinnerStyle::Highlight,
+        ..StartOptions::default()
+    }
+}
 
 fn create_root_temp_dir(name: &str) -> anyhow::Result<PathBuf> {
     let mut dir = PathBuf::new();
@@ -71,7 +93,8 @@ fn create_temp_file<P: AsRef<Path>>(path: P, size: usize) -> anyhow::Result<()>
 #[test]
 fn two_large_files_one_small_file() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier, step_count) =
+        timed_wait_for_scan_and_quit_events(true);
     let temp_dir_path =
         create_root_temp_dir("two_large_files_one_small_file").expect("failed to create temp dir");
 
@@ -91,8 +114,10 @@ fn two_large_files_one_small_file() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -104,6 +129,8 @@ fn two_large_files_one_small_file() {
         &expected_terminal_events[..]
     );
 
+    // the step between quitting and confirming was taken, so 'y' was sent after it
+    assert_eq!(step_count.load(Ordering::Acquire), 1);
     assert_eq!(terminal_draw_events_mirror.len(), 2);
     assert_snapshot!(&terminal_draw_events_mirror[0]);
     assert_snapshot!(&terminal_draw_events_mirror[1]);
@@ -112,7 +139,7 @@ fn two_large_files_one_small_file() {
 #[test]
 fn medium_width() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(60, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path = create_root_temp_dir("medium_width").expect("failed to create temp dir");
 
     let mut file_1_path = PathBuf::from(&temp_dir_path);
@@ -131,8 +158,10 @@ fn medium_width() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -152,7 +181,7 @@ fn medium_width() {
 #[test]
 fn small_width() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(50, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path = create_root_temp_dir("small_width").expect("failed to create temp dir");
 
     let mut file_1_path = PathBuf::from(&temp_dir_path);
@@ -171,8 +200,10 @@ fn small_width() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -189,10 +220,247 @@ fn small_width() {
     assert_snapshot!(&terminal_draw_events_mirror[1]);
 }
 
+#[test]
+fn small_width_selected_folder() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(55, 30);
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'l')),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+    let temp_dir_path =
+        create_root_temp_dir("small_width_selected_folder").expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert_eq!(terminal_draw_events_mirror.len(), 3);
+    // the bottom line stays two lines tall: what's selected and then the controls
+    let bottom_lines = |draw: &str| {
+        let lines: Vec<String> = draw.lines().map(String::from).collect();
+        (
+            lines[lines.len() - 2].clone(),
+            lines[lines.len() - 1].clone(),
+        )
+    };
+    let (_, controls_line) = bottom_lines(&terminal_draw_events_mirror[0]);
+    assert!(controls_line.contains("navigate"));
+    let (status_line, _) = bottom_lines(&terminal_draw_events_mirror[1]);
+    assert!(status_line.contains("subfolder1"));
+}
+
+#[test]
+fn toggle_hidden_files() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'H')),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+    let temp_dir_path =
+        create_root_temp_dir("toggle_hidden_files").expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 4096).expect("failed to create temp file");
+
+    let mut hidden_file_path = PathBuf::from(&temp_dir_path);
+    hidden_file_path.push(".hidden_file");
+    create_temp_file(hidden_file_path, 8192).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_filter: Some(ScanFilter::new(&temp_dir_path).skip_hidden(true)),
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert_eq!(terminal_draw_events_mirror.len(), 3);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+
+#[test]
+fn refresh_current_folder() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    // the third one deletes a file once the scan is done, as if from another terminal
+    let scan_complete_barrier = Arc::new(Barrier::new(3));
+    let events = vec![
+        None,
+        Some(key!(char 'r')),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+    let temp_dir_path =
+        create_root_temp_dir("refresh_current_folder").expect("failed to create temp dir");
+
+    let file_1_path = temp_dir_path.join("file1");
+    create_temp_file(&file_1_path, 4096).expect("failed to create temp file");
+    let file_2_path = temp_dir_path.join("file2");
+    create_temp_file(&file_2_path, 8192).expect("failed to create temp file");
+
+    let deleting_barrier = scan_complete_barrier.clone();
+    let deleting_thread = std::thread::spawn(move || {
+        deleting_barrier.wait();
+        std::fs::remove_file(file_2_path).expect("failed to remove temp file");
+    });
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    deleting_thread.join().expect("failed to delete file");
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    let draw_with = |text: &str| {
+        terminal_draw_events_mirror
+            .iter()
+            .find(|draw| draw.contains(text))
+            .expect("the folder was refreshed")
+    };
+    // before scanning again, what we had is shown
+    assert!(draw_with("Refreshing this folder").contains("file2"));
+    let refreshed = draw_with("Refreshed this folder");
+    assert!(refreshed.contains("file1"));
+    assert!(!refreshed.contains("file2"));
+}
+
+#[test]
+#[cfg(unix)]
+fn refresh_folder_with_unreadable_entry() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        None,
+        Some(key!(char 'r')),
+        None,
+        Some(key!(char 'r')),
+        None,
+        Some(key!(char 'E')),
+        None,
+        Some(key!(Esc)),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+    let temp_dir_path = create_root_temp_dir("refresh_folder_with_unreadable_entry")
+        .expect("failed to create temp dir");
+
+    create_temp_file(temp_dir_path.join("file1"), 4096).expect("failed to create temp file");
+    // following it leads nowhere, so reading it fails every time
+    std::os::unix::fs::symlink(
+        temp_dir_path.join("nowhere"),
+        temp_dir_path.join("dangling"),
+    )
+    .expect("failed to create symlink");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_filter: Some(ScanFilter::new(&temp_dir_path).follow_links(true)),
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    // the entry failed once when scanning and once for each refresh, and is only counted once
+    assert!(
+        terminal_draw_events_mirror
+            .iter()
+            .any(|draw| draw.contains("Failed to read 1 files") && draw.contains("dangling"))
+    );
+    assert!(
+        !terminal_draw_events_mirror
+            .iter()
+            .any(|draw| draw.to_lowercase().contains("failed to read 2 files"))
+    );
+}
+
+#[test]
+fn title_suffix() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
+    let temp_dir_path = create_root_temp_dir("title_suffix").expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            title_suffix: Some(String::from("user@remote-host")),
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+}
+
 #[test]
 fn small_width_long_folder_name() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(50, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path =
         create_root_temp_dir("small_width_long_folder_name").expect("failed to create temp dir");
 
@@ -212,8 +480,10 @@ fn small_width_long_folder_name() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -233,7 +503,7 @@ fn small_width_long_folder_name() {
 #[test]
 fn too_small_width_one() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(49, 50);
-    let keyboard_events = sleep_and_quit_events(1, false);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(false);
     let temp_dir_path =
         create_root_temp_dir("too_small_width_one").expect("failed to create temp dir");
 
@@ -253,8 +523,10 @@ fn too_small_width_one() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -273,7 +545,7 @@ fn too_small_width_one() {
 #[test]
 fn too_small_width_two() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(26, 50);
-    let keyboard_events = sleep_and_quit_events(1, false);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(false);
     let temp_dir_path =
         create_root_temp_dir("too_small_width_two").expect("failed to create temp dir");
 
@@ -293,8 +565,10 @@ fn too_small_width_two() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -313,7 +587,7 @@ fn too_small_width_two() {
 #[test]
 fn too_small_width_three() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(20, 50);
-    let keyboard_events = sleep_and_quit_events(1, false);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(false);
     let temp_dir_path =
         create_root_temp_dir("too_small_width_three").expect("failed to create temp dir");
 
@@ -321,8 +595,10 @@ fn too_small_width_three() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -341,7 +617,7 @@ fn too_small_width_three() {
 #[test]
 fn too_small_width_four() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(15, 50);
-    let keyboard_events = sleep_and_quit_events(1, false);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(false);
     let temp_dir_path =
         create_root_temp_dir("too_small_width_four").expect("failed to create temp dir");
 
@@ -349,8 +625,10 @@ fn too_small_width_four() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -369,7 +647,7 @@ fn too_small_width_four() {
 #[test]
 fn too_small_width_five() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(5, 50);
-    let keyboard_events = sleep_and_quit_events(1, false);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(false);
     let temp_dir_path =
         create_root_temp_dir("too_small_width_five").expect("failed to create temp dir");
 
@@ -377,8 +655,10 @@ fn too_small_width_five() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -397,7 +677,7 @@ fn too_small_width_five() {
 #[test]
 fn too_small_height() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 14);
-    let keyboard_events = sleep_and_quit_events(1, false);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(false);
     let temp_dir_path =
         create_root_temp_dir("too_small_height").expect("failed to create temp dir");
 
@@ -405,8 +685,10 @@ fn too_small_height() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -425,7 +707,7 @@ fn too_small_height() {
 #[test]
 fn eleven_files() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path = create_root_temp_dir("eleven_files").expect("failed to create temp dir");
 
     let mut file_1_path = PathBuf::from(&temp_dir_path);
@@ -476,8 +758,10 @@ fn eleven_files() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -537,8 +821,7 @@ fn enter_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -606,8 +889,7 @@ fn enter_folder_medium_width() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -676,8 +958,7 @@ fn enter_folder_small_width() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -705,7 +986,7 @@ fn enter_folder_small_width() {
 #[test]
 fn small_files() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path = create_root_temp_dir("small_files").expect("failed to create temp dir");
 
     let mut file_1_path = PathBuf::from(&temp_dir_path);
@@ -732,8 +1013,10 @@ fn small_files() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -800,8 +1083,7 @@ fn zoom_into_small_files() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -898,8 +1180,7 @@ fn cannot_move_into_small_files() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -931,7 +1212,7 @@ fn minimum_tile_sides() {
     // to the minimum with some sort of asterisk to explain
 
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path =
         create_root_temp_dir("minimum_tile_sides").expect("failed to create temp dir");
 
@@ -957,8 +1238,10 @@ fn minimum_tile_sides() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -1021,8 +1304,7 @@ fn move_down_and_enter_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1087,8 +1369,7 @@ fn noop_when_entering_file() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1160,8 +1441,7 @@ fn move_up_and_enter_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1233,8 +1513,7 @@ fn move_right_and_enter_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1260,6 +1539,145 @@ fn move_right_and_enter_folder() {
     assert_snapshot!(&terminal_draw_events_mirror[4]);
 }
 
+fn navigation_draw_events(temp_dir_path: &Path, keys: &[Event]) -> (Vec<String>, PathBuf) {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    for key in keys {
+        events.push(Some(key.clone()));
+        events.push(None);
+    }
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let (exit_path, _) = start(
+        backend,
+        keyboard_events,
+        temp_dir_path.to_path_buf(),
+        start_options(),
+    );
+    let terminal_draw_events_mirror = terminal_draw_events
+        .lock()
+        .expect("could not acquire lock on terminal events")
+        .clone();
+    (terminal_draw_events_mirror, exit_path)
+}
+
+#[test]
+fn copy_without_clipboard_says_how_to_enable_it() {
+    let temp_dir_path = create_root_temp_dir("copy_without_clipboard_says_how_to_enable_it")
+        .expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("file1"), 4096).expect("failed to create temp file");
+
+    let (terminal_draw_events, _) =
+        navigation_draw_events(&temp_dir_path, &[key!(char 'l'), key!(char 'y')]);
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(
+        terminal_draw_events
+            .iter()
+            .any(|draw| draw.contains("Start diskonaut with --clipboard to copy paths"))
+    );
+}
+
+#[test]
+fn hjkl_moves_like_arrow_keys() {
+    let temp_dir_path =
+        create_root_temp_dir("hjkl_moves_like_arrow_keys").expect("failed to create temp dir");
+    // only folders, so that whatever ends up selected is the exit path
+    for (name, size) in [
+        ("subfolder1", 16384),
+        ("subfolder2", 8192),
+        ("subfolder3", 4096),
+        ("subfolder4", 4096),
+    ] {
+        let subfolder_path = temp_dir_path.join(name);
+        create_dir(&subfolder_path).expect("failed to create temporary directory");
+        create_temp_file(subfolder_path.join("file"), size).expect("failed to create temp file");
+    }
+
+    let (arrow_key_draws, arrow_key_exit_path) = navigation_draw_events(
+        &temp_dir_path,
+        &[key!(Right), key!(Down), key!(Right), key!(Left), key!(Up)],
+    );
+    let (hjkl_draws, hjkl_exit_path) = navigation_draw_events(
+        &temp_dir_path,
+        &[
+            key!(char 'l'),
+            key!(char 'j'),
+            key!(char 'l'),
+            key!(char 'h'),
+            key!(char 'k'),
+        ],
+    );
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert_ne!(arrow_key_exit_path, temp_dir_path, "a folder was selected");
+    assert_eq!(hjkl_exit_path, arrow_key_exit_path);
+    assert_eq!(hjkl_draws, arrow_key_draws);
+}
+
+#[test]
+fn click_to_select_tile() {
+    let temp_dir_path =
+        create_root_temp_dir("click_to_select_tile").expect("failed to create temp dir");
+    for (name, size) in [("subfolder1", 16384), ("subfolder2", 4096)] {
+        let subfolder_path = temp_dir_path.join(name);
+        create_dir(&subfolder_path).expect("failed to create temporary directory");
+        create_temp_file(subfolder_path.join("file"), size).expect("failed to create temp file");
+    }
+
+    // the smaller folder is on the right side of the screen, the title line is on top of it
+    let (_, title_click_exit_path) = navigation_draw_events(
+        &temp_dir_path,
+        &[Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            185,
+            0,
+            KeyModifiers::NONE,
+        ))],
+    );
+    let (_, exit_path) = navigation_draw_events(
+        &temp_dir_path,
+        &[Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            185,
+            25,
+            KeyModifiers::NONE,
+        ))],
+    );
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert_eq!(title_click_exit_path, temp_dir_path);
+    assert_eq!(exit_path, temp_dir_path.join("subfolder2"));
+}
+
+#[test]
+fn scroll_wheel_zooms_like_plus_and_minus() {
+    let temp_dir_path = create_root_temp_dir("scroll_wheel_zooms_like_plus_and_minus")
+        .expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("large_file"), 1_000_000)
+        .expect("failed to create temp file");
+    for index in 0..50 {
+        create_temp_file(temp_dir_path.join(format!("small_file{}", index)), 100)
+            .expect("failed to create temp file");
+    }
+
+    let scroll_up = Event::Mouse(MouseEvent::ScrollUp(10, 10, KeyModifiers::NONE));
+    let scroll_down = Event::Mouse(MouseEvent::ScrollDown(10, 10, KeyModifiers::NONE));
+    let (key_draws, _) = navigation_draw_events(
+        &temp_dir_path,
+        &[key!(char '+'), key!(char '+'), key!(char '-')],
+    );
+    let (scroll_draws, _) =
+        navigation_draw_events(&temp_dir_path, &[scroll_up.clone(), scroll_up, scroll_down]);
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert_eq!(scroll_draws, key_draws);
+}
+
 #[test]
 fn move_left_and_enter_folder() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
@@ -1306,8 +1724,7 @@ fn move_left_and_enter_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1375,8 +1792,7 @@ fn enter_largest_folder_with_no_selected_tile() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1436,8 +1852,7 @@ fn clear_selection_when_moving_off_screen_edges() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1510,8 +1925,7 @@ fn esc_to_go_up() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1539,6 +1953,58 @@ fn esc_to_go_up() {
     assert_snapshot!(&terminal_draw_events_mirror[5]);
 }
 
+#[test]
+fn tilde_to_go_to_root() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char '\n')), // with nothing selected, enters the largest folder
+        None,
+        Some(key!(char '\n')),
+        None,
+        Some(key!(char '~')),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+
+    let temp_dir_path =
+        create_root_temp_dir("tilde_to_go_to_root").expect("failed to create temp dir");
+
+    let mut subfolder_2_path = PathBuf::from(&temp_dir_path);
+    subfolder_2_path.push("subfolder1");
+    subfolder_2_path.push("subfolder2");
+    create_dir_all(&subfolder_2_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&subfolder_2_path);
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 16384).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert_eq!(terminal_draw_events_mirror.len(), 5);
+    assert_snapshot!(&terminal_draw_events_mirror[2]);
+    assert_snapshot!(&terminal_draw_events_mirror[3]);
+}
+
 #[test]
 fn noop_when_pressing_esc_at_base_folder() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
@@ -1591,8 +2057,7 @@ fn noop_when_pressing_esc_at_base_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events
@@ -1665,8 +2130,7 @@ fn delete_file() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -1756,8 +2220,13 @@ fn delete_file_no_confirmation() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_DISABLED,
+        StartOptions {
+            app: AppOptions {
+                disable_delete_confirmation: true,
+                ..app_options()
+            },
+            ..start_options()
+        },
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -1807,57 +2276,252 @@ fn delete_file_no_confirmation() {
 }
 
 #[test]
-fn cant_delete_file_with_term_too_small() {
-    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(49, 50);
+fn delete_confirmation_disabled_warning() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'l')), // any key dismisses the warning
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
 
-    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
-    events.push(Some(key!(char 'l'))); // once to place selected marker on screen
-    events.push(None);
-    events.push(Some(key!(Backspace)));
-    events.push(None);
-    events.push(Some(key!(char 'y')));
-    events.push(None);
-    events.push(None);
-    events.push(None);
-    events.push(None);
-    events.push(Some(key!(ctrl 'c')));
-    events.push(None);
-    events.push(Some(key!(char 'y')));
-    let keyboard_events = Box::new(TerminalEvents::new(events));
+    let temp_dir_path = create_root_temp_dir("delete_confirmation_disabled_warning")
+        .expect("failed to create temp dir");
 
-    let temp_dir_path = create_root_temp_dir("cant_delete_file_with_term_too_small")
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            app: AppOptions {
+                disable_delete_confirmation: true,
+                warn_delete_confirmation_disabled: true,
+                ..app_options()
+            },
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn external_command_on_selected_file() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'l')), // once to place selected marker on screen
+        None,
+        Some(key!(char 't')),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+
+    let temp_dir_path = create_root_temp_dir("external_command_on_selected_file")
         .expect("failed to create temp dir");
 
-    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
-    subfolder_1_path.push("subfolder1");
-    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            external_commands: vec![ExternalCommand {
+                key: 't',
+                template: String::from("touch {path}.ran"),
+                interactive: false,
+            }],
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    let command_ran = file_1_path.with_file_name("file1.ran").exists();
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert!(command_ran, "the command was run on the selected file");
+    assert_eq!(terminal_draw_events_mirror.len(), 4);
+}
+
+#[test]
+fn failed_to_read_files_are_counted() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'E')), // to list them
+        None,
+        Some(key!(Esc)),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+
+    let temp_dir_path = create_root_temp_dir("failed_to_read_files_are_counted")
+        .expect("failed to create temp dir");
 
     let mut file_1_path = PathBuf::from(&temp_dir_path);
-    file_1_path.push("subfolder1");
     file_1_path.push("file1");
     create_temp_file(&file_1_path, 4096).expect("failed to create temp file");
 
     let mut file_2_path = PathBuf::from(&temp_dir_path);
     file_2_path.push("file2");
-    create_temp_file(&file_2_path, 4096).expect("failed to create temp file");
+    create_temp_file(&file_2_path, 8192).expect("failed to create temp file");
 
-    let mut file_3_path = PathBuf::from(&temp_dir_path);
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_3_path = PathBuf::from(&subfolder_1_path);
     file_3_path.push("file3");
     create_temp_file(&file_3_path, 4096).expect("failed to create temp file");
 
+    let mut file_4_path = PathBuf::from(&subfolder_1_path);
+    file_4_path.push("file4");
+    create_temp_file(&file_4_path, 4096).expect("failed to create temp file");
+
+    let mock_filesystem = MockFilesystem::new()
+        .fail_on(&file_2_path)
+        .fail_on(&file_3_path);
+
     start(
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            metadata_provider: Some(mock_filesystem.metadata_provider()),
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
-    let terminal_draw_events_mirror = terminal_draw_events
-        .lock()
-        .expect("could not acquire lock on terminal events");
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
 
-    let expected_terminal_events = vec![
-        Clear, HideCursor, Draw, HideCursor, Flush, Clear, ShowCursor,
+    // the files we could read are shown as usual, with the ones we couldn't counted in the title
+    // and listed with E
+    assert_eq!(terminal_draw_events_mirror.len(), 4);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+
+#[test]
+fn scan_timed_out_shows_partial_results() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'l')), // any key dismisses the warning
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+
+    let temp_dir_path = create_root_temp_dir("scan_timed_out_shows_partial_results")
+        .expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            // times out right after the scanned folder itself, before any of its files
+            scan_timeout: Some(Duration::ZERO),
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+
+#[test]
+fn cant_delete_file_with_term_too_small() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(49, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(key!(char 'l'))); // once to place selected marker on screen
+    events.push(None);
+    events.push(Some(key!(Backspace)));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let temp_dir_path = create_root_temp_dir("cant_delete_file_with_term_too_small")
+        .expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 4096).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(&file_2_path, 4096).expect("failed to create temp file");
+
+    let mut file_3_path = PathBuf::from(&temp_dir_path);
+    file_3_path.push("file3");
+    create_temp_file(&file_3_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        start_options(),
+    );
+    let terminal_draw_events_mirror = terminal_draw_events
+        .lock()
+        .expect("could not acquire lock on terminal events");
+
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Clear, ShowCursor,
     ];
     assert_eq!(
         &terminal_events
@@ -1891,6 +2555,332 @@ fn cant_delete_file_with_term_too_small() {
     assert_snapshot!(&terminal_draw_events_mirror[0]);
 }
 
+#[test]
+fn auto_confirm_deletion() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(key!(char 'l'))); // once to place selected marker on screen
+    events.push(None);
+    events.push(Some(key!(Backspace)));
+    // the countdown ticks every 100ms when testing, so this leaves it plenty of time
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let temp_dir_path =
+        create_root_temp_dir("auto_confirm_deletion").expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(&file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            app: AppOptions {
+                auto_confirm_secs: 2,
+                ..app_options()
+            },
+            ..start_options()
+        },
+    );
+    let terminal_draw_events_mirror = terminal_draw_events
+        .lock()
+        .expect("could not acquire lock on terminal events");
+
+    let file_1_deleted = std::fs::metadata(&file_1_path).is_err();
+    let file_2_untouched = std::fs::metadata(&file_2_path).is_ok();
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(file_1_deleted, "file deleted without answering the prompt");
+    assert!(file_2_untouched, "different file was untouched");
+    // later ticks only redraw the cells that changed, so we look for the first one
+    assert!(
+        terminal_draw_events_mirror
+            .iter()
+            .any(|draw| draw.contains("Deleting in 2 seconds... (press 'n' to cancel)")),
+        "countdown was shown"
+    );
+}
+
+#[test]
+fn cancel_auto_confirm_deletion() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(key!(char 'l'))); // once to place selected marker on screen
+    events.push(None);
+    events.push(Some(key!(Backspace)));
+    events.push(Some(key!(char 'n')));
+    // wait for longer than the countdown would have taken
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let temp_dir_path =
+        create_root_temp_dir("cancel_auto_confirm_deletion").expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 8192).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            app: AppOptions {
+                auto_confirm_secs: 10,
+                ..app_options()
+            },
+            ..start_options()
+        },
+    );
+    let terminal_draw_events_mirror = terminal_draw_events
+        .lock()
+        .expect("could not acquire lock on terminal events");
+
+    let file_1_untouched = std::fs::metadata(&file_1_path).is_ok();
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(
+        file_1_untouched,
+        "cancelled deletion left the file in place"
+    );
+    assert!(
+        terminal_draw_events_mirror
+            .iter()
+            .any(|draw| draw.contains("Deleting in 10 seconds...")),
+        "countdown was shown"
+    );
+}
+
+#[test]
+fn read_only_disables_deletion() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(key!(char 'l'))); // once to place selected marker on screen
+    events.push(None);
+    events.push(Some(key!(Backspace)));
+    events.push(None);
+    events.push(Some(key!(char 'y'))); // there's nothing to confirm
+    events.push(None);
+    events.push(Some(key!(Esc)));
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let temp_dir_path =
+        create_root_temp_dir("read_only_disables_deletion").expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(&file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            app: AppOptions {
+                read_only: true,
+                ..app_options()
+            },
+            ..start_options()
+        },
+    );
+    let terminal_draw_events_mirror = terminal_draw_events
+        .lock()
+        .expect("could not acquire lock on terminal events");
+
+    let files_untouched =
+        std::fs::metadata(&file_1_path).is_ok() && std::fs::metadata(&file_2_path).is_ok();
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(files_untouched, "nothing was deleted");
+    assert!(
+        terminal_draw_events_mirror
+            .iter()
+            .any(|draw| draw.contains("Deletion is disabled (--read-only)")),
+        "deleting was refused"
+    );
+    assert!(
+        !terminal_draw_events_mirror
+            .iter()
+            .any(|draw| draw.contains("Delete this file?") || draw.contains("<BACKSPACE>")),
+        "there was no prompt or hint to delete"
+    );
+}
+
+#[test]
+fn delete_file_updates_tree_and_display() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'l')), // the largest tile is selected first
+        None,
+        Some(key!(Backspace)),
+        None,
+        Some(key!(char 'y')),
+        // leave time for the space freed flash to come and go
+        None,
+        None,
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+    let temp_dir_path = create_root_temp_dir("delete_file_updates_tree_and_display")
+        .expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file_to_delete");
+    create_temp_file(&file_1_path, 16384).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(&file_2_path, 8192).expect("failed to create temp file");
+
+    let mut file_3_path = PathBuf::from(&temp_dir_path);
+    file_3_path.push("file3");
+    create_temp_file(&file_3_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+    let file_1_deleted = std::fs::metadata(&file_1_path).is_err();
+    let other_files_untouched =
+        std::fs::metadata(&file_2_path).is_ok() && std::fs::metadata(&file_3_path).is_ok();
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(file_1_deleted, "selected file was deleted");
+    assert!(other_files_untouched, "other files were untouched");
+    let (prompt_index, prompt) = terminal_draw_events_mirror
+        .iter()
+        .enumerate()
+        .find(|(_, draw)| draw.contains("Delete this file?"))
+        .expect("deletion prompt was shown");
+    assert!(prompt.contains("file_to_delete"), "prompt names the file");
+    let redrawn_index = terminal_draw_events_mirror[prompt_index..]
+        .iter()
+        .position(|draw| draw.contains("Deleting"))
+        .map(|index| prompt_index + index + 1)
+        .expect("deletion was in progress");
+    let redrawn = &terminal_draw_events_mirror[redrawn_index];
+    assert!(
+        redrawn.contains("8.0K (67%)") && redrawn.contains("4.0K (33%)"),
+        "remaining tiles were resized"
+    );
+    assert!(
+        !terminal_draw_events_mirror[redrawn_index..]
+            .iter()
+            .any(|draw| draw.contains("file_to_delete")),
+        "deleted file is no longer shown"
+    );
+    assert!(
+        terminal_draw_events_mirror[redrawn_index..]
+            .iter()
+            .any(|draw| draw.contains("Total: 12.0K (2 files), freed: 16.0K")),
+        "totals and space freed were updated"
+    );
+    assert_snapshot!(redrawn);
+}
+
+#[test]
+fn report_lists_deleted_and_largest_files() {
+    let (_terminal_events, _terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char 'l')),
+        None,
+        Some(key!(Backspace)),
+        None,
+        Some(key!(char 'y')),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+    let temp_dir_path = create_root_temp_dir("report_lists_deleted_and_largest_files")
+        .expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file_to_delete");
+    create_temp_file(&file_1_path, 16384).expect("failed to create temp file");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_2_path = PathBuf::from(&subfolder_1_path);
+    file_2_path.push("file2");
+    create_temp_file(&file_2_path, 4096).expect("failed to create temp file");
+
+    let mut file_3_path = PathBuf::from(&temp_dir_path);
+    file_3_path.push("file3");
+    create_temp_file(&file_3_path, 8192).expect("failed to create temp file");
+
+    let (_, report) = start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            collect_report: true,
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    let report = report.expect("report was collected");
+    assert_eq!(report.scanned_path, temp_dir_path);
+    assert_eq!(report.total_size, 12288);
+    // like in the title line, folders are counted along with the files
+    assert_eq!(report.total_files, 3);
+    assert!(report.scan_duration.is_some(), "scan was completed");
+    assert_eq!(report.deleted, vec![(file_1_path, 16384)]);
+    assert_eq!(
+        report.largest_files,
+        vec![(file_3_path, 8192), (file_2_path, 4096)]
+    );
+}
+
 #[test]
 fn delete_folder() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
@@ -1937,8 +2927,7 @@ fn delete_folder() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2034,8 +3023,13 @@ fn delete_folder_no_confirmation() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_DISABLED,
+        StartOptions {
+            app: AppOptions {
+                disable_delete_confirmation: true,
+                ..app_options()
+            },
+            ..start_options()
+        },
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2135,8 +3129,7 @@ fn delete_folder_small_window() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2237,8 +3230,13 @@ fn delete_folder_small_window_no_confirmation() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_DISABLED,
+        StartOptions {
+            app: AppOptions {
+                disable_delete_confirmation: true,
+                ..app_options()
+            },
+            ..start_options()
+        },
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2353,8 +3351,7 @@ fn delete_folder_with_multiple_children() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2484,8 +3481,13 @@ fn delete_folder_with_multiple_children_no_confirmation() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_DISABLED,
+        StartOptions {
+            app: AppOptions {
+                disable_delete_confirmation: true,
+                ..app_options()
+            },
+            ..start_options()
+        },
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2586,8 +3588,7 @@ fn pressing_delete_with_no_selected_tile() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2669,8 +3670,7 @@ fn delete_file_press_n() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2719,7 +3719,7 @@ fn delete_file_press_n() {
 #[test]
 fn files_with_size_zero() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path =
         create_root_temp_dir("files_with_size_zero").expect("failed to create temp dir");
 
@@ -2739,8 +3739,10 @@ fn files_with_size_zero() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -2757,18 +3759,34 @@ fn files_with_size_zero() {
     assert_snapshot!(&terminal_draw_events_mirror[1]);
 }
 
+#[cfg(not(target_os = "windows"))]
 #[test]
-fn empty_folder() {
+fn hardlinked_files() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
-    let temp_dir_path = create_root_temp_dir("empty_folder").expect("failed to create temp dir");
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
+    let temp_dir_path =
+        create_root_temp_dir("hardlinked_files").expect("failed to create temp dir");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("file1");
+    create_temp_file(&file_1_path, 8192).expect("failed to create temp file");
+
+    let mut link_path = PathBuf::from(&temp_dir_path);
+    link_path.push("file1_link");
+    std::fs::hard_link(&file_1_path, link_path).expect("failed to create hard link");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
 
     start(
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -2784,9 +3802,39 @@ fn empty_folder() {
     assert_snapshot!(&terminal_draw_events_mirror[0]);
     assert_snapshot!(&terminal_draw_events_mirror[1]);
 }
-#[cfg(not(target_os = "windows"))]
+
 #[test]
-fn permission_denied_when_deleting() {
+fn empty_folder() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
+    let temp_dir_path = create_root_temp_dir("empty_folder").expect("failed to create temp dir");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Draw, HideCursor, Flush, Clear, ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn permission_denied_when_deleting() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
 
     let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
@@ -2827,8 +3875,7 @@ fn permission_denied_when_deleting() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        start_options(),
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2914,8 +3961,13 @@ fn permission_denied_when_deleting_no_confirmation() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_DISABLED,
+        StartOptions {
+            app: AppOptions {
+                disable_delete_confirmation: true,
+                ..app_options()
+            },
+            ..start_options()
+        },
     );
     let terminal_draw_events_mirror = terminal_draw_events
         .lock()
@@ -2963,7 +4015,7 @@ fn permission_denied_when_deleting_no_confirmation() {
 #[test]
 fn small_files_with_y_as_zero() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path =
         create_root_temp_dir("small_files_with_y_as_zero").expect("failed to create temp dir");
 
@@ -2981,8 +4033,10 @@ fn small_files_with_y_as_zero() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -3002,7 +4056,7 @@ fn small_files_with_y_as_zero() {
 #[test]
 fn small_files_with_x_as_zero() {
     let (terminal_events, terminal_draw_events, backend) = test_backend_factory(50, 50);
-    let keyboard_events = sleep_and_quit_events(1, true);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
     let temp_dir_path =
         create_root_temp_dir("small_files_with_x_as_zero").expect("failed to create temp dir");
 
@@ -3024,8 +4078,10 @@ fn small_files_with_x_as_zero() {
         backend,
         keyboard_events,
         temp_dir_path.clone(),
-        SHOW_APPARENT_SIZE,
-        DELETE_CONFIRMATION_ENABLED,
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
     );
     std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
     let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
@@ -3041,3 +4097,601 @@ fn small_files_with_x_as_zero() {
     assert_snapshot!(&terminal_draw_events_mirror[0]);
     assert_snapshot!(&terminal_draw_events_mirror[1]);
 }
+
+#[test]
+fn exit_path_is_entered_folder() {
+    let (_terminal_events, _terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(key!(char '\n')));
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let temp_dir_path =
+        create_root_temp_dir("exit_path_is_entered_folder").expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    let (exit_path, _) = start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        start_options(),
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+
+    assert_eq!(exit_path, subfolder_1_path);
+}
+
+#[test]
+fn restore_session() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
+    let temp_dir_path = create_root_temp_dir("restore_session").expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("subfolder1");
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    let mut file_3_path = PathBuf::from(&temp_dir_path);
+    file_3_path.push("file3");
+    create_temp_file(file_3_path, 4096).expect("failed to create temp file");
+
+    let restored_session = SessionState {
+        root_path: temp_dir_path.clone(),
+        current_path: subfolder_1_path,
+        zoom_level: 1,
+        apparent_size: SHOW_APPARENT_SIZE,
+        sort_order: SortOrder::BySize,
+    };
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            app: AppOptions {
+                restored_session: Some(restored_session),
+                ..app_options()
+            },
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Draw, HideCursor, Flush, Clear, ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+
+#[test]
+fn most_accessed_modal() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(key!(Right)));
+    events.push(None);
+    events.push(Some(key!(Right)));
+    events.push(None);
+    events.push(Some(key!(Left)));
+    events.push(None);
+    events.push(Some(key!(char 'M')));
+    events.push(None);
+    events.push(Some(key!(Esc)));
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events));
+
+    let temp_dir_path =
+        create_root_temp_dir("most_accessed_modal").expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(&subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        start_options(),
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Draw, HideCursor, Flush, Draw, HideCursor,
+        Flush, Draw, HideCursor, Flush, Draw, HideCursor, Flush, Draw, HideCursor, Flush, Draw,
+        HideCursor, Flush, Clear, ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+
+    assert_eq!(terminal_draw_events_mirror.len(), 7);
+    assert_snapshot!(&terminal_draw_events_mirror[4]);
+    assert_snapshot!(&terminal_draw_events_mirror[5]);
+}
+
+#[test]
+fn largest_files_modal() {
+    let temp_dir_path =
+        create_root_temp_dir("largest_files_modal").expect("failed to create temp dir");
+    let subfolder_path = temp_dir_path.join("subfolder1");
+    create_dir(&subfolder_path).expect("failed to create temporary directory");
+    create_temp_file(subfolder_path.join("nested_file"), 8192).expect("failed to create temp file");
+    create_temp_file(temp_dir_path.join("top_file"), 4096).expect("failed to create temp file");
+
+    let (terminal_draw_events, _) =
+        navigation_draw_events(&temp_dir_path, &[key!(char 'L'), key!(Esc)]);
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    let largest_files_draw = terminal_draw_events
+        .iter()
+        .find(|draw| draw.contains("Largest files"))
+        .expect("the largest files were shown");
+    // the file in the subfolder is found too, and comes first
+    let nested_file = largest_files_draw
+        .find("subfolder1/nested_file")
+        .expect("nested file is listed");
+    let top_file = largest_files_draw
+        .find("top_file")
+        .expect("top file is listed");
+    assert!(nested_file < top_file);
+}
+
+#[test]
+fn sort_tiles_by_name() {
+    let temp_dir_path =
+        create_root_temp_dir("sort_tiles_by_name").expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("b_file"), 8192).expect("failed to create temp file");
+    create_temp_file(temp_dir_path.join("a_file"), 4096).expect("failed to create temp file");
+
+    let (terminal_draw_events, _) = navigation_draw_events(&temp_dir_path, &[key!(char 'o')]);
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(
+        terminal_draw_events
+            .iter()
+            .any(|draw| draw.contains("(sorted by name)"))
+    );
+}
+
+#[test]
+fn duplicates_modal() {
+    let temp_dir_path =
+        create_root_temp_dir("duplicates_modal").expect("failed to create temp dir");
+    let subfolder_path = temp_dir_path.join("subfolder1");
+    create_dir(&subfolder_path).expect("failed to create temporary directory");
+    create_temp_file(subfolder_path.join("copy"), 8192).expect("failed to create temp file");
+    create_temp_file(temp_dir_path.join("original"), 8192).expect("failed to create temp file");
+    create_temp_file(temp_dir_path.join("other_size"), 4096).expect("failed to create temp file");
+
+    let (terminal_draw_events, _) =
+        navigation_draw_events(&temp_dir_path, &[key!(char 'D'), key!(Esc)]);
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    let duplicates_draw = terminal_draw_events
+        .iter()
+        .find(|draw| draw.contains("Duplicate files"))
+        .expect("the duplicates were shown");
+    assert!(duplicates_draw.contains("subfolder1/copy"));
+    assert!(duplicates_draw.contains("original"));
+    // nothing else is the same size, so it can't be a duplicate
+    assert!(!duplicates_draw.contains("other_size"));
+}
+
+#[test]
+fn filter_by_extension() {
+    let temp_dir_path =
+        create_root_temp_dir("filter_by_extension").expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("app.log"), 4096).expect("failed to create temp file");
+    create_temp_file(temp_dir_path.join("notes.txt"), 8192).expect("failed to create temp file");
+
+    let (terminal_draw_events, _) = navigation_draw_events(
+        &temp_dir_path,
+        &[
+            key!(char 'f'),
+            key!(char '.'),
+            key!(char 'l'),
+            key!(char 'o'),
+            key!(char 'g'),
+            key!(Enter),
+        ],
+    );
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(
+        terminal_draw_events
+            .iter()
+            .any(|draw| draw.contains("Filter by extension: .log"))
+    );
+    let filtered_draw = terminal_draw_events
+        .iter()
+        .position(|draw| draw.contains("[filtered: .log]"))
+        .expect("the filter was applied");
+    // the file that doesn't match makes way for the one that does
+    assert!(terminal_draw_events[filtered_draw].contains("app.log"));
+}
+
+#[test]
+fn search_by_name() {
+    let temp_dir_path = create_root_temp_dir("search_by_name").expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("app.log"), 4096).expect("failed to create temp file");
+    create_temp_file(temp_dir_path.join("notes.txt"), 8192).expect("failed to create temp file");
+    create_dir(temp_dir_path.join("old")).expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("old/APP_old.log"), 8192)
+        .expect("failed to create temp file");
+
+    let (terminal_draw_events, _) = navigation_draw_events(
+        &temp_dir_path,
+        &[
+            key!(char '/'),
+            key!(char 'a'),
+            key!(char 'p'),
+            key!(char 'p'),
+            key!(Enter),
+            key!(char 'n'),
+            key!(Esc),
+        ],
+    );
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    assert!(
+        terminal_draw_events
+            .iter()
+            .any(|draw| draw.contains("Search: app"))
+    );
+    let results_draw = terminal_draw_events
+        .iter()
+        .position(|draw| draw.contains("Search: 'app' — 2 of 2 files shown"))
+        .expect("the search found both files");
+    assert!(
+        terminal_draw_events[results_draw..]
+            .iter()
+            .any(|draw| !draw.contains("Search:")),
+        "ESC clears the search"
+    );
+}
+
+#[test]
+fn jump_to_path() {
+    let temp_dir_path = create_root_temp_dir("jump_to_path").expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("notes.txt"), 8192).expect("failed to create temp file");
+    create_dir(temp_dir_path.join("subfolder")).expect("failed to create temp dir");
+    create_temp_file(temp_dir_path.join("subfolder/deep_file"), 4096)
+        .expect("failed to create temp file");
+
+    let (terminal_draw_events, _) = navigation_draw_events(
+        &temp_dir_path,
+        &[
+            key!(char 'g'),
+            key!(char '/'),
+            key!(char 's'),
+            key!(char 'u'),
+            key!(Tab),
+            key!(Enter),
+        ],
+    );
+    std::fs::remove_dir_all(&temp_dir_path).expect("failed to remove temporary folder");
+
+    let modal_draw = terminal_draw_events
+        .iter()
+        .position(|draw| draw.contains("Go to path"))
+        .expect("g opens the jump to path box");
+    assert!(
+        terminal_draw_events[modal_draw..]
+            .iter()
+            .any(|draw| !draw.contains("Go to path") && draw.contains("deep_file")),
+        "TAB completes the folder name and ENTER goes there"
+    );
+}
+
+fn layout_algorithm_draw_events(name: &str, layout_algorithm: LayoutAlgorithm) -> Vec<String> {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(190, 50);
+    let (keyboard_events, scan_complete_barrier) = wait_for_scan_and_quit_events(true);
+    let temp_dir_path = create_root_temp_dir(name).expect("failed to create temp dir");
+
+    let file_sizes = [
+        151552, 53248, 53248, 53248, 53248, 53248, 8192, 8192, 8192, 8192, 8192,
+    ];
+    for (index, size) in file_sizes.iter().enumerate() {
+        let mut file_path = PathBuf::from(&temp_dir_path);
+        file_path.push(format!("file{}", index + 1));
+        create_temp_file(file_path, *size).expect("failed to create temp file");
+    }
+    for index in 0..20 {
+        let mut file_path = PathBuf::from(&temp_dir_path);
+        file_path.push(format!("small_file{}", index + 1));
+        create_temp_file(file_path, 1024).expect("failed to create temp file");
+    }
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            app: AppOptions {
+                layout_algorithm,
+                ..app_options()
+            },
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Draw, HideCursor, Flush, Clear, ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+    terminal_draw_events.lock().unwrap().clone()
+}
+
+#[test]
+fn layout_slice_and_dice() {
+    let terminal_draw_events_mirror =
+        layout_algorithm_draw_events("layout_slice_and_dice", LayoutAlgorithm::SliceAndDice);
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+}
+
+#[test]
+fn layout_strip() {
+    let terminal_draw_events_mirror =
+        layout_algorithm_draw_events("layout_strip", LayoutAlgorithm::Strip);
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+}
+
+#[test]
+fn layout_binary_partition() {
+    let terminal_draw_events_mirror =
+        layout_algorithm_draw_events("layout_binary_partition", LayoutAlgorithm::BinaryPartition);
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+}
+
+#[test]
+fn resize_terminal() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(120, 40);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(Event::Resize(80, 24)));
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events).resize_backend(backend.clone()));
+
+    let temp_dir_path = create_root_temp_dir("resize_terminal").expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        start_options(),
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Clear, Draw, HideCursor, Flush, Draw,
+        HideCursor, Flush, Clear, ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+
+    assert_eq!(terminal_draw_events_mirror.len(), 3);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+    assert_snapshot!(&terminal_draw_events_mirror[2]);
+}
+
+#[test]
+fn resize_terminal_below_minimum() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(120, 40);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(Event::Resize(40, 10)));
+    events.push(None);
+    // when the screen is too small, we quit without asking for confirmation
+    events.push(Some(key!(ctrl 'c')));
+    let keyboard_events = Box::new(TerminalEvents::new(events).resize_backend(backend.clone()));
+
+    let temp_dir_path =
+        create_root_temp_dir("resize_terminal_below_minimum").expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        start_options(),
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Clear, Draw, HideCursor, Flush, Clear,
+        ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+
+    assert_eq!(terminal_draw_events_mirror.len(), 2);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+}
+
+#[test]
+fn resize_terminal_back_from_too_small() {
+    let (terminal_events, terminal_draw_events, backend) = test_backend_factory(40, 10);
+
+    let mut events: Vec<Option<Event>> = iter::repeat(None).take(1).collect();
+    events.push(Some(Event::Resize(120, 40)));
+    events.push(None);
+    events.push(Some(key!(ctrl 'c')));
+    events.push(None);
+    events.push(Some(key!(char 'y')));
+    let keyboard_events = Box::new(TerminalEvents::new(events).resize_backend(backend.clone()));
+
+    let temp_dir_path = create_root_temp_dir("resize_terminal_back_from_too_small")
+        .expect("failed to create temp dir");
+
+    let mut subfolder_1_path = PathBuf::from(&temp_dir_path);
+    subfolder_1_path.push("subfolder1");
+    create_dir(subfolder_1_path).expect("failed to create temporary directory");
+
+    let mut file_1_path = PathBuf::from(&temp_dir_path);
+    file_1_path.push("subfolder1");
+    file_1_path.push("file1");
+    create_temp_file(file_1_path, 8192).expect("failed to create temp file");
+
+    let mut file_2_path = PathBuf::from(&temp_dir_path);
+    file_2_path.push("file2");
+    create_temp_file(file_2_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        start_options(),
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    let expected_terminal_events = vec![
+        Clear, HideCursor, Draw, HideCursor, Flush, Clear, Draw, HideCursor, Flush, Draw,
+        HideCursor, Flush, Clear, ShowCursor,
+    ];
+    assert_eq!(
+        &terminal_events.lock().unwrap()[..],
+        &expected_terminal_events[..]
+    );
+
+    assert_eq!(terminal_draw_events_mirror.len(), 3);
+    assert_snapshot!(&terminal_draw_events_mirror[0]);
+    assert_snapshot!(&terminal_draw_events_mirror[1]);
+    assert_snapshot!(&terminal_draw_events_mirror[2]);
+}
+
+#[test]
+fn help_modal() {
+    let (_terminal_events, terminal_draw_events, backend) = test_backend_factory(60, 20);
+    let scan_complete_barrier = Arc::new(Barrier::new(2));
+    let events = vec![
+        Some(key!(char '?')),
+        None,
+        Some(key!(PageDown)),
+        None,
+        Some(key!(Esc)),
+        None,
+        Some(key!(ctrl 'c')),
+        None,
+        Some(key!(char 'y')),
+    ];
+    let keyboard_events =
+        Box::new(TerminalEvents::new(events).wait_for_barrier(scan_complete_barrier.clone()));
+
+    let temp_dir_path = create_root_temp_dir("help_modal").expect("failed to create temp dir");
+    let mut file_path = PathBuf::from(&temp_dir_path);
+    file_path.push("file1");
+    create_temp_file(file_path, 4096).expect("failed to create temp file");
+
+    start(
+        backend,
+        keyboard_events,
+        temp_dir_path.clone(),
+        StartOptions {
+            scan_complete_barrier: Some(scan_complete_barrier),
+            ..start_options()
+        },
+    );
+    std::fs::remove_dir_all(temp_dir_path).expect("failed to remove temporary folder");
+    let terminal_draw_events_mirror = terminal_draw_events.lock().unwrap();
+
+    let help_shown = terminal_draw_events_mirror
+        .iter()
+        .position(|draw_event| draw_event.contains("Diskonaut Keybindings"))
+        .expect("help modal should be shown");
+    // the first page, and then a page further down once scrolled
+    assert_snapshot!(&terminal_draw_events_mirror[help_shown]);
+    assert_snapshot!(&terminal_draw_events_mirror[help_shown + 1]);
+    // closing it brings back the tiles
+    assert!(terminal_draw_events_mirror[help_shown + 2].contains("file1"));
+}
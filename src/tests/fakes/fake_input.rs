@@ -1,25 +1,61 @@
-use std::{thread, time};
+use std::{
+    sync::{
+        Arc, Barrier,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread, time,
+};
 
-use crossterm::event::Event;
+use crossterm::event::{Event, poll};
+
+use crate::tests::fakes::TestBackend;
 
 pub struct TerminalEvents {
     pub events: Vec<Option<Event>>,
+    backend: Option<TestBackend>,
+    barrier: Option<Arc<Barrier>>,
 }
 
 impl TerminalEvents {
     pub fn new(mut events: Vec<Option<Event>>) -> Self {
         events.reverse(); // this is so that we do not have to shift the array
-        TerminalEvents { events }
+        TerminalEvents {
+            events,
+            backend: None,
+            barrier: None,
+        }
+    }
+    pub fn wait_for_barrier(mut self, barrier: Arc<Barrier>) -> Self {
+        // no events will be sent before the barrier is released
+        self.barrier = Some(barrier);
+        self
+    }
+    pub fn resize_backend(mut self, backend: TestBackend) -> Self {
+        // resize events will change the size of this backend before
+        // they are sent, the way a real terminal would
+        self.backend = Some(backend);
+        self
     }
 }
 impl Iterator for TerminalEvents {
     type Item = Event;
     fn next(&mut self) -> Option<Event> {
+        if let Some(barrier) = self.barrier.take() {
+            barrier.wait();
+        }
         match self.events.pop() {
             Some(ev) => match ev {
+                Some(Event::Resize(w, h)) => {
+                    if let Some(backend) = &self.backend {
+                        backend.set_size(w, h);
+                    }
+                    Some(Event::Resize(w, h))
+                }
                 Some(ev) => Some(ev),
                 None => {
-                    thread::sleep(time::Duration::from_millis(200));
+                    // no event this time around: wait for one the way the real
+                    // terminal does, there is never one to read so this times out
+                    let _ = poll(time::Duration::from_millis(200));
                     self.next()
                 }
             },
@@ -27,3 +63,48 @@ impl Iterator for TerminalEvents {
         }
     }
 }
+
+// like TerminalEvents, but a None is a step rather than a wait: it bumps a counter
+// shared with the test instead of waiting for the app to catch up, so that the test
+// can tell how far along the events are without depending on how fast the machine is
+pub struct TimedTerminalEvents {
+    pub events: Vec<Option<Event>>,
+    step_count: Arc<AtomicUsize>,
+    barrier: Option<Arc<Barrier>>,
+}
+
+impl TimedTerminalEvents {
+    pub fn new(mut events: Vec<Option<Event>>) -> Self {
+        events.reverse(); // this is so that we do not have to shift the array
+        TimedTerminalEvents {
+            events,
+            step_count: Arc::new(AtomicUsize::new(0)),
+            barrier: None,
+        }
+    }
+    pub fn wait_for_barrier(mut self, barrier: Arc<Barrier>) -> Self {
+        // no events will be sent before the barrier is released
+        self.barrier = Some(barrier);
+        self
+    }
+    pub fn step_count(&self) -> Arc<AtomicUsize> {
+        self.step_count.clone()
+    }
+}
+impl Iterator for TimedTerminalEvents {
+    type Item = Event;
+    fn next(&mut self) -> Option<Event> {
+        if let Some(barrier) = self.barrier.take() {
+            barrier.wait();
+        }
+        match self.events.pop() {
+            Some(Some(ev)) => Some(ev),
+            Some(None) => {
+                self.step_count.fetch_add(1, Ordering::AcqRel);
+                thread::yield_now();
+                self.next()
+            }
+            None => None,
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub enum TerminalEvent {
     Draw,
 }
 
+#[derive(Clone)]
 pub struct TestBackend {
     pub events: Arc<Mutex<Vec<TerminalEvent>>>,
     pub draw_events: Arc<Mutex<Vec<String>>>,
@@ -37,6 +38,10 @@ impl TestBackend {
             terminal_height,
         }
     }
+    pub fn set_size(&self, w: u16, h: u16) {
+        *self.terminal_width.lock().unwrap() = w;
+        *self.terminal_height.lock().unwrap() = h;
+    }
 }
 
 #[derive(Hash, Eq, PartialEq)]
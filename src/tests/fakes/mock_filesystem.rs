@@ -0,0 +1,35 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::scanner::MetadataProvider;
+
+// the real filesystem, except that reading the metadata of some paths fails the way
+// it would for files we're not allowed to look at (without needing to be root to set it up)
+#[derive(Clone, Debug, Default)]
+pub struct MockFilesystem {
+    pub should_fail_paths: HashSet<PathBuf>,
+}
+
+impl MockFilesystem {
+    pub fn new() -> Self {
+        MockFilesystem::default()
+    }
+    pub fn fail_on(mut self, path: impl Into<PathBuf>) -> Self {
+        self.should_fail_paths.insert(path.into());
+        self
+    }
+    pub fn metadata_provider(&self) -> Arc<dyn MetadataProvider> {
+        let should_fail_paths = self.should_fail_paths.clone();
+        Arc::new(move |path: &Path| {
+            if should_fail_paths.contains(path) {
+                Err(io::Error::from(io::ErrorKind::PermissionDenied))
+            } else {
+                fs::symlink_metadata(path)
+            }
+        })
+    }
+}
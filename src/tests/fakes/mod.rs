@@ -1,5 +1,7 @@
 pub use fake_input::*;
 pub use fake_output::*;
+pub use mock_filesystem::*;
 
 mod fake_input;
 mod fake_output;
+mod mock_filesystem;
@@ -1,25 +1,82 @@
-use ::std::path::PathBuf;
+use ::std::path::{Path, PathBuf};
 use ::tui::buffer::Buffer;
 use ::tui::layout::Rect;
 use ::tui::style::{Color, Modifier, Style};
 use ::tui::widgets::Widget;
 
 use crate::state::tiles::{FileType, Tile};
+use crate::ui::color_rules::{AGE_COLORS, mime_type};
 use crate::ui::format::{DisplaySize, truncate_middle};
+use crate::ui::input_widget::TextInput;
 
-fn render_currently_selected(buf: &mut Buffer, currently_selected: &Tile, max_len: u16, y: u16) {
+fn render_zero_byte_files(buf: &mut Buffer, count: u64, max_len: u16, y: u16) {
+    let lines = [
+        format!(
+            "Zero-byte files: {} (0 bytes) — these may be lock files or corrupt",
+            count
+        ),
+        format!("Zero-byte files: {} (0 bytes)", count),
+        format!("Zero-byte files: {}", count),
+    ];
+    let style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD | Modifier::ITALIC);
+    if let Some(line) = lines
+        .iter()
+        .find(|line| (line.chars().count() as u16) < max_len)
+    {
+        buf.set_string(1, y, line, style);
+    }
+}
+
+fn render_currently_selected(
+    buf: &mut Buffer,
+    currently_selected: &Tile,
+    mime_type: Option<&str>,
+    max_len: u16,
+    y: u16,
+) {
+    if let Some(count) = currently_selected.zero_byte_files {
+        render_zero_byte_files(buf, count, max_len, y);
+        return;
+    }
     let file_name = currently_selected.name.to_string_lossy();
     let size = DisplaySize(currently_selected.size as f64);
     let descendants = currently_selected.descendants;
     let (style, lines) = match currently_selected.file_type {
-        FileType::File => (
-            Style::default().add_modifier(Modifier::BOLD),
-            vec![
+        FileType::File => {
+            let mut lines = vec![
                 format!("SELECTED: {} ({})", file_name, size),
                 format!("SELECTED: {}", file_name),
                 format!("{}", file_name),
-            ],
-        ),
+            ];
+            if let Some(mime_type) = mime_type {
+                lines.insert(
+                    0,
+                    format!("SELECTED: {} ({}, {})", file_name, size, mime_type),
+                );
+            }
+            if currently_selected.is_hardlinked() {
+                let num_hardlinks = currently_selected.num_hardlinks.unwrap_or(1);
+                lines.insert(
+                    0,
+                    format!(
+                        "SELECTED: {} ({}, hard links: {})",
+                        file_name, size, num_hardlinks
+                    ),
+                );
+                if let Some(mime_type) = mime_type {
+                    lines.insert(
+                        0,
+                        format!(
+                            "SELECTED: {} ({}, {}, hard links: {})",
+                            file_name, size, mime_type, num_hardlinks
+                        ),
+                    );
+                }
+            }
+            (Style::default().add_modifier(Modifier::BOLD), lines)
+        }
         FileType::Folder => (
             Style::default()
                 .fg(Color::Blue)
@@ -59,42 +116,156 @@ fn render_last_read_path(buf: &mut Buffer, last_read_path: &PathBuf, max_len: u1
     }
 }
 
-fn render_controls_legend(buf: &mut Buffer, hide_delete: bool, max_len: u16, y: u16) {
+fn render_copied_to_clipboard(buf: &mut Buffer, copied_text: &str, max_len: u16, y: u16) {
+    let prefix = "Copied to clipboard: ";
+    let max_copied_text_len = max_len.saturating_sub(prefix.chars().count() as u16);
+    let line = format!(
+        "{}{}",
+        prefix,
+        truncate_middle(copied_text, max_copied_text_len)
+    );
+    buf.set_string(1, y, line, Style::default().add_modifier(Modifier::BOLD));
+}
+
+fn render_notice(buf: &mut Buffer, notice: &str, max_len: u16, y: u16) {
+    buf.set_string(
+        1,
+        y,
+        truncate_middle(notice, max_len),
+        Style::default().add_modifier(Modifier::BOLD),
+    );
+}
+
+fn render_search_results(buf: &mut Buffer, search_display: &SearchDisplay, max_len: u16, y: u16) {
+    let (query, visible_count, total_count) = search_display;
+    let counts = format!("{} of {} files shown", visible_count, total_count);
+    let max_query_len = max_len
+        .saturating_sub("Search: '' — ".chars().count() as u16 + counts.chars().count() as u16);
+    let line = format!(
+        "Search: '{}' — {}",
+        truncate_middle(query, max_query_len),
+        counts
+    );
+    buf.set_string(
+        1,
+        y,
+        line,
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+}
+
+fn render_text_input(buf: &mut Buffer, prefix: &str, input: &TextInput, max_len: u16, y: u16) {
+    // the input scrolls to keep its cursor in sight, in the same colors as the prefix
+    let prefix_len = prefix.chars().count() as u16;
+    let input_area = Rect::new(1 + prefix_len, y, max_len.saturating_sub(prefix_len), 1);
+    let style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    buf.set_string(1, y, prefix, style);
+    buf.set_string(
+        input_area.x,
+        y,
+        " ".repeat(input_area.width as usize),
+        style,
+    );
+    input.render(input_area, buf);
+}
+
+fn extension_filter_indicator(extension: &str) -> String {
+    format!("[filtered: .{}]", extension)
+}
+
+fn render_extension_filter(buf: &mut Buffer, indicator: &str, x: u16, y: u16) {
+    buf.set_string(
+        x,
+        y,
+        indicator,
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+}
+
+fn render_search_controls_legend(buf: &mut Buffer, max_len: u16, y: u16) {
+    let long_controls_line = "<type> - filter, <ESC> - clear, <ENTER> - confirm";
+    let short_controls_line = "<ESC>: clear, <ENTER>: confirm";
+    let too_small_line = "(...)";
+    let line = if max_len >= long_controls_line.chars().count() as u16 {
+        long_controls_line
+    } else if max_len >= short_controls_line.chars().count() as u16 {
+        short_controls_line
+    } else {
+        too_small_line
+    };
+    buf.set_string(1, y, line, Style::default().add_modifier(Modifier::BOLD));
+}
+
+fn render_search_results_legend(buf: &mut Buffer, max_len: u16, y: u16) {
+    let long_controls_line =
+        "<n> - next match, <N> - previous match, <arrows> - move around, <ESC> - clear search";
+    let short_controls_line = "<n/N>: next/previous, <ESC>: clear";
+    let too_small_line = "(...)";
+    let line = if max_len >= long_controls_line.chars().count() as u16 {
+        long_controls_line
+    } else if max_len >= short_controls_line.chars().count() as u16 {
+        short_controls_line
+    } else {
+        too_small_line
+    };
+    buf.set_string(1, y, line, Style::default().add_modifier(Modifier::BOLD));
+}
+
+fn render_controls_legend(buf: &mut Buffer, hide_delete: bool, max_len: u16, y: u16) -> u16 {
+    // returns how much of the line it took up
     let (long_controls_line, short_controls_line) = if hide_delete {
         (
             String::from(
-                "<arrows> - move around, <ENTER> - enter folder, <ESC> - parent folder, <+/-/0> - zoom in/out/reset, <q> - quit",
+                "<arrows> - move around, <ENTER> - enter folder, <ESC> - parent folder, <~> - scanned folder, <+/-/0> - zoom in/out/reset, <q> - quit",
             ),
             String::from("←↓↑→/<ENTER>/<ESC>: navigate"),
         )
     } else {
         (
             String::from(
-                "<arrows> - move around, <ENTER> - enter folder, <ESC> - parent folder, <BACKSPACE> - delete, <+/-/0> - zoom in/out/reset, <q> - quit",
+                "<arrows> - move around, <ENTER> - enter folder, <ESC> - parent folder, <~> - scanned folder, <BACKSPACE> - delete, <+/-/0> - zoom in/out/reset, <q> - quit",
             ),
             String::from("←↓↑→/<ENTER>/<ESC>: navigate, <BACKSPACE>: del"),
         )
     };
-    let too_small_line = "(...)";
-    if max_len >= long_controls_line.chars().count() as u16 {
-        buf.set_string(
-            1,
-            y,
-            long_controls_line,
-            Style::default().add_modifier(Modifier::BOLD),
-        );
+    let too_small_line = String::from("(...)");
+    let line = if max_len >= long_controls_line.chars().count() as u16 {
+        long_controls_line
     } else if max_len >= short_controls_line.chars().count() as u16 {
-        buf.set_string(
-            1,
-            y,
-            short_controls_line,
-            Style::default().add_modifier(Modifier::BOLD),
-        );
+        short_controls_line
     } else {
+        too_small_line
+    };
+    buf.set_string(1, y, &line, Style::default().add_modifier(Modifier::BOLD));
+    line.chars().count() as u16 + 1
+}
+
+// "14:32:07" and a space to keep it apart from the controls
+const CLOCK_WIDTH: u16 = 9;
+
+fn render_clock(buf: &mut Buffer, area: Rect, y: u16) {
+    let time = chrono::Local::now().format("%H:%M:%S").to_string();
+    buf.set_string(
+        area.x + area.width - CLOCK_WIDTH,
+        y,
+        time,
+        Style::default().add_modifier(Modifier::BOLD),
+    );
+}
+
+fn render_essential_controls_legend(buf: &mut Buffer, max_len: u16, y: u16) {
+    let controls_line = "←↓↑→/<ENTER>/<ESC>: navigate";
+    if max_len >= controls_line.chars().count() as u16 {
         buf.set_string(
             1,
             y,
-            too_small_line,
+            controls_line,
             Style::default().add_modifier(Modifier::BOLD),
         );
     }
@@ -114,11 +285,49 @@ fn render_small_files_legend(buf: &mut Buffer, x: u16, y: u16, small_files_legen
     small_files_legend_character.set_style(Style::default().bg(Color::White).fg(Color::Black));
 }
 
+fn age_legend_len() -> u16 {
+    // "Age:" followed by a space and each label
+    AGE_COLORS
+        .iter()
+        .map(|(_, label, _)| label.chars().count() as u16 + 1)
+        .sum::<u16>()
+        + "Age:".len() as u16
+}
+
+fn render_age_legend(buf: &mut Buffer, x: u16, y: u16) {
+    let style = Style::default().add_modifier(Modifier::BOLD);
+    buf.set_string(x, y, "Age:", style);
+    let mut label_x = x + "Age:".len() as u16;
+    for (_, label, color) in AGE_COLORS {
+        buf.set_string(label_x + 1, y, label, style.fg(*color));
+        label_x += label.chars().count() as u16 + 1;
+    }
+}
+
+fn render_current_scan_dir(buf: &mut Buffer, current_scan_dir: &Path, max_len: u16, y: u16) {
+    let line = format!("Scanning: {}", current_scan_dir.to_string_lossy());
+    buf.set_string(1, y, truncate_middle(&line, max_len), Style::default());
+}
+
+// query, visible count, total count
+type SearchDisplay = (String, usize, usize);
+
 pub struct BottomLine<'a> {
     hide_delete: bool,
     hide_small_files_legend: bool,
     currently_selected: Option<&'a Tile>,
     last_read_path: Option<&'a PathBuf>,
+    current_scan_dir: Option<&'a PathBuf>,
+    copied_to_clipboard: Option<&'a String>,
+    notice: Option<&'a String>,
+    search_display: Option<SearchDisplay>,
+    extension_filter: Option<&'a str>,
+    extension_filter_input: Option<&'a TextInput>,
+    search_input: Option<&'a TextInput>,
+    multi_line: bool,
+    show_mime_type: bool,
+    age_legend: bool,
+    clock: bool,
 }
 
 impl<'a> BottomLine<'a> {
@@ -128,12 +337,28 @@ impl<'a> BottomLine<'a> {
             hide_small_files_legend: false,
             currently_selected: None,
             last_read_path: None,
+            current_scan_dir: None,
+            copied_to_clipboard: None,
+            notice: None,
+            search_display: None,
+            extension_filter: None,
+            extension_filter_input: None,
+            search_input: None,
+            multi_line: false,
+            show_mime_type: false,
+            age_legend: false,
+            clock: false,
         }
     }
     pub fn hide_delete(mut self) -> Self {
         self.hide_delete = true;
         self
     }
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        // nothing can be deleted at all, whatever we're doing
+        self.hide_delete |= read_only;
+        self
+    }
     pub fn hide_small_files_legend(mut self, should_hide_small_files_legend: bool) -> Self {
         self.hide_small_files_legend = should_hide_small_files_legend;
         self
@@ -142,39 +367,240 @@ impl<'a> BottomLine<'a> {
         self.currently_selected = currently_selected;
         self
     }
+    pub fn show_mime_type(mut self, show_mime_type: bool) -> Self {
+        // eg. "video/mp4" next to the size of the selected file
+        self.show_mime_type = show_mime_type;
+        self
+    }
+    pub fn age_legend(mut self, age_legend: bool) -> Self {
+        // which color stands for which age, instead of the small files legend
+        self.age_legend = age_legend;
+        self
+    }
+    pub fn clock(mut self, clock: bool) -> Self {
+        // the time of day at the end of the controls line, when there's room for it
+        self.clock = clock;
+        self
+    }
     pub fn last_read_path(mut self, last_read_path: Option<&'a PathBuf>) -> Self {
         self.last_read_path = last_read_path;
         self
     }
+    pub fn current_scan_dir(mut self, current_scan_dir: Option<&'a PathBuf>) -> Self {
+        // changes less often than last_read_path, which is left as a fallback
+        self.current_scan_dir = current_scan_dir;
+        self
+    }
+    pub fn copied_to_clipboard(mut self, copied_to_clipboard: Option<&'a String>) -> Self {
+        self.copied_to_clipboard = copied_to_clipboard;
+        self
+    }
+    pub fn notice(mut self, notice: Option<&'a String>) -> Self {
+        self.notice = notice;
+        self
+    }
+    pub fn multi_line(mut self, multi_line: bool) -> Self {
+        // the selected file's line is kept clear of the legend, for narrow terminals
+        self.multi_line = multi_line;
+        self
+    }
+    pub fn extension_filter(mut self, extension_filter: Option<&'a str>) -> Self {
+        // only files with this extension (and the folders they're in) are shown
+        self.extension_filter = extension_filter;
+        self
+    }
+    pub fn extension_filter_input(mut self, extension_filter_input: Option<&'a TextInput>) -> Self {
+        // what's been typed so far, in place of everything else on the status line
+        self.extension_filter_input = extension_filter_input;
+        self
+    }
+    pub fn search_input(mut self, search_input: Option<&'a TextInput>) -> Self {
+        // like extension_filter_input, for what's looked for with '/'
+        self.search_input = search_input;
+        self
+    }
+    pub fn showing_search_results(
+        mut self,
+        query: &str,
+        visible_count: usize,
+        total_count: usize,
+    ) -> Self {
+        self.search_display = Some((query.to_owned(), visible_count, total_count));
+        self
+    }
+}
+
+impl<'a> BottomLine<'a> {
+    fn selected_mime_type(&self) -> Option<String> {
+        if self.show_mime_type {
+            self.currently_selected.and_then(mime_type)
+        } else {
+            None
+        }
+    }
+    fn render_multi_line(self, area: Rect, buf: &mut Buffer) {
+        // the selected file gets the whole first line to itself and the legend
+        // makes do with what's left of the controls line
+        let small_files_legend = "(x = Small files)";
+        let legend_len = if self.age_legend {
+            age_legend_len()
+        } else if self.hide_small_files_legend {
+            0
+        } else {
+            small_files_legend.chars().count() as u16
+        };
+        let filter_indicator = self.extension_filter.map(extension_filter_indicator);
+        let filter_len = filter_indicator
+            .as_ref()
+            .map_or(0, |indicator| indicator.chars().count() as u16 + 1);
+        let max_len = area.width - 1;
+        let max_status_len = max_len.saturating_sub(filter_len);
+        let status_line_y = area.y + area.height - 2;
+        let controls_line_y = status_line_y + 1;
+        let selected_mime_type = self.selected_mime_type();
+        if let Some(input) = self.extension_filter_input {
+            render_text_input(buf, "Filter by extension: ", input, max_len, status_line_y);
+        } else if let Some(input) = self.search_input {
+            render_text_input(buf, "Search: ", input, max_len, status_line_y);
+        } else if let Some(search_display) = &self.search_display {
+            render_search_results(buf, search_display, max_status_len, status_line_y);
+        } else if let Some(notice) = self.notice {
+            render_notice(buf, notice, max_status_len, status_line_y);
+        } else if let Some(copied_to_clipboard) = self.copied_to_clipboard {
+            render_copied_to_clipboard(buf, copied_to_clipboard, max_status_len, status_line_y);
+        } else if let Some(currently_selected) = self.currently_selected {
+            render_currently_selected(
+                buf,
+                currently_selected,
+                selected_mime_type.as_deref(),
+                max_status_len,
+                status_line_y,
+            );
+        } else if let Some(current_scan_dir) = self.current_scan_dir {
+            render_current_scan_dir(buf, current_scan_dir, max_status_len, status_line_y);
+        } else if let Some(last_read_path) = self.last_read_path {
+            render_last_read_path(buf, last_read_path, max_status_len, status_line_y);
+        }
+
+        if let Some(filter_indicator) = &filter_indicator
+            && max_len > filter_len
+        {
+            render_extension_filter(
+                buf,
+                filter_indicator,
+                area.width - filter_len,
+                status_line_y,
+            );
+        }
+
+        if self.search_input.is_some() || self.extension_filter_input.is_some() {
+            render_search_controls_legend(buf, max_len, controls_line_y);
+        } else if self.search_display.is_some() {
+            render_search_results_legend(buf, max_len, controls_line_y);
+        } else if legend_len == 0 {
+            render_controls_legend(buf, self.hide_delete, max_len, controls_line_y);
+        } else {
+            render_essential_controls_legend(buf, max_len - legend_len, controls_line_y);
+        }
+
+        if self.age_legend {
+            render_age_legend(buf, area.width - legend_len - 1, controls_line_y);
+        } else if !self.hide_small_files_legend {
+            render_small_files_legend(
+                buf,
+                area.width - legend_len - 1,
+                controls_line_y,
+                small_files_legend,
+            );
+        }
+    }
 }
 
 impl<'a> Widget for BottomLine<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.multi_line {
+            self.render_multi_line(area, buf);
+            return;
+        }
         let small_files_legend = "(x = Small files)";
-        let small_files_len = if self.hide_small_files_legend {
+        let legend_len = if self.age_legend {
+            age_legend_len()
+        } else if self.hide_small_files_legend {
             0
         } else {
             small_files_legend.chars().count() as u16
         };
-        let max_status_len = area.width - small_files_len - 1;
+        let filter_indicator = self.extension_filter.map(extension_filter_indicator);
+        let filter_len = filter_indicator
+            .as_ref()
+            .map_or(0, |indicator| indicator.chars().count() as u16 + 1);
+        let max_status_len = (area.width - legend_len - 1).saturating_sub(filter_len);
         let max_controls_len = area.width - 1;
         let status_line_y = area.y + area.height - 2;
         let controls_line_y = status_line_y + 1;
-        if let Some(currently_selected) = self.currently_selected {
-            render_currently_selected(buf, currently_selected, max_status_len, status_line_y);
+        let selected_mime_type = self.selected_mime_type();
+        if let Some(input) = self.extension_filter_input {
+            render_text_input(
+                buf,
+                "Filter by extension: ",
+                input,
+                max_status_len,
+                status_line_y,
+            );
+        } else if let Some(input) = self.search_input {
+            render_text_input(buf, "Search: ", input, max_status_len, status_line_y);
+        } else if let Some(search_display) = &self.search_display {
+            render_search_results(buf, search_display, max_status_len, status_line_y);
+        } else if let Some(notice) = self.notice {
+            render_notice(buf, notice, max_status_len, status_line_y);
+        } else if let Some(copied_to_clipboard) = self.copied_to_clipboard {
+            render_copied_to_clipboard(buf, copied_to_clipboard, max_status_len, status_line_y);
+        } else if let Some(currently_selected) = self.currently_selected {
+            render_currently_selected(
+                buf,
+                currently_selected,
+                selected_mime_type.as_deref(),
+                max_status_len,
+                status_line_y,
+            );
+        } else if let Some(current_scan_dir) = self.current_scan_dir {
+            render_current_scan_dir(buf, current_scan_dir, max_status_len, status_line_y);
         } else if let Some(last_read_path) = self.last_read_path {
             render_last_read_path(buf, last_read_path, max_status_len, status_line_y);
         }
 
-        if !self.hide_small_files_legend {
+        if let Some(filter_indicator) = &filter_indicator
+            && area.width - legend_len - 1 > filter_len
+        {
+            // right before the legend, with a space to keep them apart
+            render_extension_filter(
+                buf,
+                filter_indicator,
+                area.width - legend_len - 1 - filter_len,
+                status_line_y,
+            );
+        }
+        if self.age_legend {
+            render_age_legend(buf, area.width - legend_len - 1, status_line_y);
+        } else if !self.hide_small_files_legend {
             render_small_files_legend(
                 buf,
-                area.width - small_files_len - 1,
+                area.width - legend_len - 1,
                 status_line_y,
                 small_files_legend,
             );
         }
 
-        render_controls_legend(buf, self.hide_delete, max_controls_len, controls_line_y);
+        if self.search_input.is_some() || self.extension_filter_input.is_some() {
+            render_search_controls_legend(buf, max_controls_len, controls_line_y);
+        } else if self.search_display.is_some() {
+            render_search_results_legend(buf, max_controls_len, controls_line_y);
+        } else {
+            let controls_len =
+                render_controls_legend(buf, self.hide_delete, max_controls_len, controls_line_y);
+            if self.clock && area.width >= controls_len + CLOCK_WIDTH {
+                render_clock(buf, area, controls_line_y);
+            }
+        }
     }
 }
@@ -0,0 +1,158 @@
+use std::time::{Duration, SystemTime};
+
+use tui::style::Color;
+
+use crate::state::tiles::{FileType, Tile};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+// from the most recently modified to the oldest, the last one is for anything older
+pub const AGE_COLORS: &[(Duration, &str, Color)] = &[
+    (DAY, "<1d", Color::LightGreen),
+    (Duration::from_secs(7 * 24 * 60 * 60), "<1w", Color::Green),
+    (Duration::from_secs(30 * 24 * 60 * 60), "<1m", Color::Yellow),
+    (
+        Duration::from_secs(182 * 24 * 60 * 60),
+        "<6m",
+        Color::Rgb(255, 165, 0),
+    ),
+    (Duration::from_secs(730 * 24 * 60 * 60), "<2y", Color::Red),
+    (Duration::MAX, "older", Color::Rgb(139, 0, 0)),
+];
+
+pub fn age_to_color(mtime: Option<SystemTime>, now: SystemTime) -> Color {
+    let mtime = match mtime {
+        Some(mtime) => mtime,
+        None => return Color::Reset,
+    };
+    // files from the future (eg. clock skew) count as brand new
+    let age = now.duration_since(mtime).unwrap_or_default();
+    AGE_COLORS
+        .iter()
+        .find(|(max_age, _, _)| age < *max_age)
+        .map(|(_, _, color)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+// archives are "application/..." as far as mime types go, but deserve a color of their own
+const ARCHIVE_SUBTYPES: &[&str] = &[
+    "zip",
+    "gzip",
+    "x-gzip",
+    "x-tar",
+    "x-gtar",
+    "x-bzip",
+    "x-bzip2",
+    "x-xz",
+    "zstd",
+    "x-7z-compressed",
+    "x-rar-compressed",
+    "vnd.rar",
+    "x-lzma",
+    "x-compress",
+    "java-archive",
+    "x-iso9660-image",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MimeCategory {
+    Video,
+    Audio,
+    Image,
+    Text,
+    Application,
+    Archive,
+    Unknown,
+}
+
+impl MimeCategory {
+    pub fn color(&self) -> Color {
+        // light colors, so that the black tile text stays readable on top of them
+        match self {
+            MimeCategory::Video => Color::LightMagenta,
+            MimeCategory::Audio => Color::LightCyan,
+            MimeCategory::Image => Color::LightGreen,
+            MimeCategory::Text => Color::LightBlue,
+            MimeCategory::Application => Color::LightYellow,
+            MimeCategory::Archive => Color::LightRed,
+            MimeCategory::Unknown => Color::Reset,
+        }
+    }
+}
+
+pub fn mime_type(tile: &Tile) -> Option<String> {
+    // guessed from the file name alone, the contents are never read
+    match tile.file_type {
+        FileType::File if tile.zero_byte_files.is_none() => mime_guess::from_path(&tile.name)
+            .first()
+            .map(|mime| mime.essence_str().to_string()),
+        _ => None,
+    }
+}
+
+pub fn mime_category(tile: &Tile) -> MimeCategory {
+    let mime_type = match mime_type(tile) {
+        Some(mime_type) => mime_type,
+        None => return MimeCategory::Unknown,
+    };
+    let (top_level_type, subtype) = mime_type.split_once('/').unwrap_or((&mime_type, ""));
+    match top_level_type {
+        "video" => MimeCategory::Video,
+        "audio" => MimeCategory::Audio,
+        "image" => MimeCategory::Image,
+        "text" => MimeCategory::Text,
+        "application" if ARCHIVE_SUBTYPES.contains(&subtype) => MimeCategory::Archive,
+        "application" => MimeCategory::Application,
+        _ => MimeCategory::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_tile(name: &str) -> Tile {
+        Tile::builder().name(name).file_type(FileType::File).build()
+    }
+
+    #[test]
+    fn mime_category_from_file_name() {
+        assert_eq!(mime_category(&file_tile("movie.mp4")), MimeCategory::Video);
+        assert_eq!(mime_category(&file_tile("song.FLAC")), MimeCategory::Audio);
+        assert_eq!(mime_category(&file_tile("photo.png")), MimeCategory::Image);
+        assert_eq!(mime_category(&file_tile("notes.txt")), MimeCategory::Text);
+        assert_eq!(
+            mime_category(&file_tile("manual.pdf")),
+            MimeCategory::Application
+        );
+        assert_eq!(
+            mime_category(&file_tile("backup.tar.gz")),
+            MimeCategory::Archive
+        );
+        assert_eq!(mime_category(&file_tile("Makefile")), MimeCategory::Unknown);
+    }
+
+    #[test]
+    fn age_colors() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let age = |days: u64| Some(now - DAY * days as u32);
+        assert_eq!(age_to_color(age(0), now), Color::LightGreen);
+        assert_eq!(age_to_color(age(3), now), Color::Green);
+        assert_eq!(age_to_color(age(10), now), Color::Yellow);
+        assert_eq!(age_to_color(age(100), now), Color::Rgb(255, 165, 0));
+        assert_eq!(age_to_color(age(365), now), Color::Red);
+        assert_eq!(age_to_color(age(3650), now), Color::Rgb(139, 0, 0));
+        assert_eq!(age_to_color(Some(now + DAY), now), Color::LightGreen);
+        assert_eq!(age_to_color(None, now), Color::Reset);
+    }
+
+    #[test]
+    fn folders_have_no_mime_category() {
+        let folder = Tile::builder()
+            .name("videos.mp4")
+            .file_type(FileType::Folder)
+            .build();
+        assert_eq!(mime_type(&folder), None);
+        assert_eq!(mime_category(&folder), MimeCategory::Unknown);
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::SystemTime};
 
 use tui::{
     Terminal,
@@ -8,14 +8,31 @@ use tui::{
 
 use crate::{
     UiMode,
-    state::{UiEffects, files::FileTree, tiles::Board},
+    app::ConfirmableAction,
+    state::{
+        ScanProgress, UiEffects,
+        files::FileTree,
+        tiles::{Board, Tile},
+    },
     ui::{
-        BottomLine, TermTooSmall,
+        BottomLine, HelpModalState, TermTooSmall,
         grid::RectangleGrid,
-        modals::{ConfirmBox, ErrorBox, MessageBox, WarningBox},
-        title::TitleLine,
+        modals::{
+            ConfirmBox, DuplicatesBox, ErrorBox, ErrorListBox, FileDetailsBox, InfoBox,
+            JumpToPathBox, LargestFilesBox, MessageBox, MostAccessedBox, WarningBox,
+            render_help_modal,
+        },
+        title::{SpinnerStyle, TitleLine},
     },
 };
+#[cfg(test)]
+use crate::{
+    state::files::DiffFileTree,
+    tests::{cases::test_utils::test_backend_factory, fakes::TestBackend},
+};
+
+// below this width, the legend and the selected file don't fit on the same line
+const MULTI_LINE_BOTTOM_WIDTH: u16 = 60;
 
 pub struct FolderInfo<'a> {
     pub path: &'a PathBuf,
@@ -23,11 +40,43 @@ pub struct FolderInfo<'a> {
     pub num_descendants: u64,
 }
 
+fn split_screen(full_screen: Rect) -> Vec<Rect> {
+    // the title line, the grid and the bottom line
+    let mut chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(0)
+        .constraints(
+            [
+                Constraint::Length(1),
+                Constraint::Min(10),
+                Constraint::Length(2),
+            ]
+            .as_ref(),
+        )
+        .split(full_screen);
+
+    // -1 cos we draw starting at offset 1 in both x and y directions
+
+    if chunks[1].width > 0 {
+        chunks[1].width -= 1;
+    }
+    if chunks[1].height > 0 {
+        chunks[1].height -= 1;
+    }
+    chunks
+}
+
 pub struct Display<B>
 where
     B: Backend,
 {
     terminal: Terminal<B>,
+    pub title_suffix: String,
+    pub remote: Option<(String, &'static str)>, // user@host we scan and how we logged in there
+    pub show_clock: bool,
+    pub read_only: bool,                // so there's no deleting to hint at
+    pub disk_space: Option<(u64, u64)>, // used and total, of the disk we scan
+    pub spinner_style: SpinnerStyle,
 }
 
 impl<B> Display<B>
@@ -38,7 +87,15 @@ where
         let mut terminal = Terminal::new(terminal_backend).expect("failed to create terminal");
         terminal.clear().expect("failed to clear terminal");
         terminal.hide_cursor().expect("failed to hide cursor");
-        Display { terminal }
+        Display {
+            terminal,
+            title_suffix: String::new(),
+            remote: None,
+            show_clock: false,
+            read_only: false,
+            disk_space: None,
+            spinner_style: SpinnerStyle::default(),
+        }
     }
     pub fn size(&self) -> Rect {
         self.terminal.size().expect("could not get terminal size")
@@ -49,13 +106,31 @@ where
         board: &mut Board,
         ui_mode: &UiMode,
         ui_effects: &UiEffects,
+        help_modal_state: &mut HelpModalState,
+        largest_files_scroll: usize,
+        scan_progress: &ScanProgress,
     ) {
+        let title_suffix = &self.title_suffix;
+        let show_clock = self.show_clock;
+        let read_only = self.read_only;
+        let disk_space = self.disk_space;
+        let spinner_style = self.spinner_style;
+        let remote = self
+            .remote
+            .as_ref()
+            .map(|(user_and_host, authenticated_with)| {
+                (user_and_host.as_str(), *authenticated_with)
+            });
         self.terminal
             .draw(|f| {
                 let full_screen = f.size();
                 let current_path = file_tree.get_current_path();
-                let current_path_size = file_tree.get_current_folder_size();
-                let current_path_descendants = file_tree.get_current_folder().num_descendants;
+                // with a filter in place, only what is shown counts
+                let (current_path_size, current_path_descendants) =
+                    board.filtered_stats().unwrap_or((
+                        file_tree.get_current_folder_size(),
+                        file_tree.get_current_folder().num_descendants,
+                    ));
                 let base_path_size = file_tree.get_total_size();
                 let base_path_descendants = file_tree.get_total_descendants();
                 let current_path_info = FolderInfo {
@@ -69,36 +144,41 @@ where
                     size: base_path_size,
                     num_descendants: base_path_descendants,
                 };
-                let mut chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints(
-                        [
-                            Constraint::Length(1),
-                            Constraint::Min(10),
-                            Constraint::Length(2),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(full_screen);
-
-                // -1 cos we draw starting at offset 1 in both x and y directions
-
-                chunks[1].width -= 1;
-                chunks[1].height -= 1;
-                board.change_area(&chunks[1]);
+                let multi_line_bottom = full_screen.width < MULTI_LINE_BOTTOM_WIDTH;
+                let chunks = split_screen(full_screen);
+                let ui_mode = if chunks[1].width == 0 || chunks[1].height == 0 {
+                    // there's no room to draw the grid, regardless of what
+                    // the terminal reports as its total size
+                    &UiMode::ScreenTooSmall
+                } else {
+                    board.change_area(&chunks[1]);
+                    ui_mode
+                };
                 match ui_mode {
                     UiMode::Loading => {
                         f.render_widget(
                             TitleLine::new(
                                 base_path_info,
                                 current_path_info,
-                                file_tree.space_freed,
+                                file_tree.get_space_freed(),
                             )
                             .progress_indicator(ui_effects.loading_progress_indicator)
                             .path_error(ui_effects.current_path_is_red)
+                            .path_highlight(ui_effects.path_highlight_active)
                             .read_errors(file_tree.failed_to_read)
-                            .zoom_level(board.zoom_level)
+                            .with_read_error_detail(&file_tree.failed_paths)
+                            .scan_timed_out(ui_effects.scan_timed_out)
+                            .hidden_files_excluded(file_tree.skip_hidden)
+                            .sort_order(board.sort_order)
+                            .disk_space(disk_space)
+                            .with_custom_right_section(title_suffix.as_str())
+                            .remote(remote)
+                            .zoom_level(board.zoom_level, board.max_zoom_level())
+                            .estimated_hidden_tiles(board.estimate_unrenderable_count(chunks[1]))
+                            .scan_progress(scan_progress)
+                            .scan_rate(ui_effects.scan_rate)
+                            .scan_eta(ui_effects.scan_eta)
+                            .spinner_style(spinner_style)
                             .show_loading(),
                             chunks[0],
                         );
@@ -107,31 +187,54 @@ where
                                 &board.tiles,
                                 board.unrenderable_tile_coordinates,
                                 board.selected_index,
-                            ),
+                            )
+                            .size_ranks(board.tile_ranks())
+                            .mime_colors(board.show_mime_category)
+                            .age_colors_at(board.color_by_age.then(SystemTime::now))
+                            .search_matches(board.matching_indices())
+                            .viewport(board.layout_area(), board.viewport_offset),
                             chunks[1],
                         );
                         f.render_widget(
                             BottomLine::new()
-                                .currently_selected(board.currently_selected())
+                                .multi_line(multi_line_bottom)
+                                .clock(show_clock)
+                                .copied_to_clipboard(ui_effects.copied_to_clipboard.as_ref())
+                                .notice(ui_effects.notice.as_ref())
+                                .currently_selected(board.tile_at_cursor())
+                                .show_mime_type(board.show_mime_category)
+                                .age_legend(board.color_by_age)
                                 .last_read_path(ui_effects.last_read_path.as_ref())
+                                .current_scan_dir(ui_effects.current_scan_dir.as_ref())
                                 .hide_delete()
-                                .hide_small_files_legend(
-                                    board.unrenderable_tile_coordinates.is_none(),
-                                ),
+                                .hide_small_files_legend(board.tiles_hidden_count() == 0),
                             chunks[2],
                         );
                     }
-                    UiMode::Normal => {
+                    UiMode::Normal
+                    | UiMode::ExtensionFilterInput(_)
+                    | UiMode::SearchInput(_)
+                    | UiMode::SearchResults { .. } => {
                         f.render_widget(
                             TitleLine::new(
                                 base_path_info,
                                 current_path_info,
-                                file_tree.space_freed,
+                                file_tree.get_space_freed(),
                             )
                             .path_error(ui_effects.current_path_is_red)
+                            .path_highlight(ui_effects.path_highlight_active)
                             .flash_space(ui_effects.flash_space_freed)
-                            .zoom_level(board.zoom_level)
-                            .read_errors(file_tree.failed_to_read),
+                            .restored(ui_effects.session_restored)
+                            .zoom_level(board.zoom_level, board.max_zoom_level())
+                            .tile_counts(board.tiles_visible_count(), board.tiles_hidden_count())
+                            .read_errors(file_tree.failed_to_read)
+                            .with_read_error_detail(&file_tree.failed_paths)
+                            .scan_timed_out(ui_effects.scan_timed_out)
+                            .hidden_files_excluded(file_tree.skip_hidden)
+                            .sort_order(board.sort_order)
+                            .disk_space(disk_space)
+                            .with_custom_right_section(title_suffix.as_str())
+                            .remote(remote),
                             chunks[0],
                         );
                         f.render_widget(
@@ -139,31 +242,76 @@ where
                                 &board.tiles,
                                 board.unrenderable_tile_coordinates,
                                 board.selected_index,
-                            ),
+                            )
+                            .size_ranks(board.tile_ranks())
+                            .mime_colors(board.show_mime_category)
+                            .age_colors_at(board.color_by_age.then(SystemTime::now))
+                            .search_matches(board.matching_indices())
+                            .viewport(board.layout_area(), board.viewport_offset),
                             chunks[1],
                         );
-                        f.render_widget(
-                            BottomLine::new()
-                                .currently_selected(board.currently_selected())
-                                .hide_small_files_legend(
-                                    board.unrenderable_tile_coordinates.is_none(),
-                                ),
-                            chunks[2],
-                        );
+                        let mut bottom_line = BottomLine::new()
+                            .multi_line(multi_line_bottom)
+                            .clock(show_clock)
+                            .read_only(read_only)
+                            .copied_to_clipboard(ui_effects.copied_to_clipboard.as_ref())
+                            .notice(ui_effects.notice.as_ref())
+                            .currently_selected(board.tile_at_cursor())
+                            .show_mime_type(board.show_mime_category)
+                            .age_legend(board.color_by_age)
+                            .extension_filter(file_tree.extension_filter())
+                            .extension_filter_input(match ui_mode {
+                                UiMode::ExtensionFilterInput(input) => Some(input),
+                                _ => None,
+                            })
+                            .search_input(match ui_mode {
+                                UiMode::SearchInput(input) => Some(input),
+                                _ => None,
+                            })
+                            .hide_small_files_legend(board.tiles_hidden_count() == 0);
+                        if let UiMode::SearchResults { query, matches } = ui_mode {
+                            // how many of the matches are somewhere in the current folder
+                            let current_path = file_tree.get_current_path();
+                            let matches_here = matches
+                                .iter()
+                                .filter(|path| {
+                                    path.starts_with(&current_path) && **path != current_path
+                                })
+                                .count();
+                            bottom_line = bottom_line.showing_search_results(
+                                query,
+                                matches_here,
+                                matches.len(),
+                            );
+                        }
+                        f.render_widget(bottom_line, chunks[2]);
+                        if let Some((message, _)) = &ui_effects.info_flash_message {
+                            f.render_widget(InfoBox::new(message), full_screen);
+                        }
                     }
                     UiMode::ScreenTooSmall => {
                         f.render_widget(TermTooSmall::new(), full_screen);
                     }
-                    UiMode::DeleteFile(file_to_delete) => {
+                    UiMode::ErrorMessage(message) => {
                         f.render_widget(
                             TitleLine::new(
                                 base_path_info,
                                 current_path_info,
-                                file_tree.space_freed,
+                                file_tree.get_space_freed(),
                             )
                             .path_error(ui_effects.current_path_is_red)
-                            .zoom_level(board.zoom_level)
-                            .read_errors(file_tree.failed_to_read),
+                            .path_highlight(ui_effects.path_highlight_active)
+                            .flash_space(ui_effects.flash_space_freed)
+                            .zoom_level(board.zoom_level, board.max_zoom_level())
+                            .tile_counts(board.tiles_visible_count(), board.tiles_hidden_count())
+                            .read_errors(file_tree.failed_to_read)
+                            .with_read_error_detail(&file_tree.failed_paths)
+                            .scan_timed_out(ui_effects.scan_timed_out)
+                            .hidden_files_excluded(file_tree.skip_hidden)
+                            .sort_order(board.sort_order)
+                            .disk_space(disk_space)
+                            .with_custom_right_section(title_suffix.as_str())
+                            .remote(remote),
                             chunks[0],
                         );
                         f.render_widget(
@@ -171,33 +319,55 @@ where
                                 &board.tiles,
                                 board.unrenderable_tile_coordinates,
                                 board.selected_index,
-                            ),
+                            )
+                            .size_ranks(board.tile_ranks())
+                            .mime_colors(board.show_mime_category)
+                            .age_colors_at(board.color_by_age.then(SystemTime::now))
+                            .search_matches(board.matching_indices())
+                            .viewport(board.layout_area(), board.viewport_offset),
                             chunks[1],
                         );
                         f.render_widget(
                             BottomLine::new()
-                                .currently_selected(board.currently_selected())
-                                .hide_small_files_legend(
-                                    board.unrenderable_tile_coordinates.is_none(),
-                                ),
+                                .multi_line(multi_line_bottom)
+                                .clock(show_clock)
+                                .read_only(read_only)
+                                .currently_selected(board.tile_at_cursor())
+                                .show_mime_type(board.show_mime_category)
+                                .age_legend(board.color_by_age)
+                                .hide_small_files_legend(board.tiles_hidden_count() == 0),
                             chunks[2],
                         );
-                        f.render_widget(
-                            MessageBox::new(file_to_delete, ui_effects.deletion_in_progress),
-                            full_screen,
-                        );
+                        f.render_widget(ErrorBox::new(message), full_screen);
                     }
-                    UiMode::ErrorMessage(message) => {
+                    UiMode::MostAccessed(_)
+                    | UiMode::LargestFiles(_)
+                    | UiMode::Duplicates(_)
+                    | UiMode::FileDetails(_)
+                    | UiMode::JumpToPath { .. }
+                    | UiMode::ErrorList
+                    | UiMode::Help
+                    | UiMode::DeleteConfirmationWarning
+                    | UiMode::ScanTimedOutWarning => {
                         f.render_widget(
                             TitleLine::new(
                                 base_path_info,
                                 current_path_info,
-                                file_tree.space_freed,
+                                file_tree.get_space_freed(),
                             )
                             .path_error(ui_effects.current_path_is_red)
+                            .path_highlight(ui_effects.path_highlight_active)
                             .flash_space(ui_effects.flash_space_freed)
-                            .zoom_level(board.zoom_level)
-                            .read_errors(file_tree.failed_to_read),
+                            .zoom_level(board.zoom_level, board.max_zoom_level())
+                            .tile_counts(board.tiles_visible_count(), board.tiles_hidden_count())
+                            .read_errors(file_tree.failed_to_read)
+                            .with_read_error_detail(&file_tree.failed_paths)
+                            .scan_timed_out(ui_effects.scan_timed_out)
+                            .hidden_files_excluded(file_tree.skip_hidden)
+                            .sort_order(board.sort_order)
+                            .disk_space(disk_space)
+                            .with_custom_right_section(title_suffix.as_str())
+                            .remote(remote),
                             chunks[0],
                         );
                         f.render_widget(
@@ -205,40 +375,103 @@ where
                                 &board.tiles,
                                 board.unrenderable_tile_coordinates,
                                 board.selected_index,
-                            ),
+                            )
+                            .size_ranks(board.tile_ranks())
+                            .mime_colors(board.show_mime_category)
+                            .age_colors_at(board.color_by_age.then(SystemTime::now))
+                            .search_matches(board.matching_indices())
+                            .viewport(board.layout_area(), board.viewport_offset),
                             chunks[1],
                         );
                         f.render_widget(
                             BottomLine::new()
-                                .currently_selected(board.currently_selected())
-                                .hide_small_files_legend(
-                                    board.unrenderable_tile_coordinates.is_none(),
-                                ),
+                                .multi_line(multi_line_bottom)
+                                .clock(show_clock)
+                                .read_only(read_only)
+                                .currently_selected(board.tile_at_cursor())
+                                .show_mime_type(board.show_mime_category)
+                                .age_legend(board.color_by_age)
+                                .hide_small_files_legend(board.tiles_hidden_count() == 0),
                             chunks[2],
                         );
-                        f.render_widget(ErrorBox::new(message), full_screen);
+                        match ui_mode {
+                            UiMode::MostAccessed(most_accessed) => {
+                                f.render_widget(MostAccessedBox::new(most_accessed), full_screen);
+                            }
+                            UiMode::LargestFiles(largest_files) => {
+                                f.render_widget(
+                                    LargestFilesBox::new(largest_files)
+                                        .scroll_offset(largest_files_scroll),
+                                    full_screen,
+                                );
+                            }
+                            UiMode::Duplicates(duplicates) => {
+                                f.render_widget(DuplicatesBox::new(duplicates), full_screen);
+                            }
+                            UiMode::FileDetails(details) => {
+                                f.render_widget(FileDetailsBox::new(details), full_screen);
+                            }
+                            UiMode::JumpToPath { input, .. } => {
+                                f.render_widget(JumpToPathBox::new(input), full_screen);
+                            }
+                            UiMode::ErrorList => {
+                                f.render_widget(
+                                    ErrorListBox::new(
+                                        &file_tree.failed_paths,
+                                        file_tree.failed_to_read,
+                                        &file_tree.path_in_filesystem,
+                                    ),
+                                    full_screen,
+                                );
+                            }
+                            UiMode::DeleteConfirmationWarning => {
+                                f.render_widget(
+                                    WarningBox::delete_confirmation_disabled(),
+                                    full_screen,
+                                );
+                            }
+                            UiMode::ScanTimedOutWarning => {
+                                f.render_widget(WarningBox::scan_timed_out(), full_screen);
+                            }
+                            _ => render_help_modal(f, full_screen, help_modal_state),
+                        }
                     }
-                    UiMode::Exiting { app_loaded } => {
-                        if *app_loaded {
+                    UiMode::Confirming { action } => {
+                        if !matches!(action, ConfirmableAction::Exit { app_loaded: false }) {
                             // render normal ui mode
                             f.render_widget(
                                 TitleLine::new(
                                     base_path_info,
                                     current_path_info,
-                                    file_tree.space_freed,
+                                    file_tree.get_space_freed(),
                                 )
                                 .path_error(ui_effects.current_path_is_red)
+                                .path_highlight(ui_effects.path_highlight_active)
                                 .flash_space(ui_effects.flash_space_freed)
-                                .zoom_level(board.zoom_level)
-                                .read_errors(file_tree.failed_to_read),
+                                .zoom_level(board.zoom_level, board.max_zoom_level())
+                                .tile_counts(
+                                    board.tiles_visible_count(),
+                                    board.tiles_hidden_count(),
+                                )
+                                .read_errors(file_tree.failed_to_read)
+                                .with_read_error_detail(&file_tree.failed_paths)
+                                .scan_timed_out(ui_effects.scan_timed_out)
+                                .hidden_files_excluded(file_tree.skip_hidden)
+                                .sort_order(board.sort_order)
+                                .disk_space(disk_space)
+                                .with_custom_right_section(title_suffix.as_str())
+                                .remote(remote),
                                 chunks[0],
                             );
                             f.render_widget(
                                 BottomLine::new()
-                                    .currently_selected(board.currently_selected())
-                                    .hide_small_files_legend(
-                                        board.unrenderable_tile_coordinates.is_none(),
-                                    ),
+                                    .multi_line(multi_line_bottom)
+                                    .clock(show_clock)
+                                    .read_only(read_only)
+                                    .currently_selected(board.tile_at_cursor())
+                                    .show_mime_type(board.show_mime_category)
+                                    .age_legend(board.color_by_age)
+                                    .hide_small_files_legend(board.tiles_hidden_count() == 0),
                                 chunks[2],
                             );
                         } else {
@@ -247,23 +480,42 @@ where
                                 TitleLine::new(
                                     base_path_info,
                                     current_path_info,
-                                    file_tree.space_freed,
+                                    file_tree.get_space_freed(),
                                 )
                                 .progress_indicator(ui_effects.loading_progress_indicator)
                                 .path_error(ui_effects.current_path_is_red)
-                                .zoom_level(board.zoom_level)
+                                .path_highlight(ui_effects.path_highlight_active)
+                                .zoom_level(board.zoom_level, board.max_zoom_level())
+                                .tile_counts(
+                                    board.tiles_visible_count(),
+                                    board.tiles_hidden_count(),
+                                )
                                 .read_errors(file_tree.failed_to_read)
+                                .with_read_error_detail(&file_tree.failed_paths)
+                                .scan_timed_out(ui_effects.scan_timed_out)
+                                .hidden_files_excluded(file_tree.skip_hidden)
+                                .sort_order(board.sort_order)
+                                .disk_space(disk_space)
+                                .with_custom_right_section(title_suffix.as_str())
+                                .remote(remote)
+                                .scan_progress(scan_progress)
+                                .scan_rate(ui_effects.scan_rate)
+                                .scan_eta(ui_effects.scan_eta)
+                                .spinner_style(spinner_style)
                                 .show_loading(),
                                 chunks[0],
                             );
                             f.render_widget(
                                 BottomLine::new()
-                                    .currently_selected(board.currently_selected())
+                                    .multi_line(multi_line_bottom)
+                                    .clock(show_clock)
+                                    .currently_selected(board.tile_at_cursor())
+                                    .show_mime_type(board.show_mime_category)
+                                    .age_legend(board.color_by_age)
                                     .last_read_path(ui_effects.last_read_path.as_ref())
+                                    .current_scan_dir(ui_effects.current_scan_dir.as_ref())
                                     .hide_delete()
-                                    .hide_small_files_legend(
-                                        board.unrenderable_tile_coordinates.is_none(),
-                                    ),
+                                    .hide_small_files_legend(board.tiles_hidden_count() == 0),
                                 chunks[2],
                             );
                         }
@@ -273,21 +525,53 @@ where
                                 &board.tiles,
                                 board.unrenderable_tile_coordinates,
                                 board.selected_index,
-                            ),
+                            )
+                            .size_ranks(board.tile_ranks())
+                            .mime_colors(board.show_mime_category)
+                            .age_colors_at(board.color_by_age.then(SystemTime::now))
+                            .search_matches(board.matching_indices())
+                            .viewport(board.layout_area(), board.viewport_offset),
                             chunks[1],
                         );
-                        f.render_widget(ConfirmBox::new(), full_screen);
+                        match action {
+                            ConfirmableAction::DeleteFile(file_to_delete) => {
+                                // this one has more to say about what is about to be deleted
+                                f.render_widget(
+                                    MessageBox::new(
+                                        file_to_delete,
+                                        ui_effects.deletion_in_progress,
+                                    )
+                                    .auto_confirm_secs(ui_effects.auto_confirm_countdown),
+                                    full_screen,
+                                );
+                            }
+                            ConfirmableAction::Exit { .. } => {
+                                f.render_widget(ConfirmBox::new(), full_screen);
+                            }
+                        }
                     }
                     UiMode::WarningMessage(_) => {
                         f.render_widget(
                             TitleLine::new(
                                 base_path_info,
                                 current_path_info,
-                                file_tree.space_freed,
+                                file_tree.get_space_freed(),
                             )
                             .progress_indicator(ui_effects.loading_progress_indicator)
                             .path_error(ui_effects.current_path_is_red)
+                            .path_highlight(ui_effects.path_highlight_active)
                             .read_errors(file_tree.failed_to_read)
+                            .with_read_error_detail(&file_tree.failed_paths)
+                            .scan_timed_out(ui_effects.scan_timed_out)
+                            .hidden_files_excluded(file_tree.skip_hidden)
+                            .sort_order(board.sort_order)
+                            .disk_space(disk_space)
+                            .with_custom_right_section(title_suffix.as_str())
+                            .remote(remote)
+                            .scan_progress(scan_progress)
+                            .scan_rate(ui_effects.scan_rate)
+                            .scan_eta(ui_effects.scan_eta)
+                            .spinner_style(spinner_style)
                             .show_loading(),
                             chunks[0],
                         );
@@ -296,17 +580,25 @@ where
                                 &board.tiles,
                                 board.unrenderable_tile_coordinates,
                                 board.selected_index,
-                            ),
+                            )
+                            .size_ranks(board.tile_ranks())
+                            .mime_colors(board.show_mime_category)
+                            .age_colors_at(board.color_by_age.then(SystemTime::now))
+                            .search_matches(board.matching_indices())
+                            .viewport(board.layout_area(), board.viewport_offset),
                             chunks[1],
                         );
                         f.render_widget(
                             BottomLine::new()
-                                .currently_selected(board.currently_selected())
+                                .multi_line(multi_line_bottom)
+                                .clock(show_clock)
+                                .currently_selected(board.tile_at_cursor())
+                                .show_mime_type(board.show_mime_category)
+                                .age_legend(board.color_by_age)
                                 .last_read_path(ui_effects.last_read_path.as_ref())
+                                .current_scan_dir(ui_effects.current_scan_dir.as_ref())
                                 .hide_delete()
-                                .hide_small_files_legend(
-                                    board.unrenderable_tile_coordinates.is_none(),
-                                ),
+                                .hide_small_files_legend(board.tiles_hidden_count() == 0),
                             chunks[2],
                         );
                         f.render_widget(WarningBox::new(), full_screen);
@@ -315,8 +607,305 @@ where
             })
             .expect("failed to draw");
     }
+    // nothing compares two scans outside of the tests yet
+    #[cfg(test)]
+    pub fn render_diff(
+        &mut self,
+        diff_tree: &mut DiffFileTree<'_>,
+        board: &mut Board,
+        ui_mode: &UiMode,
+        ui_effects: &UiEffects,
+    ) {
+        // the changes between two scans of the same folder, laid out like the
+        // current folder is in render but with tiles colored by how they changed
+        let title_suffix = &self.title_suffix;
+        let show_clock = self.show_clock;
+        self.terminal
+            .draw(|f| {
+                let full_screen = f.size();
+                let current_path = diff_tree.get_current_path();
+                let after = diff_tree.after;
+                let current_path_info = FolderInfo {
+                    path: &current_path,
+                    size: after.size_at_path(&current_path).unwrap_or(0),
+                    num_descendants: after.descendant_count_at_path(&current_path).unwrap_or(0),
+                };
+                let base_path_info = FolderInfo {
+                    path: &after.path_in_filesystem,
+                    size: after.get_total_size(),
+                    num_descendants: after.get_total_descendants(),
+                };
+                let multi_line_bottom = full_screen.width < MULTI_LINE_BOTTOM_WIDTH;
+                let chunks = split_screen(full_screen);
+                if chunks[1].width == 0
+                    || chunks[1].height == 0
+                    || matches!(ui_mode, UiMode::ScreenTooSmall)
+                {
+                    f.render_widget(TermTooSmall::new(), full_screen);
+                    return;
+                }
+                diff_tree.layout_algorithm = board.layout_algorithm;
+                diff_tree.change_area(&chunks[1]);
+                let diff_tiles = diff_tree.get_diff_tiles(&current_path);
+                let tiles: Vec<Tile> = diff_tiles
+                    .iter()
+                    .map(|diff_tile| diff_tile.tile.clone())
+                    .collect();
+                // the diff has tiles of its own, so the selection might be past them
+                let selected_index = board.selected_index.filter(|index| *index < tiles.len());
+                f.render_widget(
+                    TitleLine::new(base_path_info, current_path_info, 0)
+                        .diff(
+                            diff_tree.before_scanned_at,
+                            diff_tree.after_scanned_at,
+                            diff_tree.net_size_change(),
+                        )
+                        .path_error(ui_effects.current_path_is_red)
+                        .path_highlight(ui_effects.path_highlight_active)
+                        .with_custom_right_section(title_suffix.as_str()),
+                    chunks[0],
+                );
+                f.render_widget(
+                    RectangleGrid::new(&tiles, None, selected_index).diff_tiles(Some(&diff_tiles)),
+                    chunks[1],
+                );
+                f.render_widget(
+                    BottomLine::new()
+                        .multi_line(multi_line_bottom)
+                        .clock(show_clock)
+                        .notice(ui_effects.notice.as_ref())
+                        .currently_selected(selected_index.map(|index| &tiles[index]))
+                        .hide_delete()
+                        .hide_small_files_legend(true),
+                    chunks[2],
+                );
+                if let UiMode::ErrorMessage(message) = ui_mode {
+                    f.render_widget(ErrorBox::new(message), full_screen);
+                } else if let Some((message, _)) = &ui_effects.info_flash_message {
+                    f.render_widget(InfoBox::new(message), full_screen);
+                }
+            })
+            .expect("failed to draw");
+    }
     pub fn clear(&mut self) {
         self.terminal.clear().expect("failed to clear terminal");
         self.terminal.show_cursor().expect("failed to show cursor");
     }
+    pub fn resume(&mut self) {
+        // after something else drew on the terminal, so that the next render draws everything
+        self.terminal.clear().expect("failed to clear terminal");
+        self.terminal.hide_cursor().expect("failed to hide cursor");
+    }
+}
+
+#[cfg(test)]
+const RENDER_TO_STRING_WIDTH: u16 = 190;
+#[cfg(test)]
+const RENDER_TO_STRING_HEIGHT: u16 = 50;
+
+#[cfg(test)]
+impl Display<TestBackend> {
+    pub fn render_to_string(
+        file_tree: &mut FileTree,
+        board: &mut Board,
+        ui_mode: &UiMode,
+        ui_effects: &UiEffects,
+    ) -> String {
+        // the whole screen as drawn the first time, one line per row and
+        // without any style, rather than only what changed since the last draw
+        let (_, draw_events, backend) =
+            test_backend_factory(RENDER_TO_STRING_WIDTH, RENDER_TO_STRING_HEIGHT);
+        let mut display = Display::new(backend);
+        display.render(
+            file_tree,
+            board,
+            ui_mode,
+            ui_effects,
+            &mut HelpModalState::default(),
+            0,
+            &ScanProgress::default(),
+        );
+        let draw_events = draw_events.lock().unwrap();
+        let screen = draw_events.last().expect("nothing was drawn");
+        screen.trim_end_matches('\n').to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        time::{Duration, Instant},
+    };
+
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::state::{PATH_HIGHLIGHT_DURATION, files::Folder};
+
+    fn file_tree_and_board() -> (FileTree, Board) {
+        let path_in_filesystem = PathBuf::from("/tmp/diskonaut_display_test");
+        let mut base_folder = Folder::new(&path_in_filesystem);
+        base_folder.add_file(PathBuf::from("bigfile.tar.gz"), 8192);
+        base_folder.add_file(PathBuf::from("notes.txt"), 4096);
+        let mut board = Board::new(&base_folder);
+        board.change_files(&base_folder, &path_in_filesystem);
+        let file_tree = FileTree::new(base_folder, path_in_filesystem, false);
+        (file_tree, board)
+    }
+
+    #[test]
+    fn render_whole_screen_to_string() {
+        let (mut file_tree, mut board) = file_tree_and_board();
+        let rendered = Display::render_to_string(
+            &mut file_tree,
+            &mut board,
+            &UiMode::Normal,
+            &UiEffects::new(),
+        );
+        assert_eq!(rendered.lines().count(), RENDER_TO_STRING_HEIGHT as usize);
+        assert!(rendered.contains("Total: 12.0K (2 files), freed: 0"));
+        assert!(rendered.contains("bigfile.tar.gz"));
+        assert!(!rendered.contains("SELECTED:"));
+
+        board.set_selected_index(&0);
+        let rendered = Display::render_to_string(
+            &mut file_tree,
+            &mut board,
+            &UiMode::Normal,
+            &UiEffects::new(),
+        );
+        assert!(rendered.contains("SELECTED: bigfile.tar.gz (8.0K)"));
+    }
+
+    #[test]
+    fn render_filtered_folder_to_string() {
+        let (mut file_tree, mut board) = file_tree_and_board();
+        let base_folder = file_tree.get_current_folder().clone();
+        board.apply_filter(&base_folder, |path, _| {
+            path.extension() == Some(Path::new("txt").as_os_str())
+        });
+        let rendered = Display::render_to_string(
+            &mut file_tree,
+            &mut board,
+            &UiMode::Normal,
+            &UiEffects::new(),
+        );
+        assert!(rendered.contains("notes.txt"));
+        assert!(!rendered.contains("bigfile.tar.gz"));
+        assert!(rendered.contains("4.0K (100%)"));
+    }
+
+    #[test]
+    fn render_without_room_for_the_grid() {
+        // App::render shows anything this small as too small before it gets here,
+        // but the grid's area still mustn't underflow if it's drawn on anyway
+        for width in [0, 1] {
+            let (mut file_tree, mut board) = file_tree_and_board();
+            let (_, draw_events, backend) = test_backend_factory(width, 20);
+            let mut display = Display::new(backend);
+            display.render(
+                &mut file_tree,
+                &mut board,
+                &UiMode::Normal,
+                &UiEffects::new(),
+                &mut HelpModalState::default(),
+                0,
+                &ScanProgress::default(),
+            );
+            let draw_events = draw_events.lock().unwrap();
+            let rendered = draw_events.last().expect("nothing was drawn");
+            assert!(
+                rendered.chars().all(char::is_whitespace),
+                "only the too small screen is drawn, and it has no room for text"
+            );
+        }
+    }
+
+    #[test]
+    fn render_highlighted_path() {
+        let (mut file_tree, mut board) = file_tree_and_board();
+        let (_, draw_events, backend) =
+            test_backend_factory(RENDER_TO_STRING_WIDTH, RENDER_TO_STRING_HEIGHT);
+        let mut display = Display::new(backend);
+        let mut ui_effects = UiEffects::new();
+        ui_effects.path_highlight_duration = PATH_HIGHLIGHT_DURATION;
+        let mut render = |display: &mut Display<TestBackend>, ui_effects: &UiEffects| {
+            display.render(
+                &mut file_tree,
+                &mut board,
+                &UiMode::Normal,
+                ui_effects,
+                &mut HelpModalState::default(),
+                0,
+                &ScanProgress::default(),
+            )
+        };
+        render(&mut display, &ui_effects);
+        ui_effects.highlight_path();
+        render(&mut display, &ui_effects);
+        ui_effects.path_highlight_end = Some(Instant::now() - Duration::from_millis(1));
+        ui_effects.clear_expired();
+        render(&mut display, &ui_effects);
+
+        // only what changed is drawn, so this is the path alone: once to highlight it,
+        // and once more to take the highlight away
+        let draw_events = draw_events.lock().unwrap();
+        assert_eq!(draw_events.len(), 3);
+        assert_snapshot!(&draw_events[1]);
+        assert_eq!(draw_events[2], draw_events[1]);
+    }
+
+    #[test]
+    fn render_diff_of_two_scans() {
+        let (before, mut board) = file_tree_and_board();
+        let path_in_filesystem = before.path_in_filesystem.clone();
+        let mut base_folder = Folder::new(&path_in_filesystem);
+        base_folder.add_file(PathBuf::from("bigfile.tar.gz"), 8192);
+        base_folder.add_file(PathBuf::from("video.mp4"), 16384);
+        let after = FileTree::new(base_folder, path_in_filesystem, false);
+        let scanned_at = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let mut diff_tree =
+            DiffFileTree::new(&before, &after, scanned_at(86400), scanned_at(86400 * 32));
+        let (_, draw_events, backend) =
+            test_backend_factory(RENDER_TO_STRING_WIDTH, RENDER_TO_STRING_HEIGHT);
+        let mut display = Display::new(backend);
+        display.render_diff(
+            &mut diff_tree,
+            &mut board,
+            &UiMode::Normal,
+            &UiEffects::new(),
+        );
+        let draw_events = draw_events.lock().unwrap();
+        let rendered = draw_events.last().expect("nothing was drawn");
+        assert!(rendered.contains("Δ 1970-01-"));
+        assert!(rendered.contains("(+12.0K)"));
+        assert!(rendered.contains("video.mp4"));
+        assert!(
+            rendered.contains("notes.txt"),
+            "removed files are still shown"
+        );
+    }
+
+    #[test]
+    fn render_disk_space() {
+        let (mut file_tree, mut board) = file_tree_and_board();
+        let (_, draw_events, backend) =
+            test_backend_factory(RENDER_TO_STRING_WIDTH, RENDER_TO_STRING_HEIGHT);
+        let mut display = Display::new(backend);
+        let gigabyte = 1024 * 1024 * 1024;
+        display.disk_space = Some((234 * gigabyte, 512 * gigabyte));
+        display.render(
+            &mut file_tree,
+            &mut board,
+            &UiMode::Normal,
+            &UiEffects::new(),
+            &mut HelpModalState::default(),
+            0,
+            &ScanProgress::default(),
+        );
+        let draw_events = draw_events.lock().unwrap();
+        let rendered = draw_events.last().expect("nothing was drawn");
+        assert!(rendered.contains("(Disk: 234.0G / 512.0G (46%))"));
+    }
 }
@@ -0,0 +1,32 @@
+use std::fmt;
+
+pub struct DisplayCount(pub u64);
+
+impl fmt::Display for DisplayCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // with thousands separators, eg. 12,345
+        let digits = self.0.to_string();
+        let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                formatted.push(',');
+            }
+            formatted.push(digit);
+        }
+        write!(f, "{}", formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_have_thousands_separators() {
+        assert_eq!(DisplayCount(0).to_string(), "0");
+        assert_eq!(DisplayCount(999).to_string(), "999");
+        assert_eq!(DisplayCount(1000).to_string(), "1,000");
+        assert_eq!(DisplayCount(12345).to_string(), "12,345");
+        assert_eq!(DisplayCount(1234567).to_string(), "1,234,567");
+    }
+}
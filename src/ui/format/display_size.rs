@@ -4,7 +4,9 @@ pub struct DisplaySize(pub f64);
 
 impl fmt::Display for DisplaySize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 > 999_999_999.0 {
+        if self.0 > 999_999_999_999.0 {
+            write!(f, "{:.1}T", self.0 / 1099511627776.0) // 1024 * 1024 * 1024 * 1024
+        } else if self.0 > 999_999_999.0 {
             write!(f, "{:.1}G", self.0 / 1073741824.0) // 1024 * 1024 * 1024
         } else if self.0 > 999_999.0 {
             write!(f, "{:.1}M", self.0 / 1048576.0) //  1024 * 1024
@@ -20,7 +22,9 @@ pub struct DisplaySizeRounded(pub f64);
 
 impl fmt::Display for DisplaySizeRounded {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 > 999_999_999.0 {
+        if self.0 > 999_999_999_999.0 {
+            write!(f, "{:.0}T", self.0 / 1099511627776.0) // 1024 * 1024 * 1024 * 1024
+        } else if self.0 > 999_999_999.0 {
             write!(f, "{:.0}G", self.0 / 1073741824.0) // 1024 * 1024 * 1024
         } else if self.0 > 999_999.0 {
             write!(f, "{:.0}M", self.0 / 1048576.0) //  1024 * 1024
@@ -31,3 +35,52 @@ impl fmt::Display for DisplaySizeRounded {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display_size(size: f64) -> String {
+        DisplaySize(size).to_string()
+    }
+
+    #[test]
+    fn bytes_are_shown_as_they_are() {
+        assert_eq!(display_size(0.0), "0");
+        assert_eq!(display_size(1.0), "1");
+        assert_eq!(display_size(999.0), "999");
+        // sizes are never negative, so these are not treated any differently
+        assert_eq!(display_size(-1.0), "-1");
+    }
+
+    #[test]
+    fn units_change_past_three_digits() {
+        // units are powers of 1024, but we move to the next one after 999
+        // so that sizes never take up more than three digits before the point
+        assert_eq!(display_size(1000.0), "1.0K");
+        assert_eq!(display_size(1023.0), "1.0K");
+        assert_eq!(display_size(1024.0), "1.0K");
+        assert_eq!(display_size(1025.0), "1.0K");
+        assert_eq!(display_size(1536.0), "1.5K");
+        assert_eq!(display_size(999_999.0), "976.6K");
+        assert_eq!(display_size(999.0 * 1024.0), "1.0M");
+        assert_eq!(display_size(1000.0 * 1024.0), "1.0M");
+        assert_eq!(display_size(1024.0 * 1024.0), "1.0M");
+        assert_eq!(display_size(1024.0 * 1024.0 * 1024.0), "1.0G");
+        assert_eq!(display_size(1024.0 * 1024.0 * 1024.0 * 1024.0), "1.0T");
+        assert_eq!(
+            display_size(u128::MAX as f64),
+            format!("{:.1}T", u128::MAX as f64 / 1099511627776.0)
+        );
+    }
+
+    #[test]
+    fn rounded_sizes_have_no_fraction() {
+        assert_eq!(DisplaySizeRounded(999.0).to_string(), "999");
+        assert_eq!(DisplaySizeRounded(1536.0).to_string(), "2K");
+        assert_eq!(
+            DisplaySizeRounded(1024.0 * 1024.0 * 1024.0 * 1024.0).to_string(),
+            "1T"
+        );
+    }
+}
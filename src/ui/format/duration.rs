@@ -0,0 +1,125 @@
+use std::time::{Duration, SystemTime};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const MONTH: u64 = 30 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+pub fn format_duration(d: Duration) -> String {
+    let seconds = d.as_secs();
+    if seconds < 1 {
+        String::from("< 1s")
+    } else if seconds < MINUTE {
+        format!("{}s", seconds)
+    } else if seconds < HOUR {
+        format!("{}m {}s", seconds / MINUTE, seconds % MINUTE)
+    } else if seconds < DAY {
+        format!("{}h {}m", seconds / HOUR, seconds % HOUR / MINUTE)
+    } else {
+        format!("{}d {}h", seconds / DAY, seconds % DAY / HOUR)
+    }
+}
+
+pub fn format_duration_precise(d: Duration) -> String {
+    let seconds = d.as_secs();
+    let seconds_with_millis = format!("{}.{:03}s", seconds % MINUTE, d.subsec_millis());
+    if seconds < MINUTE {
+        seconds_with_millis
+    } else if seconds < HOUR {
+        format!("{}m {}", seconds / MINUTE, seconds_with_millis)
+    } else {
+        format!(
+            "{}h {}m {}",
+            seconds / HOUR,
+            seconds % HOUR / MINUTE,
+            seconds_with_millis
+        )
+    }
+}
+
+pub fn format_age(t: SystemTime) -> String {
+    format_age_at(t, SystemTime::now())
+}
+
+fn format_age_at(t: SystemTime, now: SystemTime) -> String {
+    // a modification time in the future (eg. clock skew) is treated as "now"
+    let seconds = now.duration_since(t).unwrap_or_default().as_secs();
+    let (count, unit) = if seconds < MINUTE {
+        return String::from("just now");
+    } else if seconds < HOUR {
+        (seconds / MINUTE, "minute")
+    } else if seconds < DAY {
+        (seconds / HOUR, "hour")
+    } else if seconds < MONTH {
+        (seconds / DAY, "day")
+    } else if seconds < YEAR {
+        (seconds / MONTH, "month")
+    } else {
+        (seconds / YEAR, "year")
+    };
+    if count == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_duration_boundaries() {
+        assert_eq!(format_duration(Duration::from_millis(999)), "< 1s");
+        assert_eq!(format_duration(Duration::from_secs(1)), "1s");
+        assert_eq!(format_duration(Duration::from_secs(59)), "59s");
+        assert_eq!(format_duration(Duration::from_secs(60)), "1m 0s");
+        assert_eq!(format_duration(Duration::from_secs(330)), "5m 30s");
+        assert_eq!(format_duration(Duration::from_secs(3599)), "59m 59s");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1h 0m");
+        assert_eq!(format_duration(Duration::from_secs(8100)), "2h 15m");
+        assert_eq!(format_duration(Duration::from_secs(86399)), "23h 59m");
+        assert_eq!(format_duration(Duration::from_secs(86400)), "1d 0h");
+        assert_eq!(format_duration(Duration::from_secs(277_200)), "3d 5h");
+    }
+
+    #[test]
+    fn format_duration_precise_boundaries() {
+        assert_eq!(format_duration_precise(Duration::from_millis(0)), "0.000s");
+        assert_eq!(
+            format_duration_precise(Duration::from_millis(59_999)),
+            "59.999s"
+        );
+        assert_eq!(
+            format_duration_precise(Duration::from_secs(60)),
+            "1m 0.000s"
+        );
+        assert_eq!(
+            format_duration_precise(Duration::from_millis(222_543)),
+            "3m 42.543s"
+        );
+        assert_eq!(
+            format_duration_precise(Duration::from_secs(3600)),
+            "1h 0m 0.000s"
+        );
+    }
+
+    #[test]
+    fn format_age_boundaries() {
+        let now = SystemTime::now();
+        let ago = |seconds| now - Duration::from_secs(seconds);
+        assert_eq!(
+            format_age_at(now + Duration::from_secs(10), now),
+            "just now"
+        );
+        assert_eq!(format_age_at(ago(59), now), "just now");
+        assert_eq!(format_age_at(ago(MINUTE), now), "1 minute ago");
+        assert_eq!(format_age_at(ago(2 * HOUR), now), "2 hours ago");
+        assert_eq!(format_age_at(ago(3 * DAY), now), "3 days ago");
+        assert_eq!(format_age_at(ago(MONTH - 1), now), "29 days ago");
+        assert_eq!(format_age_at(ago(2 * MONTH), now), "2 months ago");
+        assert_eq!(format_age_at(ago(YEAR), now), "1 year ago");
+        assert_eq!(format_age_at(ago(3 * YEAR), now), "3 years ago");
+    }
+}
@@ -1,5 +1,9 @@
+pub use count::*;
 pub use display_size::*;
+pub use duration::*;
 pub use truncate::*;
 
+mod count;
 mod display_size;
+mod duration;
 mod truncate;
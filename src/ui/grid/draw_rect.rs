@@ -19,6 +19,8 @@ fn tile_first_line(tile: &Tile) -> String {
     let descendant_count = &tile.descendants;
     let filename_text = match tile.file_type {
         FileType::File => format!("{}", name),
+        // there's more in it than we scanned
+        FileType::Folder if tile.unexpanded => format!("{}/…", name),
         FileType::Folder => format!("{}/", name),
     };
     match tile.file_type {
@@ -66,6 +68,12 @@ fn tile_second_line(tile: &Tile) -> String {
 }
 
 pub fn tile_style(tile: &Tile, selected: bool) -> (Option<Style>, Style, Style) {
+    if tile.zero_byte_files.is_some() && !selected {
+        let style = Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC);
+        return (None, style, style);
+    }
     let (background_style, first_line_style, second_line_style) = match (selected, &tile.file_type)
     {
         (true, FileType::File) => (
@@ -1,13 +1,20 @@
+use std::{collections::HashSet, time::SystemTime};
+
 use tui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     widgets::Widget,
 };
 
+#[cfg(test)]
+use crate::state::files::{ChangeKind, DiffTile};
 use crate::{
     state::tiles::Tile,
-    ui::grid::{draw_rect_on_grid, draw_tile_text_on_grid},
+    ui::{
+        color_rules::{MimeCategory, age_to_color, mime_category},
+        grid::{draw_rect_on_grid, draw_tile_text_on_grid},
+    },
 };
 
 fn draw_small_files_rect_on_grid(buf: &mut Buffer, rect: Rect) {
@@ -43,11 +50,82 @@ fn draw_empty_folder(buf: &mut Buffer, area: Rect) {
     draw_rect_on_grid(buf, (area.x, area.y), (area.width, area.height));
 }
 
+fn gradient_color(rank: usize, total: usize) -> Color {
+    // the largest tile is bright red, smaller ones fade towards white
+    let intensity = (255.0 * (rank as f64 / total as f64).powf(0.5)) as u8;
+    Color::Rgb(255, intensity, intensity)
+}
+
+fn draw_tile_background_on_grid(buf: &mut Buffer, tile: &Tile, color: Color) {
+    for x in tile.x + 1..tile.x + tile.width {
+        for y in tile.y + 1..tile.y + tile.height {
+            buf.get_mut(x, y)
+                .set_symbol(" ")
+                .set_style(Style::default().bg(color).fg(Color::Black));
+        }
+    }
+}
+
+fn recolor_tile_on_grid(buf: &mut Buffer, tile: &Tile, color: Color) {
+    // the border and the text take the color, the background stays as it is
+    let buffer_area = buf.area;
+    for x in tile.x..=tile.x + tile.width {
+        for y in tile.y..=tile.y + tile.height {
+            if x < buffer_area.x + buffer_area.width && y < buffer_area.y + buffer_area.height {
+                buf.get_mut(x, y).set_fg(color);
+            }
+        }
+    }
+}
+
+fn dim_tile_on_grid(buf: &mut Buffer, tile: &Tile, selected: bool) {
+    // the selected tile keeps its border, so that it can still be told apart
+    let border = if selected { 1 } else { 0 };
+    let buffer_area = buf.area;
+    for x in tile.x + border..=tile.x + tile.width - border {
+        for y in tile.y + border..=tile.y + tile.height - border {
+            if x < buffer_area.x + buffer_area.width && y < buffer_area.y + buffer_area.height {
+                buf.get_mut(x, y)
+                    .set_style(Style::default().add_modifier(Modifier::DIM));
+            }
+        }
+    }
+}
+
+fn draw_hardlink_badge_on_grid(buf: &mut Buffer, tile: &Tile) {
+    // files with more than one link to them are counted once in each place they
+    // appear, so we mark them on the top right corner of their border
+    if tile.width < 4 {
+        return;
+    }
+    buf.get_mut(tile.x + tile.width - 2, tile.y)
+        .set_symbol("H")
+        .set_style(Style::default().fg(Color::Yellow));
+}
+
+#[cfg(test)]
+fn change_color(change: ChangeKind) -> Option<Color> {
+    match change {
+        ChangeKind::Added => Some(Color::Green),
+        ChangeKind::Removed => Some(Color::Red),
+        ChangeKind::Grown(_) => Some(Color::Yellow),
+        ChangeKind::Shrunk(_) => Some(Color::Cyan),
+        ChangeKind::Unchanged => None,
+    }
+}
+
 #[derive(Clone)]
 pub struct RectangleGrid<'a> {
     rectangles: &'a [Tile],
     small_files_coordinates: Option<(u16, u16)>,
     selected_rect_index: Option<usize>,
+    size_ranks: Option<&'a [usize]>,
+    mime_colors: bool,
+    age_colors_at: Option<SystemTime>,
+    search_matches: Option<&'a HashSet<usize>>,
+    viewport: Option<(Rect, (u16, u16))>,
+    #[cfg(test)]
+    diff_tiles: Option<&'a [DiffTile]>,
 }
 
 impl<'a> RectangleGrid<'a> {
@@ -60,12 +138,65 @@ impl<'a> RectangleGrid<'a> {
             rectangles,
             small_files_coordinates,
             selected_rect_index,
+            size_ranks: None,
+            mime_colors: false,
+            age_colors_at: None,
+            search_matches: None,
+            viewport: None,
+            #[cfg(test)]
+            diff_tiles: None,
         }
     }
-}
-
-impl<'a> Widget for RectangleGrid<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    pub fn size_ranks(mut self, size_ranks: Option<&'a [usize]>) -> Self {
+        // when given, tiles get a background color according to their size rank
+        self.size_ranks = size_ranks;
+        self
+    }
+    pub fn age_colors_at(mut self, now: Option<SystemTime>) -> Self {
+        // when given, the border and text of tiles are colored by how long ago
+        // they were modified, as of this moment
+        self.age_colors_at = now;
+        self
+    }
+    pub fn mime_colors(mut self, mime_colors: bool) -> Self {
+        // files get a background color according to their kind (video, image, ...),
+        // this takes precedence over the size rank
+        self.mime_colors = mime_colors;
+        self
+    }
+    pub fn search_matches(mut self, search_matches: Option<&'a HashSet<usize>>) -> Self {
+        // when given, tiles that are not in it are dimmed
+        self.search_matches = search_matches;
+        self
+    }
+    pub fn viewport(mut self, layout_area: Rect, viewport_offset: (u16, u16)) -> Self {
+        // when the tiles were laid out on a larger area than we render to (see
+        // Board::scroll_mode), only the part of it at this offset is shown
+        self.viewport = Some((layout_area, viewport_offset));
+        self
+    }
+    // only the tests compare two scans so far, see Display::render_diff
+    #[cfg(test)]
+    pub fn diff_tiles(mut self, diff_tiles: Option<&'a [DiffTile]>) -> Self {
+        // when comparing two scans, tiles get a background color according to how
+        // they changed (one per rectangle, in the same order) instead of their kind
+        self.diff_tiles = diff_tiles;
+        self
+    }
+    #[cfg(test)]
+    fn diff_color(&self, index: usize) -> Option<Option<Color>> {
+        // None unless comparing two scans, Some(None) for tiles that didn't change
+        self.diff_tiles.map(|diff_tiles| {
+            diff_tiles
+                .get(index)
+                .and_then(|diff_tile| change_color(diff_tile.change))
+        })
+    }
+    #[cfg(not(test))]
+    fn diff_color(&self, _index: usize) -> Option<Option<Color>> {
+        None
+    }
+    fn draw(&self, area: Rect, buf: &mut Buffer) {
         if self.rectangles.is_empty() {
             draw_empty_folder(buf, area);
         } else {
@@ -75,8 +206,49 @@ impl<'a> Widget for RectangleGrid<'a> {
                 } else {
                     false
                 };
+                let mime_category = if self.mime_colors {
+                    mime_category(tile)
+                } else {
+                    MimeCategory::Unknown
+                };
+                if let Some(diff_color) = self.diff_color(index) {
+                    if let Some(color) = diff_color
+                        && !selected
+                    {
+                        draw_tile_background_on_grid(buf, tile, color);
+                    }
+                } else if mime_category != MimeCategory::Unknown && !selected {
+                    draw_tile_background_on_grid(buf, tile, mime_category.color());
+                } else if let Some(size_ranks) = self.size_ranks
+                    && !selected
+                    && let Some(rank) = size_ranks.get(index)
+                {
+                    draw_tile_background_on_grid(
+                        buf,
+                        tile,
+                        gradient_color(*rank, self.rectangles.len()),
+                    );
+                }
                 draw_tile_text_on_grid(buf, &tile, selected);
                 draw_rect_on_grid(buf, (tile.x, tile.y), (tile.width, tile.height));
+                if let Some(now) = self.age_colors_at
+                    && !selected
+                    && tile.modified.is_some()
+                {
+                    recolor_tile_on_grid(buf, tile, age_to_color(tile.modified, now));
+                }
+                if tile.mount_point && !selected {
+                    // another file system is mounted here, which we did not scan
+                    recolor_tile_on_grid(buf, tile, Color::DarkGray);
+                }
+                if tile.is_hardlinked() {
+                    draw_hardlink_badge_on_grid(buf, tile);
+                }
+                if let Some(search_matches) = self.search_matches
+                    && !search_matches.contains(&index)
+                {
+                    dim_tile_on_grid(buf, tile, selected);
+                }
             }
         }
         if let Some(coords) = self.small_files_coordinates {
@@ -93,3 +265,222 @@ impl<'a> Widget for RectangleGrid<'a> {
         }
     }
 }
+
+impl<'a> Widget for RectangleGrid<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some((layout_area, (offset_x, offset_y))) = self.viewport else {
+            self.draw(area, buf);
+            return;
+        };
+        if layout_area == area {
+            // everything fits on screen, there's nothing to scroll
+            self.draw(area, buf);
+            return;
+        }
+        // like on screen, the right and bottom borders are drawn one cell past the area
+        let mut layout_buf = Buffer::empty(Rect {
+            width: layout_area.width + 1,
+            height: layout_area.height + 1,
+            ..layout_area
+        });
+        self.draw(layout_area, &mut layout_buf);
+        let buffer_area = buf.area;
+        for x in area.x..=area.x + area.width {
+            for y in area.y..=area.y + area.height {
+                let (layout_x, layout_y) = (x + offset_x, y + offset_y);
+                if x < buffer_area.x + buffer_area.width
+                    && y < buffer_area.y + buffer_area.height
+                    && layout_x <= layout_area.x + layout_area.width
+                    && layout_y <= layout_area.y + layout_area.height
+                {
+                    *buf.get_mut(x, y) = layout_buf.get(layout_x, layout_y).clone();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::tiles::FileType;
+
+    #[test]
+    fn gradient_color_fades_with_rank() {
+        assert_eq!(gradient_color(0, 4), Color::Rgb(255, 0, 0));
+        assert_eq!(gradient_color(1, 4), Color::Rgb(255, 127, 127));
+        assert_eq!(gradient_color(3, 4), Color::Rgb(255, 220, 220));
+    }
+
+    #[test]
+    fn hardlinked_files_get_a_badge() {
+        let area = Rect::new(0, 0, 20, 5);
+        let tiles = [
+            Tile::builder()
+                .name("linked")
+                .width(10)
+                .height(4)
+                .num_hardlinks(2)
+                .build(),
+            Tile::builder()
+                .name("single")
+                .x(10)
+                .width(9)
+                .height(4)
+                .num_hardlinks(1)
+                .build(),
+        ];
+        let mut buf = Buffer::empty(area);
+        RectangleGrid::new(&tiles, None, None).render(area, &mut buf);
+        assert_eq!(buf.get(8, 0).symbol, "H");
+        assert_ne!(buf.get(17, 0).symbol, "H");
+    }
+
+    #[test]
+    fn unexpanded_folders_are_marked() {
+        let area = Rect::new(0, 0, 40, 8);
+        let tiles = [Tile::builder()
+            .name("deep")
+            .file_type(FileType::Folder)
+            .unexpanded()
+            .width(39)
+            .height(7)
+            .build()];
+        let mut buf = Buffer::empty(area);
+        RectangleGrid::new(&tiles, None, None).render(area, &mut buf);
+        let text: String = buf
+            .content
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect();
+        assert!(text.contains("deep/… (+0 descendants)"));
+    }
+
+    #[test]
+    fn mount_points_are_grayed_out() {
+        let area = Rect::new(0, 0, 20, 5);
+        let tiles = [
+            Tile::builder()
+                .name("mnt")
+                .file_type(FileType::Folder)
+                .mount_point()
+                .width(10)
+                .height(4)
+                .build(),
+            Tile::builder()
+                .name("home")
+                .file_type(FileType::Folder)
+                .x(10)
+                .width(9)
+                .height(4)
+                .build(),
+        ];
+        let mut buf = Buffer::empty(area);
+        RectangleGrid::new(&tiles, None, None).render(area, &mut buf);
+        assert_eq!(buf.get(0, 0).fg, Color::DarkGray);
+        assert_ne!(buf.get(19, 0).fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn tiles_that_dont_match_are_dimmed() {
+        let area = Rect::new(0, 0, 30, 5);
+        let tiles = [
+            Tile::builder().name("match").width(10).height(4).build(),
+            Tile::builder()
+                .name("other")
+                .x(10)
+                .width(9)
+                .height(4)
+                .build(),
+            Tile::builder()
+                .name("selected")
+                .x(19)
+                .width(10)
+                .height(4)
+                .build(),
+        ];
+        let search_matches = HashSet::from([0]);
+        let mut buf = Buffer::empty(area);
+        RectangleGrid::new(&tiles, None, Some(2))
+            .search_matches(Some(&search_matches))
+            .render(area, &mut buf);
+        let dimmed = |x, y| buf.get(x, y).modifier.contains(Modifier::DIM);
+        assert!(!dimmed(0, 0));
+        assert!(!dimmed(5, 2));
+        assert!(dimmed(14, 0));
+        assert!(dimmed(14, 2));
+        assert!(!dimmed(24, 0), "the selected tile's border stays as it is");
+        assert!(dimmed(24, 2));
+    }
+
+    #[test]
+    fn changed_tiles_are_colored_by_how_they_changed() {
+        let area = Rect::new(0, 0, 30, 5);
+        let tiles = [
+            Tile::builder().name("added").width(10).height(4).build(),
+            Tile::builder()
+                .name("removed")
+                .x(10)
+                .width(10)
+                .height(4)
+                .build(),
+            Tile::builder()
+                .name("unchanged")
+                .x(20)
+                .width(9)
+                .height(4)
+                .build(),
+        ];
+        let diff_tiles: Vec<DiffTile> = tiles
+            .iter()
+            .zip([
+                ChangeKind::Added,
+                ChangeKind::Removed,
+                ChangeKind::Unchanged,
+            ])
+            .map(|(tile, change)| DiffTile {
+                tile: tile.clone(),
+                change,
+            })
+            .collect();
+        let mut buf = Buffer::empty(area);
+        RectangleGrid::new(&tiles, None, None)
+            .mime_colors(true)
+            .diff_tiles(Some(&diff_tiles))
+            .render(area, &mut buf);
+        assert_eq!(buf.get(5, 3).bg, Color::Green);
+        assert_eq!(buf.get(15, 3).bg, Color::Red);
+        assert_eq!(buf.get(25, 3).bg, Color::Reset);
+    }
+
+    #[test]
+    fn only_the_scrolled_to_part_is_shown() {
+        let layout_area = Rect::new(0, 0, 20, 12);
+        let area = Rect::new(0, 0, 20, 6);
+        let tiles = [
+            Tile::builder().name("upper").width(20).height(6).build(),
+            Tile::builder()
+                .name("lower")
+                .y(6)
+                .width(20)
+                .height(6)
+                .build(),
+        ];
+        let rendered_text = |viewport_offset| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 21, 7));
+            RectangleGrid::new(&tiles, None, None)
+                .viewport(layout_area, viewport_offset)
+                .render(area, &mut buf);
+            buf.content
+                .iter()
+                .map(|cell| cell.symbol.as_str())
+                .collect::<String>()
+        };
+        let top = rendered_text((0, 0));
+        assert!(top.contains("upper"));
+        assert!(!top.contains("lower"));
+        let bottom = rendered_text((0, 6));
+        assert!(!bottom.contains("upper"));
+        assert!(bottom.contains("lower"));
+    }
+}
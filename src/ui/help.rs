@@ -0,0 +1,33 @@
+// what each key does, as shown in the help modal ('?')
+pub const KEYBINDINGS: &[(&str, &str)] = &[
+    ("<arrows>/hjkl", "move between tiles"),
+    ("ctrl-b/f/n/p", "move left/right/down/up"),
+    ("<ENTER>", "enter the selected folder"),
+    ("<ESC>", "go back to the parent folder"),
+    ("~/ctrl-home", "go back to the scanned folder"),
+    ("<BACKSPACE>", "delete the selected file or folder"),
+    ("+", "zoom in (show smaller files)"),
+    ("-", "zoom out"),
+    ("0", "reset the zoom"),
+    ("A", "cycle through the tile layouts"),
+    ("o", "order tiles by size, name or item count"),
+    ("s", "scroll through all tiles instead of zooming"),
+    ("Z", "gather zero-byte files into one tile"),
+    ("H", "show or hide hidden files and rescan"),
+    ("r", "scan the current folder again"),
+    ("M", "show the most selected files and folders"),
+    ("L", "show the largest files anywhere"),
+    ("D", "find files with the same contents"),
+    ("E", "show the files that failed to read"),
+    ("c", "count the items in the current folder"),
+    ("i", "show details of the selected file"),
+    ("f", "show only files with an extension"),
+    ("/", "search for a name (then n/N)"),
+    ("g", "go to a path (<TAB> completes)"),
+    ("v", "view the selected file with $PAGER"),
+    ("y", "copy the selected path (with --clipboard)"),
+    ("Y", "copy the selected name (with --clipboard)"),
+    ("y/n", "answer a question (eg. before deleting)"),
+    ("?", "show this help"),
+    ("q/ctrl-c", "quit"),
+];
@@ -0,0 +1,155 @@
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextInput {
+    buf: String,
+    cursor: usize, // in characters, not bytes
+    max_len: Option<usize>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        TextInput::default()
+    }
+    pub fn with_max_len(max_len: usize) -> Self {
+        TextInput {
+            max_len: Some(max_len),
+            ..TextInput::new()
+        }
+    }
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.buf
+            .char_indices()
+            .nth(char_index)
+            .map(|(byte_index, _)| byte_index)
+            .unwrap_or(self.buf.len())
+    }
+    fn len(&self) -> usize {
+        self.buf.chars().count()
+    }
+    pub fn push_char(&mut self, c: char) {
+        if let Some(max_len) = self.max_len
+            && self.len() >= max_len
+        {
+            return;
+        }
+        let byte_index = self.byte_index(self.cursor);
+        self.buf.insert(byte_index, c);
+        self.cursor += 1;
+    }
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let byte_index = self.byte_index(self.cursor);
+            self.buf.remove(byte_index);
+        }
+    }
+    pub fn delete(&mut self) {
+        if self.cursor < self.len() {
+            let byte_index = self.byte_index(self.cursor);
+            self.buf.remove(byte_index);
+        }
+    }
+    pub fn move_cursor_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+    pub fn move_cursor_right(&mut self) {
+        if self.cursor < self.len() {
+            self.cursor += 1;
+        }
+    }
+    pub fn set_text(&mut self, text: &str) {
+        // replacing whatever was there, with the cursor at the end
+        self.clear();
+        for c in text.chars() {
+            self.push_char(c);
+        }
+    }
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.cursor = 0;
+    }
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl Widget for &TextInput {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        // leave room for the cursor at the end of the text and
+        // scroll the text so that the cursor is always visible
+        let width = area.width as usize;
+        let first_visible = (self.cursor + 1).saturating_sub(width);
+        let visible_text: String = self.buf.chars().skip(first_visible).take(width).collect();
+        buf.set_string(area.x, area.y, visible_text, Style::default());
+        let cursor_x = area.x + (self.cursor - first_visible) as u16;
+        let cursor_cell = buf.get_mut(cursor_x, area.y);
+        if self.cursor == self.len() {
+            cursor_cell.set_symbol(" ");
+        }
+        cursor_cell.set_style(Style::default().add_modifier(Modifier::REVERSED));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_input(text: &str) -> TextInput {
+        let mut text_input = TextInput::new();
+        for c in text.chars() {
+            text_input.push_char(c);
+        }
+        text_input
+    }
+
+    #[test]
+    fn editing_in_the_middle() {
+        let mut text_input = text_input("fo");
+        text_input.move_cursor_left();
+        text_input.push_char('ö');
+        assert_eq!(text_input.as_str(), "föo");
+        text_input.delete();
+        assert_eq!(text_input.as_str(), "fö");
+        text_input.backspace();
+        text_input.backspace();
+        text_input.backspace();
+        assert_eq!(text_input.as_str(), "");
+        text_input.move_cursor_right();
+        text_input.push_char('x');
+        assert_eq!(text_input.as_str(), "x");
+        text_input.clear();
+        assert_eq!(text_input.as_str(), "");
+    }
+
+    #[test]
+    fn max_len_is_respected() {
+        let mut text_input = TextInput::with_max_len(3);
+        for c in "abcdef".chars() {
+            text_input.push_char(c);
+        }
+        assert_eq!(text_input.as_str(), "abc");
+    }
+
+    #[test]
+    fn render_scrolls_to_cursor() {
+        let text_input = text_input("abcdefgh");
+        let area = Rect::new(0, 0, 5, 1);
+        let mut buf = Buffer::empty(area);
+        text_input.render(area, &mut buf);
+        let rendered: String = buf
+            .content
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect();
+        assert_eq!(rendered, "efgh ");
+    }
+}
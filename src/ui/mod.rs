@@ -1,11 +1,16 @@
 pub use bottom_line::*;
 pub use display::*;
+pub use modals::{HELP_PAGE_SIZE, HelpModalState, LargestFilesBox};
 pub use term_too_small::*;
+pub use title::SpinnerStyle;
 
 mod bottom_line;
+mod color_rules;
 mod display;
-mod format;
+pub mod format;
 mod grid;
+mod help;
+pub mod input_widget;
 mod modals;
 mod term_too_small;
 mod title;
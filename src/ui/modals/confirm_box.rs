@@ -7,33 +7,30 @@ use tui::{
 
 use crate::ui::{format::truncate_middle, grid::draw_filled_rect};
 
+const QUIT_TEXTS: [&str; 4] = [
+    "Are you sure you want to quit?",
+    "Sure you want to quit?",
+    "Really quit?",
+    "Quit?",
+];
+
 fn render_confirm_prompt(buf: &mut Buffer, confirm_rect: &Rect) {
     let text_style = Style::default()
         .bg(Color::Black)
         .fg(Color::White)
         .add_modifier(Modifier::BOLD);
 
-    let possible_confirm_texts = [
-        "Are you sure you want to quit?",
-        "Sure you want to quit?",
-        "Really quit?",
-        "Quit?",
-    ];
-    // set default value of the confirm_text
-    // to the longest one from possible_confirm_text array
-    let mut confirm_text = String::from(possible_confirm_texts[0]);
-    let mut confirm_text_start_position: u16 = 0;
+    // from longest to shortest, we show the first one that fits
     let text_max_length = confirm_rect.width - 4;
-    for line in possible_confirm_texts.iter() {
+    let confirm_text = QUIT_TEXTS
+        .iter()
         // "+10" here is to make sure confirm message has always some padding
-        if confirm_rect.width >= (line.chars().count() as u16) + 10 {
-            confirm_text = truncate_middle(line, text_max_length);
-            confirm_text_start_position =
-                ((confirm_rect.width - confirm_text.len() as u16) as f64 / 2.0).ceil() as u16
-                    + confirm_rect.x;
-            break;
-        }
-    }
+        .find(|line| confirm_rect.width >= (line.chars().count() as u16) + 10)
+        .unwrap_or(&QUIT_TEXTS[QUIT_TEXTS.len() - 1]);
+    let confirm_text = truncate_middle(confirm_text, text_max_length);
+    let confirm_text_start_position =
+        ((confirm_rect.width - confirm_text.chars().count() as u16) as f64 / 2.0).ceil() as u16
+            + confirm_rect.x;
 
     let y_n_line = "(y/n)";
     let y_n_line_start_position =
@@ -61,7 +58,7 @@ impl ConfirmBox {
     }
 }
 
-impl<'a> Widget for ConfirmBox {
+impl Widget for ConfirmBox {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let (width, height) = if area.width > 150 {
             (150, 10)
@@ -91,3 +88,24 @@ impl<'a> Widget for ConfirmBox {
         render_confirm_prompt(buf, &confirm_rect);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered_text(confirm_box: ConfirmBox, width: u16) -> String {
+        let area = Rect::new(0, 0, width, 20);
+        let mut buf = Buffer::empty(area);
+        confirm_box.render(area, &mut buf);
+        buf.content
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn quit_message_gets_shorter_on_narrow_screens() {
+        assert!(rendered_text(ConfirmBox::new(), 100).contains("Are you sure you want to quit?"));
+        assert!(rendered_text(ConfirmBox::new(), 50).contains("Really quit?"));
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::ui::{format::truncate_middle, grid::draw_filled_rect};
+
+pub struct DuplicatesBox<'a> {
+    duplicates: &'a [Vec<PathBuf>],
+}
+
+impl<'a> DuplicatesBox<'a> {
+    pub fn new(duplicates: &'a [Vec<PathBuf>]) -> Self {
+        Self { duplicates }
+    }
+}
+
+impl<'a> Widget for DuplicatesBox<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // every group is numbered on its first line, its other files are lined up below
+        let number_width = self.duplicates.len().to_string().len();
+        let lines: Vec<(String, String)> = self
+            .duplicates
+            .iter()
+            .enumerate()
+            .flat_map(|(index, group)| {
+                group.iter().enumerate().map(move |(index_in_group, path)| {
+                    let number = if index_in_group == 0 {
+                        format!("{:>number_width$}. ", index + 1)
+                    } else {
+                        " ".repeat(number_width + 2)
+                    };
+                    (number, path.to_string_lossy().into_owned())
+                })
+            })
+            .collect();
+
+        // title, list (or a line saying it's empty), controls and some margins
+        let height = (lines.len().max(1) as u16 + 6).min(area.height - 1);
+        let max_lines = (height - 6) as usize;
+        let width = if area.width > 150 {
+            150
+        } else if area.width >= 50 {
+            area.width / 2
+        } else {
+            unreachable!("app should not be rendered if window is so small")
+        };
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2).saturating_sub(height / 2);
+
+        let message_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default()
+            .bg(Color::Black)
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let text_max_length = message_rect.width - 4;
+        draw_filled_rect(buf, fill_style, &message_rect);
+
+        let title = "Duplicate files";
+        buf.set_string(
+            message_rect.x + (message_rect.width - title.len() as u16) / 2,
+            message_rect.y + 1,
+            title,
+            fill_style,
+        );
+        if lines.is_empty() {
+            let empty_line = "No duplicate files were found";
+            buf.set_string(
+                message_rect.x + 2,
+                message_rect.y + 3,
+                truncate_middle(empty_line, text_max_length),
+                fill_style,
+            );
+        }
+        for (index, (number, path)) in lines.iter().take(max_lines).enumerate() {
+            let max_path_length = text_max_length.saturating_sub(number.len() as u16);
+            #[cfg(test)]
+            let path = str::replace(path, "\\", "/");
+            let line = format!("{}{}", number, truncate_middle(&path, max_path_length));
+            buf.set_string(
+                message_rect.x + 2,
+                message_rect.y + 3 + index as u16,
+                line,
+                fill_style,
+            );
+        }
+
+        let controls_text = ["(Press <ESC> to dismiss)", "(<ESC> to dismiss)"];
+        for line in controls_text.iter() {
+            if text_max_length >= line.len() as u16 {
+                buf.set_string(
+                    message_rect.x + (message_rect.width - line.len() as u16) / 2,
+                    message_rect.y + message_rect.height - 1,
+                    line,
+                    fill_style,
+                );
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered_lines(duplicates: &[Vec<PathBuf>]) -> Vec<String> {
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        DuplicatesBox::new(duplicates).render(area, &mut buf);
+        buf.content
+            .chunks(area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn groups_are_numbered_once() {
+        let duplicates = vec![
+            vec![PathBuf::from("a/large"), PathBuf::from("b/large")],
+            vec![
+                PathBuf::from("small"),
+                PathBuf::from("small_copy"),
+                PathBuf::from("a/small"),
+            ],
+        ];
+        let lines = rendered_lines(&duplicates);
+        let start = lines
+            .iter()
+            .position(|line| line.contains("1. a/large"))
+            .expect("first group was not rendered");
+        assert!(lines[start + 1].contains("   b/large"));
+        assert!(lines[start + 2].contains("2. small"));
+        assert!(lines[start + 3].contains("   small_copy"));
+        assert!(lines[start + 4].contains("   a/small"));
+    }
+
+    #[test]
+    fn nothing_found() {
+        let lines = rendered_lines(&[]);
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains("No duplicate files were found"))
+        );
+    }
+}
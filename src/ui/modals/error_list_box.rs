@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::ui::{format::truncate_middle, grid::draw_filled_rect};
+
+pub struct ErrorListBox<'a> {
+    failed_paths: &'a [(PathBuf, String)],
+    failed_to_read: u64, // we might not know all of their paths
+    base_path: &'a Path,
+}
+
+impl<'a> ErrorListBox<'a> {
+    pub fn new(
+        failed_paths: &'a [(PathBuf, String)],
+        failed_to_read: u64,
+        base_path: &'a Path,
+    ) -> Self {
+        Self {
+            failed_paths,
+            failed_to_read,
+            base_path,
+        }
+    }
+}
+
+impl<'a> Widget for ErrorListBox<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // title, list (or a line saying we don't know which), controls and some margins
+        let height = (self.failed_paths.len().max(1) as u16 + 6).min(area.height - 1);
+        let max_lines = (height - 6) as usize;
+        let width = if area.width > 150 {
+            150
+        } else if area.width >= 50 {
+            area.width / 2
+        } else {
+            unreachable!("app should not be rendered if window is so small")
+        };
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2).saturating_sub(height / 2);
+
+        let message_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default()
+            .bg(Color::Black)
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let text_max_length = message_rect.width - 4;
+        draw_filled_rect(buf, fill_style, &message_rect);
+
+        let title = truncate_middle(
+            &format!("Failed to read {} files", self.failed_to_read),
+            text_max_length,
+        );
+        buf.set_string(
+            message_rect.x + (message_rect.width - title.len() as u16) / 2,
+            message_rect.y + 1,
+            &title,
+            fill_style,
+        );
+        if self.failed_paths.is_empty() {
+            let empty_line = "We weren't told which ones";
+            buf.set_string(
+                message_rect.x + 2,
+                message_rect.y + 3,
+                truncate_middle(empty_line, text_max_length),
+                fill_style,
+            );
+        }
+        for (index, (path, error)) in self.failed_paths.iter().take(max_lines).enumerate() {
+            let path = path.strip_prefix(self.base_path).unwrap_or(path);
+            let path = path.to_string_lossy();
+            #[cfg(test)]
+            let path = str::replace(&path, "\\", "/");
+            let line = format!("{}: {}", path, error);
+            buf.set_string(
+                message_rect.x + 2,
+                message_rect.y + 3 + index as u16,
+                truncate_middle(&line, text_max_length),
+                fill_style,
+            );
+        }
+
+        let controls_text = ["(Press <ESC> to dismiss)", "(<ESC> to dismiss)"];
+        for line in controls_text.iter() {
+            if text_max_length >= line.len() as u16 {
+                buf.set_string(
+                    message_rect.x + (message_rect.width - line.len() as u16) / 2,
+                    message_rect.y + message_rect.height - 1,
+                    line,
+                    fill_style,
+                );
+                break;
+            }
+        }
+    }
+}
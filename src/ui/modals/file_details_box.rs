@@ -0,0 +1,204 @@
+use std::time::SystemTime;
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::{
+    state::FileDetails,
+    ui::{
+        format::{DisplaySize, format_age, truncate_middle},
+        grid::draw_filled_rect,
+    },
+};
+
+const FILE_DETAILS_BOX_WIDTH: u16 = 70;
+const LABEL_COLUMN_WIDTH: u16 = 13;
+
+pub struct FileDetailsBox<'a> {
+    details: &'a FileDetails,
+}
+
+impl<'a> FileDetailsBox<'a> {
+    pub fn new(details: &'a FileDetails) -> Self {
+        Self { details }
+    }
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        // whatever the platform or file system doesn't tell us is left out
+        let details = self.details;
+        let path = details.path.to_string_lossy().into_owned();
+        #[cfg(test)]
+        let path = str::replace(&path, "\\", "/");
+        let mut rows = vec![("Path", path)];
+        if let Some((apparent_size, size_on_disk)) = details.size {
+            rows.push((
+                "Size",
+                format!(
+                    "{} ({} bytes), {} on disk",
+                    DisplaySize(apparent_size as f64),
+                    apparent_size,
+                    DisplaySize(size_on_disk as f64)
+                ),
+            ));
+        }
+        let optional_rows = [
+            ("Permissions", details.permissions.clone()),
+            ("Owner", details.owner.clone()),
+            ("Group", details.group.clone()),
+            ("Inode", details.inode.map(|inode| inode.to_string())),
+            (
+                "Hard links",
+                details.hard_links.map(|links| links.to_string()),
+            ),
+            (
+                "Modified",
+                details
+                    .modified
+                    .map(|time| format!("{} ({})", format_time(time), format_age(time))),
+            ),
+            ("Accessed", details.accessed.map(format_time)),
+            ("Created", details.created.map(format_time)),
+        ];
+        rows.extend(
+            optional_rows
+                .into_iter()
+                .filter_map(|(label, value)| value.map(|value| (label, value))),
+        );
+        rows
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Local>::from(time)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+impl<'a> Widget for FileDetailsBox<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self.rows();
+        // title, rows, controls and some margins
+        let height = (rows.len() as u16 + 5).min(area.height - 1);
+        let max_rows = height.saturating_sub(5) as usize;
+        let width = FILE_DETAILS_BOX_WIDTH.min(area.width - 2);
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2).saturating_sub(height / 2);
+        let details_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default().bg(Color::Black).fg(Color::White);
+        let label_style = fill_style.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+        let text_max_length = details_rect.width - 4;
+        draw_filled_rect(buf, fill_style, &details_rect);
+
+        let title = if self.details.is_dir {
+            "Folder details"
+        } else {
+            "File details"
+        };
+        buf.set_string(
+            details_rect.x + (details_rect.width - title.len() as u16) / 2,
+            details_rect.y + 1,
+            title,
+            fill_style.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        );
+        let value_max_length = text_max_length.saturating_sub(LABEL_COLUMN_WIDTH);
+        for (index, (label, value)) in rows.iter().take(max_rows).enumerate() {
+            let line_y = details_rect.y + 3 + index as u16;
+            buf.set_string(details_rect.x + 2, line_y, label, label_style);
+            buf.set_string(
+                details_rect.x + 2 + LABEL_COLUMN_WIDTH,
+                line_y,
+                truncate_middle(value, value_max_length),
+                fill_style,
+            );
+        }
+
+        let controls_text = ["(Press <ESC> to dismiss)", "(<ESC> to dismiss)"];
+        for line in controls_text.iter() {
+            if text_max_length >= line.len() as u16 {
+                buf.set_string(
+                    details_rect.x + (details_rect.width - line.len() as u16) / 2,
+                    details_rect.y + details_rect.height - 1,
+                    line,
+                    fill_style.add_modifier(Modifier::BOLD),
+                );
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn unknown_details_are_left_out() {
+        let details = FileDetails {
+            path: PathBuf::from("folder/file.txt"),
+            is_dir: false,
+            size: Some((4096, 8192)),
+            permissions: Some(String::from("rw-r--r--")),
+            owner: None,
+            group: None,
+            inode: Some(1234),
+            hard_links: Some(2),
+            modified: None,
+            accessed: None,
+            created: None,
+        };
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        FileDetailsBox::new(&details).render(area, &mut buf);
+        let lines: Vec<String> = buf
+            .content
+            .chunks(area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect())
+            .collect();
+        let row = |label: &str| lines.iter().find(|line| line.contains(label)).cloned();
+
+        assert!(row("File details").is_some());
+        assert!(row("folder/file.txt").is_some());
+        assert!(
+            row("Size")
+                .unwrap()
+                .contains("4.0K (4096 bytes), 8.0K on disk")
+        );
+        assert!(row("Permissions").unwrap().contains("rw-r--r--"));
+        assert!(row("Inode").unwrap().contains("1234"));
+        assert!(row("Hard links").unwrap().contains("2"));
+        assert!(row("Owner").is_none());
+        assert!(row("Modified").is_none());
+    }
+
+    #[test]
+    fn modified_time_shows_its_age() {
+        let details = FileDetails {
+            path: PathBuf::from("file.txt"),
+            is_dir: false,
+            size: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            inode: None,
+            hard_links: None,
+            modified: Some(SystemTime::now() - std::time::Duration::from_secs(3 * 24 * 60 * 60)),
+            accessed: None,
+            created: None,
+        };
+        let rows = FileDetailsBox::new(&details).rows();
+        let (_, modified) = rows.iter().find(|(label, _)| *label == "Modified").unwrap();
+        assert!(modified.ends_with(" (3 days ago)"));
+    }
+}
@@ -0,0 +1,148 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Row, StatefulWidget, Table, Widget},
+};
+
+use crate::ui::{format::truncate_middle, grid::draw_filled_rect, help::KEYBINDINGS};
+
+const HELP_MODAL_WIDTH: u16 = 60;
+const HELP_MODAL_HEIGHT_PERCENT: u16 = 80;
+const COLUMN_SPACING: u16 = 2;
+pub const HELP_PAGE_SIZE: usize = 10;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HelpModalState {
+    pub scroll_offset: usize, // index of the first keybinding shown
+}
+
+impl HelpModalState {
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+    }
+    pub fn scroll_down(&mut self, lines: usize) {
+        // this is narrowed down further once we know how many lines fit on screen
+        self.scroll_offset = (self.scroll_offset + lines).min(KEYBINDINGS.len().saturating_sub(1));
+    }
+}
+
+pub fn render_help_modal<B: Backend>(f: &mut Frame<B>, area: Rect, state: &mut HelpModalState) {
+    f.render_stateful_widget(HelpModal, area, state);
+}
+
+pub struct HelpModal;
+
+impl StatefulWidget for HelpModal {
+    type State = HelpModalState;
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut HelpModalState) {
+        let width = HELP_MODAL_WIDTH.min(area.width - 2);
+        // borders, title, table header, footer and a margin below the title and above the footer
+        let height = (area.height * HELP_MODAL_HEIGHT_PERCENT / 100).max(7);
+        let visible_lines = (height - 6) as usize;
+        let max_scroll_offset = KEYBINDINGS.len().saturating_sub(visible_lines);
+        state.scroll_offset = state.scroll_offset.min(max_scroll_offset);
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2).saturating_sub(height / 2);
+        let help_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default().bg(Color::Black).fg(Color::White);
+        let text_max_length = help_rect.width - 4;
+        draw_filled_rect(buf, fill_style, &help_rect);
+
+        let title = "Diskonaut Keybindings";
+        buf.set_string(
+            help_rect.x + (help_rect.width - title.len() as u16) / 2,
+            help_rect.y + 1,
+            title,
+            fill_style.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        );
+
+        let key_column_width = KEYBINDINGS
+            .iter()
+            .map(|(key, _)| key.chars().count() as u16)
+            .max()
+            .unwrap_or(0);
+        let action_column_width = text_max_length.saturating_sub(key_column_width + COLUMN_SPACING);
+        let widths = [
+            Constraint::Length(key_column_width),
+            Constraint::Length(action_column_width),
+        ];
+        let rows = KEYBINDINGS
+            .iter()
+            .skip(state.scroll_offset)
+            .take(visible_lines)
+            .map(|(key, action)| {
+                Row::Data(
+                    vec![
+                        key.to_string(),
+                        truncate_middle(action, action_column_width),
+                    ]
+                    .into_iter(),
+                )
+            });
+        // the table keeps two columns free for the borders of a block, which we draw
+        // ourselves, so it gets the room up to our right border
+        let table_rect = Rect {
+            x: help_rect.x + 2,
+            y: help_rect.y + 3,
+            width: text_max_length + 2,
+            height: visible_lines as u16 + 1,
+        };
+        let header = ["Key", "Action"];
+        let table = Table::new(header.iter(), rows)
+            .header_style(fill_style.fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .header_gap(0)
+            .widths(&widths)
+            .column_spacing(COLUMN_SPACING);
+        Widget::render(table, table_rect, buf);
+
+        let footer = if state.scroll_offset < max_scroll_offset {
+            "j/k: scroll, ESC: close (more below)"
+        } else {
+            "j/k: scroll, ESC: close"
+        };
+        buf.set_string(
+            help_rect.x + (help_rect.width - footer.len() as u16) / 2,
+            help_rect.y + help_rect.height - 1,
+            footer,
+            fill_style.add_modifier(Modifier::BOLD),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolling_stays_within_the_keybindings() {
+        let mut state = HelpModalState::default();
+        state.scroll_up(1);
+        assert_eq!(state.scroll_offset, 0);
+        state.scroll_down(HELP_PAGE_SIZE);
+        assert_eq!(state.scroll_offset, HELP_PAGE_SIZE);
+        state.scroll_down(1000);
+        assert_eq!(state.scroll_offset, KEYBINDINGS.len() - 1);
+    }
+
+    #[test]
+    fn render_narrows_down_the_scroll_offset() {
+        // 80% of 20 lines leaves room for the table header and 10 keybindings
+        let area = Rect::new(0, 0, 80, 20);
+        let mut buf = Buffer::empty(area);
+        let mut state = HelpModalState {
+            scroll_offset: KEYBINDINGS.len() - 1,
+        };
+        HelpModal.render(area, &mut buf, &mut state);
+        assert_eq!(state.scroll_offset, KEYBINDINGS.len() - 10);
+    }
+}
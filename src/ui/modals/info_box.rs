@@ -0,0 +1,85 @@
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::ui::{format::truncate_middle, grid::draw_filled_rect};
+
+// a single line that goes away on its own, so smaller than the other modals
+const INFO_BOX_HEIGHT: u16 = 4;
+
+pub struct InfoBox<'a> {
+    message: &'a str,
+}
+
+impl<'a> InfoBox<'a> {
+    pub fn new(message: &'a str) -> Self {
+        Self { message }
+    }
+}
+
+impl<'a> Widget for InfoBox<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = (self.message.chars().count() as u16 + 6).min(area.width - 2);
+        let height = INFO_BOX_HEIGHT.min(area.height - 1);
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2) - height / 2;
+
+        let info_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default()
+            .bg(Color::Black)
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD);
+        let text = truncate_middle(self.message, info_rect.width - 4);
+        let text_start_position = ((info_rect.width - text.chars().count() as u16) as f64 / 2.0)
+            .ceil() as u16
+            + info_rect.x;
+
+        draw_filled_rect(buf, fill_style, &info_rect);
+        buf.set_string(
+            text_start_position,
+            info_rect.y + info_rect.height / 2,
+            text,
+            fill_style,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered_text(info_box: InfoBox, width: u16) -> String {
+        let area = Rect::new(0, 0, width, 20);
+        let mut buf = Buffer::empty(area);
+        info_box.render(area, &mut buf);
+        buf.content
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn shows_given_message() {
+        let message = "Current folder: 5 items (3 files, 2 subdirectories)";
+        assert!(rendered_text(InfoBox::new(message), 100).contains(message));
+    }
+
+    #[test]
+    fn long_message_is_truncated_on_narrow_screens() {
+        let message = "Current folder: 12345 items (12000 files, 345 subdirectories)";
+        let text = rendered_text(InfoBox::new(message), 50);
+        assert!(!text.contains(message));
+        assert!(text.contains("Current folder"));
+        assert!(text.contains("subdirectories)"));
+    }
+}
@@ -0,0 +1,75 @@
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::ui::{grid::draw_filled_rect, input_widget::TextInput};
+
+const JUMP_TO_PATH_BOX_WIDTH: u16 = 100;
+// title, input, controls and a margin between each of them
+const JUMP_TO_PATH_BOX_HEIGHT: u16 = 6;
+
+pub struct JumpToPathBox<'a> {
+    input: &'a TextInput,
+}
+
+impl<'a> JumpToPathBox<'a> {
+    pub fn new(input: &'a TextInput) -> Self {
+        Self { input }
+    }
+}
+
+impl<'a> Widget for JumpToPathBox<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = JUMP_TO_PATH_BOX_WIDTH.min(area.width - 2);
+        let height = JUMP_TO_PATH_BOX_HEIGHT.min(area.height - 1);
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2).saturating_sub(height / 2);
+        let jump_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default().bg(Color::Black).fg(Color::White);
+        let text_max_length = jump_rect.width - 4;
+        draw_filled_rect(buf, fill_style, &jump_rect);
+
+        let title = "Go to path";
+        buf.set_string(
+            jump_rect.x + (jump_rect.width - title.len() as u16) / 2,
+            jump_rect.y + 1,
+            title,
+            fill_style.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        );
+        self.input.render(
+            Rect {
+                x: jump_rect.x + 2,
+                y: jump_rect.y + 3,
+                width: text_max_length,
+                height: 1,
+            },
+            buf,
+        );
+
+        let controls_text = [
+            "<TAB> - complete folder, <ENTER> - go there, <ESC> - cancel",
+            "<TAB>/<ENTER>/<ESC>",
+        ];
+        for line in controls_text.iter() {
+            if text_max_length >= line.len() as u16 {
+                buf.set_string(
+                    jump_rect.x + (jump_rect.width - line.len() as u16) / 2,
+                    jump_rect.y + jump_rect.height - 1,
+                    line,
+                    fill_style.add_modifier(Modifier::BOLD),
+                );
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::ui::{
+    format::{DisplaySize, truncate_middle},
+    grid::draw_filled_rect,
+};
+
+pub struct LargestFilesBox<'a> {
+    largest_files: &'a [(PathBuf, u128)],
+    scroll_offset: usize, // index of the first file shown
+}
+
+impl<'a> LargestFilesBox<'a> {
+    pub fn new(largest_files: &'a [(PathBuf, u128)]) -> Self {
+        Self {
+            largest_files,
+            scroll_offset: 0,
+        }
+    }
+    pub fn scroll_offset(mut self, scroll_offset: usize) -> Self {
+        self.scroll_offset = scroll_offset;
+        self
+    }
+    pub fn max_scroll_offset(file_count: usize, area: Rect) -> usize {
+        // so that the last file is on the last line rather than past it
+        file_count.saturating_sub(visible_lines(file_count, area))
+    }
+}
+
+fn box_height(file_count: usize, area: Rect) -> u16 {
+    // title, list (or a line saying it's empty), controls and some margins
+    (file_count.max(1) as u16 + 6).min(area.height - 1)
+}
+
+fn visible_lines(file_count: usize, area: Rect) -> usize {
+    (box_height(file_count, area) - 6) as usize
+}
+
+impl<'a> Widget for LargestFilesBox<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let file_count = self.largest_files.len();
+        let height = box_height(file_count, area);
+        let max_lines = visible_lines(file_count, area);
+        let scroll_offset = self
+            .scroll_offset
+            .min(LargestFilesBox::max_scroll_offset(file_count, area));
+        let width = if area.width > 150 {
+            150
+        } else if area.width >= 50 {
+            area.width / 2
+        } else {
+            unreachable!("app should not be rendered if window is so small")
+        };
+
+        // position self in the middle of the rect
+        let x = ((area.x + area.width) / 2) - width / 2;
+        let y = ((area.y + area.height) / 2).saturating_sub(height / 2);
+
+        let message_rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let fill_style = Style::default()
+            .bg(Color::Black)
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let text_max_length = message_rect.width - 4;
+        draw_filled_rect(buf, fill_style, &message_rect);
+
+        let title = "Largest files";
+        buf.set_string(
+            message_rect.x + (message_rect.width - title.len() as u16) / 2,
+            message_rect.y + 1,
+            title,
+            fill_style,
+        );
+        if self.largest_files.is_empty() {
+            let empty_line = "No files were scanned";
+            buf.set_string(
+                message_rect.x + 2,
+                message_rect.y + 3,
+                truncate_middle(empty_line, text_max_length),
+                fill_style,
+            );
+        }
+        // ranked across the whole list, not only the part that's shown
+        let rank_width = file_count.to_string().len();
+        for (index, (path, size)) in self
+            .largest_files
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_lines)
+        {
+            let rank_and_size = format!(
+                "{:>rank_width$}. {:>10} ",
+                index + 1,
+                DisplaySize(*size as f64).to_string()
+            );
+            let max_path_length =
+                text_max_length.saturating_sub(rank_and_size.chars().count() as u16);
+            let path = path.to_string_lossy();
+            #[cfg(test)]
+            let path = str::replace(&path, "\\", "/");
+            let line = format!(
+                "{}{}",
+                rank_and_size,
+                truncate_middle(&path, max_path_length)
+            );
+            buf.set_string(
+                message_rect.x + 2,
+                message_rect.y + 3 + (index - scroll_offset) as u16,
+                line,
+                fill_style,
+            );
+        }
+
+        let controls_text = [
+            "(j/k to scroll, <ESC> to dismiss)",
+            "(Press <ESC> to dismiss)",
+            "(<ESC> to dismiss)",
+        ];
+        for line in controls_text.iter() {
+            if text_max_length >= line.len() as u16 {
+                buf.set_string(
+                    message_rect.x + (message_rect.width - line.len() as u16) / 2,
+                    message_rect.y + message_rect.height - 1,
+                    line,
+                    fill_style,
+                );
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered_text(largest_files: &[(PathBuf, u128)], scroll_offset: usize) -> String {
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        LargestFilesBox::new(largest_files)
+            .scroll_offset(scroll_offset)
+            .render(area, &mut buf);
+        buf.content
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn scrolls_through_ranked_files() {
+        let largest_files: Vec<(PathBuf, u128)> = (0..30)
+            .map(|index| (PathBuf::from(format!("file{:02}", index)), 30 - index))
+            .collect();
+        let area = Rect::new(0, 0, 100, 20);
+        // 19 lines high, 6 of which aren't files
+        assert_eq!(LargestFilesBox::max_scroll_offset(30, area), 17);
+        assert_eq!(LargestFilesBox::max_scroll_offset(5, area), 0);
+
+        let text = rendered_text(&largest_files, 0);
+        assert!(text.contains(" 1.         30 file00"));
+        assert!(text.contains("13.         18 file12"));
+        assert!(!text.contains("file13"));
+
+        // past the end, the last file stays on the last line
+        let text = rendered_text(&largest_files, 100);
+        assert!(!text.contains("file16"));
+        assert!(text.contains("18.         13 file17"));
+        assert!(text.contains("30.          1 file29"));
+    }
+
+    #[test]
+    fn nothing_scanned() {
+        assert!(rendered_text(&[], 0).contains("No files were scanned"));
+    }
+}
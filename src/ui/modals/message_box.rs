@@ -30,7 +30,12 @@ fn truncated_file_name_line(file_to_delete: &FileToDelete, max_len: u16) -> Stri
     }
 }
 
-fn render_deletion_prompt(buf: &mut Buffer, message_rect: &Rect, file_to_delete: &FileToDelete) {
+fn render_deletion_prompt(
+    buf: &mut Buffer,
+    message_rect: &Rect,
+    file_to_delete: &FileToDelete,
+    auto_confirm_secs: Option<u64>,
+) {
     let max_text_len = message_rect.width - 4;
     let file_name_line = truncated_file_name_line(file_to_delete, max_text_len);
     let text_style = Style::default()
@@ -62,7 +67,21 @@ fn render_deletion_prompt(buf: &mut Buffer, message_rect: &Rect, file_to_delete:
             }
         }
     };
-    let y_n_line = "(y/n)";
+    let y_n_line = match auto_confirm_secs {
+        Some(seconds) => {
+            let unit = if seconds == 1 { "second" } else { "seconds" };
+            let full_line = format!("Deleting in {} {}... (press 'n' to cancel)", seconds, unit);
+            let short_line = format!("Deleting in {}s (y/n)", seconds);
+            if max_text_len >= full_line.len() as u16 {
+                full_line
+            } else if max_text_len >= short_line.len() as u16 {
+                short_line
+            } else {
+                String::from("(y/n)")
+            }
+        }
+        None => String::from("(y/n)"),
+    };
     let question_line_start_position =
         ((message_rect.width - question_line.len() as u16) as f64 / 2.0).ceil() as u16
             + message_rect.x;
@@ -126,6 +145,7 @@ fn render_deletion_in_progress(
 pub struct MessageBox<'a> {
     file_to_delete: &'a FileToDelete,
     deletion_in_progress: bool,
+    auto_confirm_secs: Option<u64>,
 }
 
 impl<'a> MessageBox<'a> {
@@ -133,8 +153,13 @@ impl<'a> MessageBox<'a> {
         Self {
             file_to_delete,
             deletion_in_progress,
+            auto_confirm_secs: None,
         }
     }
+    pub fn auto_confirm_secs(mut self, auto_confirm_secs: Option<u64>) -> Self {
+        self.auto_confirm_secs = auto_confirm_secs;
+        self
+    }
 }
 
 impl<'a> Widget for MessageBox<'a> {
@@ -166,7 +191,12 @@ impl<'a> Widget for MessageBox<'a> {
         if self.deletion_in_progress {
             render_deletion_in_progress(buf, &message_rect, &self.file_to_delete);
         } else {
-            render_deletion_prompt(buf, &message_rect, &self.file_to_delete);
+            render_deletion_prompt(
+                buf,
+                &message_rect,
+                self.file_to_delete,
+                self.auto_confirm_secs,
+            );
         }
     }
 }
@@ -1,9 +1,25 @@
 pub use confirm_box::*;
+pub use duplicates_box::*;
 pub use error_box::*;
+pub use error_list_box::*;
+pub use file_details_box::*;
+pub use help_modal::*;
+pub use info_box::*;
+pub use jump_to_path_box::*;
+pub use largest_files_box::*;
 pub use message_box::*;
+pub use most_accessed_box::*;
 pub use warning_box::*;
 
 mod confirm_box;
+mod duplicates_box;
 mod error_box;
+mod error_list_box;
+mod file_details_box;
+mod help_modal;
+mod info_box;
+mod jump_to_path_box;
+mod largest_files_box;
 mod message_box;
+mod most_accessed_box;
 mod warning_box;
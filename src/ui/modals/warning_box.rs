@@ -7,15 +7,50 @@ use tui::{
 
 use crate::ui::{format::truncate_end, grid::draw_filled_rect};
 
-pub struct WarningBox {}
+const DELETION_WHILE_SCANNING: [&str; 4] = [
+    "Sorry, deletion is only allowed once the scanning has completed",
+    "Deletion is not allowed while scanning",
+    "Can't delete while scanning",
+    "Can't delete now",
+];
 
-impl<'a> WarningBox {
+const DELETE_CONFIRMATION_DISABLED: [&str; 4] = [
+    "WARNING: Delete confirmation is disabled. Files will be permanently deleted immediately on Backspace.",
+    "WARNING: Delete confirmation is disabled, Backspace deletes right away",
+    "WARNING: Delete confirmation is disabled",
+    "No delete confirmation",
+];
+
+const SCAN_TIMED_OUT: [&str; 4] = [
+    "The scan timed out before it was done, what you see are partial results",
+    "The scan timed out, showing partial results",
+    "Scan timed out, partial results",
+    "Scan timed out",
+];
+
+pub struct WarningBox {
+    possible_warning_texts: &'static [&'static str],
+}
+
+impl WarningBox {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            possible_warning_texts: &DELETION_WHILE_SCANNING,
+        }
+    }
+    pub fn delete_confirmation_disabled() -> Self {
+        Self {
+            possible_warning_texts: &DELETE_CONFIRMATION_DISABLED,
+        }
+    }
+    pub fn scan_timed_out() -> Self {
+        Self {
+            possible_warning_texts: &SCAN_TIMED_OUT,
+        }
     }
 }
 
-impl<'a> Widget for WarningBox {
+impl Widget for WarningBox {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let (width, height) = if area.width > 150 {
             (150, 10)
@@ -42,12 +77,7 @@ impl<'a> Widget for WarningBox {
         let text_max_length = warning_rect.width - 4;
         let mut warning_text_start_position: u16 = 0;
 
-        let possible_warning_texts = [
-            "Sorry, deletion is only allowed once the scanning has completed",
-            "Deletion is not allowed while scanning",
-            "Can't delete while scanning",
-            "Can't delete now",
-        ];
+        let possible_warning_texts = self.possible_warning_texts;
         // set default value of the warning_text
         // to the longest one from possible_warning_texts array
         let mut warning_text = String::from(possible_warning_texts[0]);
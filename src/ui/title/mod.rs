@@ -1,5 +1,7 @@
+pub use spinner_style::*;
 pub use title_line::*;
 pub use title_telescope::*;
 
+mod spinner_style;
 mod title_line;
 mod title_telescope;
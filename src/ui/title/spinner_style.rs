@@ -0,0 +1,62 @@
+use std::str::FromStr;
+
+// what moves at the start of the title line while scanning, to show we're not stuck
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum SpinnerStyle {
+    Highlight, // no spinner, a few bold characters running along the title instead
+    #[default]
+    Classic,
+    Braille,
+    Dots,
+}
+
+impl SpinnerStyle {
+    fn frames(self) -> &'static [char] {
+        match self {
+            SpinnerStyle::Highlight => &[],
+            SpinnerStyle::Classic => &['-', '\\', '|', '/'],
+            SpinnerStyle::Braille => &['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'],
+            SpinnerStyle::Dots => &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'],
+        }
+    }
+    pub fn frame(self, loading_indicator: u64) -> Option<char> {
+        // the indicator goes up by 3 on every tick (see UiEffects), and so
+        // we move on by one frame
+        let frames = self.frames();
+        if frames.is_empty() {
+            return None;
+        }
+        Some(frames[(loading_indicator / 3) as usize % frames.len()])
+    }
+}
+
+impl FromStr for SpinnerStyle {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "highlight" => Ok(SpinnerStyle::Highlight),
+            "classic" => Ok(SpinnerStyle::Classic),
+            "braille" => Ok(SpinnerStyle::Braille),
+            "dots" => Ok(SpinnerStyle::Dots),
+            _ => Err(format!(
+                "unknown spinner '{}', expected one of: highlight, classic, braille, dots",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_move_on_every_tick() {
+        let frames: String = (0..6)
+            .filter_map(|tick| SpinnerStyle::Classic.frame(tick * 3))
+            .collect();
+        assert_eq!(frames, "-\\|/-\\");
+        assert_eq!(SpinnerStyle::Braille.frame(3), Some('⣽'));
+        assert_eq!(SpinnerStyle::Highlight.frame(3), None);
+    }
+}
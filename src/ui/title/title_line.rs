@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 use tui::{
     buffer::Buffer,
@@ -7,10 +10,13 @@ use tui::{
     widgets::Widget,
 };
 
-use crate::ui::{
-    FolderInfo,
-    format::DisplaySize,
-    title::{CellSizeOpt, TitleTelescope},
+use crate::{
+    state::{ScanProgress, tiles::SortOrder},
+    ui::{
+        FolderInfo,
+        format::{DisplayCount, DisplaySize, format_duration},
+        title::{CellSizeOpt, SpinnerStyle, TitleTelescope},
+    },
 };
 
 #[cfg(not(target_os = "windows"))]
@@ -18,16 +24,38 @@ use crate::os::unix::is_user_admin;
 #[cfg(target_os = "windows")]
 use crate::os::windows::is_user_admin;
 
+// how many of the paths we failed to read fit next to their count
+const MAX_ERROR_PATHS_SHOWN: usize = 3;
+
+// with less than this left, the estimate is too rough to count down
+const ALMOST_DONE: Duration = Duration::from_secs(5);
+
 pub struct TitleLine<'a> {
     base_path_info: FolderInfo<'a>,
     current_path_info: FolderInfo<'a>,
     space_freed: u128,
     show_loading: bool,
     progress_indicator: u64,
+    spinner_style: SpinnerStyle,
     read_errors: Option<u64>,
+    error_paths: Option<&'a [(PathBuf, String)]>, // the first few are shown next to the count
     flash_space: bool,
     path_error: bool,
-    zoom_level: Option<usize>,
+    path_highlight: bool,
+    zoom_level: Option<(usize, u8)>, // current and maximum
+    tile_counts: Option<(usize, usize)>,
+    estimated_hidden_tiles: Option<u64>,
+    hidden_files_excluded: bool,
+    sort_order: SortOrder,
+    custom_right_section: Option<String>,
+    restored: bool,
+    scan_timed_out: bool,
+    remote: Option<(&'a str, &'a str)>, // user@host and how we logged in
+    scan_progress: Option<ScanProgress>,
+    scan_rate: f64,                               // files per second
+    scan_eta: Option<Duration>,                   // how long the rest of the scan might take
+    diff: Option<(SystemTime, SystemTime, i128)>, // when the scans were made and the net change
+    disk_space: Option<(u64, u64)>,               // used and total
 }
 
 impl<'a> TitleLine<'a> {
@@ -41,11 +69,27 @@ impl<'a> TitleLine<'a> {
             current_path_info,
             space_freed,
             progress_indicator: 0,
+            spinner_style: SpinnerStyle::default(),
             read_errors: None,
+            error_paths: None,
             show_loading: false,
             flash_space: false,
             path_error: false,
+            path_highlight: false,
             zoom_level: None,
+            tile_counts: None,
+            estimated_hidden_tiles: None,
+            hidden_files_excluded: false,
+            sort_order: SortOrder::default(),
+            custom_right_section: None,
+            restored: false,
+            scan_timed_out: false,
+            remote: None,
+            scan_progress: None,
+            scan_rate: 0.0,
+            scan_eta: None,
+            diff: None,
+            disk_space: None,
         }
     }
     pub fn show_loading(mut self) -> Self {
@@ -60,6 +104,10 @@ impl<'a> TitleLine<'a> {
         self.path_error = path_error;
         self
     }
+    pub fn path_highlight(mut self, path_highlight: bool) -> Self {
+        self.path_highlight = path_highlight;
+        self
+    }
     pub fn progress_indicator(mut self, progress_indicator: u64) -> Self {
         self.progress_indicator = progress_indicator;
         self
@@ -70,12 +118,87 @@ impl<'a> TitleLine<'a> {
         }
         self
     }
-    pub fn zoom_level(mut self, zoom_level: usize) -> Self {
+    pub fn with_read_error_detail(mut self, errors: &'a [(PathBuf, String)]) -> Self {
+        if !errors.is_empty() {
+            self.error_paths = Some(errors);
+        }
+        self
+    }
+    pub fn zoom_level(mut self, zoom_level: usize, max_zoom_level: u8) -> Self {
         if zoom_level > 0 {
-            self.zoom_level = Some(zoom_level);
+            self.zoom_level = Some((zoom_level, max_zoom_level));
         }
         self
     }
+    pub fn restored(mut self, restored: bool) -> Self {
+        self.restored = restored;
+        self
+    }
+    pub fn scan_timed_out(mut self, scan_timed_out: bool) -> Self {
+        self.scan_timed_out = scan_timed_out;
+        self
+    }
+    pub fn remote(mut self, remote: Option<(&'a str, &'a str)>) -> Self {
+        self.remote = remote;
+        self
+    }
+    pub fn scan_progress(mut self, scan_progress: &ScanProgress) -> Self {
+        self.scan_progress = Some(*scan_progress);
+        self
+    }
+    pub fn scan_rate(mut self, scan_rate: f64) -> Self {
+        // only shown while loading, and once there's something to show
+        self.scan_rate = scan_rate;
+        self
+    }
+    pub fn scan_eta(mut self, scan_eta: Option<Duration>) -> Self {
+        // a rough guess, shown while loading
+        self.scan_eta = scan_eta;
+        self
+    }
+    pub fn spinner_style(mut self, spinner_style: SpinnerStyle) -> Self {
+        self.spinner_style = spinner_style;
+        self
+    }
+    pub fn tile_counts(mut self, visible: usize, hidden: usize) -> Self {
+        self.tile_counts = Some((visible, hidden));
+        self
+    }
+    pub fn with_custom_right_section(mut self, text: impl Into<String>) -> Self {
+        let text = text.into();
+        self.custom_right_section = if text.is_empty() { None } else { Some(text) };
+        self
+    }
+    pub fn hidden_files_excluded(mut self, hidden_files_excluded: bool) -> Self {
+        self.hidden_files_excluded = hidden_files_excluded;
+        self
+    }
+    pub fn sort_order(mut self, sort_order: SortOrder) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+    pub fn disk_space(mut self, disk_space: Option<(u64, u64)>) -> Self {
+        // of the whole disk the scanned folder is on, to put its size in proportion
+        self.disk_space = disk_space;
+        self
+    }
+    // only the tests compare two scans so far, see Display::render_diff
+    #[cfg(test)]
+    pub fn diff(
+        mut self,
+        before_scanned_at: SystemTime,
+        after_scanned_at: SystemTime,
+        net_size_change: i128,
+    ) -> Self {
+        // comparing two scans rather than showing one, the total is their difference
+        self.diff = Some((before_scanned_at, after_scanned_at, net_size_change));
+        self
+    }
+    pub fn estimated_hidden_tiles(mut self, estimated_hidden_tiles: u64) -> Self {
+        // while scanning, we only have a rough idea of how many tiles won't fit
+        self.estimated_hidden_tiles = Some(estimated_hidden_tiles);
+        self
+    }
 }
 
 impl<'a> Widget for TitleLine<'a> {
@@ -120,7 +243,26 @@ impl<'a> Widget for TitleLine<'a> {
             default_style = default_style.add_modifier(Modifier::BOLD);
         };
         let mut title_telescope = TitleTelescope::new(default_style);
-        if self.show_loading {
+        if let (true, Some(scan_progress)) = (self.show_loading, self.scan_progress) {
+            let files = DisplayCount(scan_progress.files_scanned);
+            let directories = DisplayCount(scan_progress.directories_entered);
+            let bytes = DisplaySize(scan_progress.bytes_scanned as f64);
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(format!(
+                    "Scanning: {} files / {} directories / {}",
+                    files, directories, bytes
+                )),
+                CellSizeOpt::new(format!("Scanning: {} files / {}", files, bytes)),
+                CellSizeOpt::new(format!("Scanning: {}", bytes)),
+                CellSizeOpt::new(format!("{}", bytes)),
+            ]);
+            if scan_progress.hardlinked_files > 0 {
+                title_telescope.append_to_left_side(vec![CellSizeOpt::new(format!(
+                    " ({} hard-linked files)",
+                    DisplayCount(scan_progress.hardlinked_files)
+                ))]);
+            }
+        } else if self.show_loading {
             title_telescope.append_to_left_side(vec![
                 CellSizeOpt::new(format!(
                     "Scanning: {} ({} files)",
@@ -129,6 +271,33 @@ impl<'a> Widget for TitleLine<'a> {
                 CellSizeOpt::new(format!("Scanning: {}", total_size)),
                 CellSizeOpt::new(format!("{}", total_size)),
             ]);
+        } else if let Some((before_scanned_at, after_scanned_at, net_size_change)) = self.diff {
+            let scan_date =
+                |time: SystemTime| chrono::DateTime::<chrono::Local>::from(time).format("%Y-%m-%d");
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(format!(
+                    "Δ {} vs {}",
+                    scan_date(before_scanned_at),
+                    scan_date(after_scanned_at)
+                )),
+                CellSizeOpt::new(String::from("Δ")),
+            ]);
+            // growing is what we're here to find, so that's the one in red
+            let (sign, color) = if net_size_change > 0 {
+                ("+", Color::Red)
+            } else if net_size_change < 0 {
+                ("-", Color::Green)
+            } else {
+                ("", Color::Yellow)
+            };
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(format!(
+                    " ({}{})",
+                    sign,
+                    DisplaySize(net_size_change.unsigned_abs() as f64)
+                ))
+                .style(default_style.fg(color)),
+            ]);
         } else {
             title_telescope.append_to_left_side(vec![
                 CellSizeOpt::new(format!(
@@ -140,14 +309,93 @@ impl<'a> Widget for TitleLine<'a> {
                 CellSizeOpt::new(format!("{}", total_size)),
             ]);
         };
-        if let Some(read_errors) = self.read_errors {
+        if let (false, None, Some((used, total))) = (self.show_loading, self.diff, self.disk_space)
+            && total > 0
+        {
+            let percentage = used as f64 / total as f64 * 100.0;
             title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(format!(
+                    " (Disk: {} / {} ({:.0}%))",
+                    DisplaySize(used as f64),
+                    DisplaySize(total as f64),
+                    percentage
+                ))
+                .style(default_style.fg(disk_usage_color(percentage))),
+                CellSizeOpt::new(format!(" (Disk: {:.0}%)", percentage))
+                    .style(default_style.fg(disk_usage_color(percentage))),
+            ]);
+        }
+        if self.show_loading && self.scan_rate >= 1.0 {
+            let scan_rate = DisplayCount(self.scan_rate.round() as u64);
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(format!(" ({} files/sec)", scan_rate)),
+                CellSizeOpt::new(format!(" ({}/s)", scan_rate)),
+            ]);
+        }
+        if let (true, Some(scan_eta)) = (self.show_loading, self.scan_eta) {
+            let eta = if scan_eta < ALMOST_DONE {
+                String::from(" (Almost done…)")
+            } else {
+                format!(" (ETA: ~{})", format_duration(scan_eta))
+            };
+            title_telescope.append_to_left_side(vec![CellSizeOpt::new(eta)]);
+        }
+        if self.restored {
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(" (restored)".to_string()).style(default_style.fg(Color::Green)),
+            ]);
+        }
+        if self.scan_timed_out {
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(" (scan timed out, partial results)".to_string())
+                    .style(default_style.fg(Color::Yellow)),
+                CellSizeOpt::new(" (timed out)".to_string()).style(default_style.fg(Color::Yellow)),
+            ]);
+        }
+        if let Some((user_and_host, authenticated_with)) = self.remote {
+            title_telescope.append_to_left_side(vec![
+                CellSizeOpt::new(format!(
+                    " (Remote: {}, logged in with {})",
+                    user_and_host, authenticated_with
+                ))
+                .style(default_style.fg(Color::Cyan)),
+                CellSizeOpt::new(format!(" (Remote: {})", user_and_host))
+                    .style(default_style.fg(Color::Cyan)),
+                CellSizeOpt::new(" (remote)".to_string()).style(default_style.fg(Color::Cyan)),
+            ]);
+        }
+        if let Some(read_errors) = self.read_errors {
+            // yellow rather than red, since <E> shows what they are
+            let mut read_error_options = vec![];
+            if let Some(error_paths) = self.error_paths {
+                let mut shown_paths: Vec<String> = error_paths
+                    .iter()
+                    .take(MAX_ERROR_PATHS_SHOWN)
+                    .map(|(path, _)| {
+                        let path = path.strip_prefix(self.base_path_info.path).unwrap_or(path);
+                        path.to_string_lossy().into_owned()
+                    })
+                    .collect();
+                if read_errors > shown_paths.len() as u64 {
+                    shown_paths.push(String::from("..."));
+                }
+                read_error_options.push(
+                    CellSizeOpt::new(format!(
+                        " (failed to read {} files: {})",
+                        read_errors,
+                        shown_paths.join(", ")
+                    ))
+                    .style(default_style.fg(Color::Yellow)),
+                );
+            }
+            read_error_options.extend([
                 CellSizeOpt::new(format!(" (failed to read {} files)", read_errors))
-                    .style(default_style.fg(Color::Red)),
+                    .style(default_style.fg(Color::Yellow)),
                 CellSizeOpt::new(format!(" ({} errors)", read_errors))
-                    .style(default_style.fg(Color::Red)),
-                CellSizeOpt::new(" (errors)".to_string()).style(default_style.fg(Color::Red)),
+                    .style(default_style.fg(Color::Yellow)),
+                CellSizeOpt::new(" (errors)".to_string()).style(default_style.fg(Color::Yellow)),
             ]);
+            title_telescope.append_to_left_side(read_error_options);
         }
         if is_user_admin() {
             title_telescope.append_to_left_side(vec![
@@ -174,22 +422,109 @@ impl<'a> Widget for TitleLine<'a> {
                     .style(default_style.fg(Color::Green)),
             ]);
         }
-        if let Some(zoom_level) = self.zoom_level {
+        if let Some((zoom_level, max_zoom_level)) = self.zoom_level {
             title_telescope.append_to_right_side(vec![
                 CellSizeOpt::new(format!(
-                    " (+{} larger file(s), zoom out to show)",
-                    zoom_level
+                    " (Zoom: {}/{}, +{} larger file(s), zoom out to show)",
+                    zoom_level, max_zoom_level, zoom_level
                 ))
                 .style(default_style.fg(Color::Green)),
-                CellSizeOpt::new(format!(" (+{} larger file(s))", zoom_level))
+                CellSizeOpt::new(format!(
+                    " (Zoom: {}/{}, +{} larger file(s))",
+                    zoom_level, max_zoom_level, zoom_level
+                ))
+                .style(default_style.fg(Color::Green)),
+                CellSizeOpt::new(format!(" (Zoom: {}/{})", zoom_level, max_zoom_level))
+                    .style(default_style.fg(Color::Green)),
+            ]);
+        }
+        if let Some((visible, hidden)) = self.tile_counts
+            && (hidden > 0 || self.zoom_level.is_some())
+        {
+            let total = visible + hidden;
+            title_telescope.append_to_right_side(vec![
+                CellSizeOpt::new(format!(" (showing {}/{} tiles)", visible, total))
+                    .style(default_style.fg(Color::Green)),
+                CellSizeOpt::new(format!(" ({}/{})", visible, total))
+                    .style(default_style.fg(Color::Green)),
+            ]);
+        }
+        if self.hidden_files_excluded {
+            title_telescope.append_to_right_side(vec![
+                CellSizeOpt::new(String::from(" (hidden files excluded)"))
+                    .style(default_style.fg(Color::Green)),
+                CellSizeOpt::new(String::from(" (no hidden)"))
+                    .style(default_style.fg(Color::Green)),
+            ]);
+        }
+        if self.sort_order != SortOrder::BySize {
+            // by size is what's expected of a treemap, so it goes without saying
+            title_telescope.append_to_right_side(vec![
+                CellSizeOpt::new(format!(" (sorted by {})", self.sort_order))
+                    .style(default_style.fg(Color::Green)),
+                CellSizeOpt::new(format!(" (by {})", self.sort_order))
+                    .style(default_style.fg(Color::Green)),
+            ]);
+        }
+        if let Some(estimated_hidden_tiles) = self.estimated_hidden_tiles
+            && estimated_hidden_tiles > 0
+        {
+            title_telescope.append_to_right_side(vec![
+                CellSizeOpt::new(format!(
+                    " (~{} tiles will be hidden)",
+                    estimated_hidden_tiles
+                ))
+                .style(default_style.fg(Color::Green)),
+                CellSizeOpt::new(format!(" (~{} hidden)", estimated_hidden_tiles))
                     .style(default_style.fg(Color::Green)),
             ]);
         }
 
+        let rect = match &self.custom_right_section {
+            Some(text) => render_custom_right_section(text, rect, buf),
+            None => rect,
+        };
         title_telescope
             .loading(self.show_loading, self.progress_indicator)
+            .spinner_style(self.spinner_style)
             .path_error(self.path_error)
+            .path_highlight(self.path_highlight)
             .size_flash(self.flash_space)
             .render(rect, buf);
     }
 }
+
+fn disk_usage_color(percentage: f64) -> Color {
+    if percentage < 70.0 {
+        Color::Green
+    } else if percentage < 90.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+fn render_custom_right_section(text: &str, rect: Rect, buf: &mut Buffer) -> Rect {
+    // returns what's left of the title for everything else
+    let max_len = (rect.width / 3) as usize;
+    let text = if text.chars().count() > max_len {
+        let truncated: String = text.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    } else {
+        text.to_owned()
+    };
+    let text_len = text.chars().count() as u16;
+    if text_len == 0 || text_len + 1 >= rect.width {
+        return rect;
+    }
+    buf.set_string(
+        rect.x + rect.width - text_len - 1,
+        rect.y,
+        &text,
+        Style::default().fg(Color::DarkGray),
+    );
+    Rect {
+        width: rect.width - text_len - 1,
+        ..rect
+    }
+}
@@ -6,7 +6,7 @@ use tui::{
     style::{Color, Modifier, Style},
 };
 
-use crate::ui::format::truncate_middle;
+use crate::ui::{format::truncate_middle, title::SpinnerStyle};
 
 fn get_index_or_last(vec: &[CellSizeOpt], index: usize) -> &CellSizeOpt {
     match vec.get(index) {
@@ -41,7 +41,9 @@ pub struct TitleTelescope {
     right_side: Vec<CollapsingCell>,
     loading: bool,
     loading_indicator: u64,
+    spinner_style: SpinnerStyle,
     path_error: bool,
+    path_highlight: bool,
     size_flash: bool,
 }
 
@@ -53,7 +55,9 @@ impl TitleTelescope {
             right_side: vec![],
             loading: false,
             loading_indicator: 0,
+            spinner_style: SpinnerStyle::default(),
             path_error: false,
+            path_highlight: false,
             size_flash: false,
         }
     }
@@ -68,10 +72,18 @@ impl TitleTelescope {
         self.loading_indicator = loading_indicator;
         self
     }
+    pub fn spinner_style(mut self, spinner_style: SpinnerStyle) -> Self {
+        self.spinner_style = spinner_style;
+        self
+    }
     pub fn path_error(mut self, should_show_path_error: bool) -> Self {
         self.path_error = should_show_path_error;
         self
     }
+    pub fn path_highlight(mut self, should_highlight_path: bool) -> Self {
+        self.path_highlight = should_highlight_path;
+        self
+    }
     pub fn size_flash(mut self, should_flash_size: bool) -> Self {
         self.size_flash = should_flash_size;
         self
@@ -119,7 +131,12 @@ impl TitleTelescope {
     }
     fn style_of_right_side(&self, style: Option<Style>) -> Style {
         let style_if_path_error = Style::default().bg(Color::Red).fg(Color::White);
-        self.condition_style_or_default(self.path_error, style_if_path_error, style)
+        let style_if_path_highlight = Style::default().bg(Color::White).fg(Color::Black);
+        if self.path_error {
+            self.condition_style_or_default(self.path_error, style_if_path_error, style)
+        } else {
+            self.condition_style_or_default(self.path_highlight, style_if_path_highlight, style)
+        }
     }
     fn condition_style_or_default(
         &self,
@@ -150,17 +167,17 @@ impl TitleTelescope {
         let mut current_position = rect.x + 1;
         for cell_size_opt in &left_side {
             self.render_left_side_cell(cell_size_opt, current_position, rect.y, buf);
-            current_position += cell_size_opt.content.len() as u16;
+            current_position += cell_size_opt.content.chars().count() as u16;
         }
         self.render_pipe(current_position, rect.y, buf);
         current_position += 3;
         for cell_size_opt in &right_side {
             self.render_right_side_cell(cell_size_opt, current_position, rect.y, buf);
-            current_position += cell_size_opt.content.len() as u16;
+            current_position += cell_size_opt.content.chars().count() as u16;
         }
         if self.loading {
             let text_length = current_position - (rect.x + 1);
-            self.draw_loading_chars(text_length, rect, buf);
+            self.draw_loading_indicator(text_length, rect, buf);
         }
     }
     fn render_truncated_line_index(&self, index: usize, rect: Rect, buf: &mut Buffer) {
@@ -169,7 +186,7 @@ impl TitleTelescope {
         let mut current_position = rect.x + 1;
         for cell_size_opt in &left_side {
             self.render_left_side_cell(cell_size_opt, current_position, rect.y, buf);
-            current_position += cell_size_opt.content.len() as u16;
+            current_position += cell_size_opt.content.chars().count() as u16;
         }
         self.render_pipe(current_position, rect.y, buf);
         current_position += 3;
@@ -189,7 +206,16 @@ impl TitleTelescope {
         }
         if self.loading {
             let text_length = current_position - (rect.x + 1);
-            self.draw_loading_chars(text_length, rect, buf);
+            self.draw_loading_indicator(text_length, rect, buf);
+        }
+    }
+    fn draw_loading_indicator(&self, text_length: u16, rect: Rect, buf: &mut Buffer) {
+        // the spinner goes in the blank cell before the title
+        match self.spinner_style.frame(self.loading_indicator) {
+            Some(frame) => {
+                buf.set_string(rect.x, rect.y, frame.to_string(), self.default_style);
+            }
+            None => self.draw_loading_chars(text_length, rect, buf),
         }
     }
     fn draw_loading_chars(&self, text_length: u16, rect: Rect, buf: &mut Buffer) {
@@ -213,10 +239,10 @@ impl TitleTelescope {
         let line_candidate_right = self.right_side_candidate(i);
         let left_candidate_len = line_candidate_left
             .iter()
-            .fold(0, |len, c| len + c.content.len());
+            .fold(0, |len, c| len + c.content.chars().count());
         let right_candidate_len = line_candidate_right
             .iter()
-            .fold(0, |len, c| len + c.content.len());
+            .fold(0, |len, c| len + c.content.chars().count());
         let pipe_separator_len = 3;
         left_candidate_len + right_candidate_len + pipe_separator_len
     }
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use anyhow::Context;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/xubaiwang/diskonaut/releases/latest";
+const VERSION_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn parse_version(version: &str) -> Option<(Vec<u64>, bool)> {
+    // the numbers and whether it's a pre-release (eg. 1.0.0-beta.1), build metadata
+    // (eg. 1.0.0+20260101) doesn't count for anything
+    let version = version.trim_start_matches('v');
+    let version = version
+        .split_once('+')
+        .map_or(version, |(version, _)| version);
+    let (numbers, pre_release) = match version.split_once('-') {
+        Some((numbers, _)) => (numbers, true),
+        None => (version, false),
+    };
+    let numbers = numbers
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((numbers, pre_release))
+}
+
+fn is_newer_version(latest_version: &str, current_version: &str) -> bool {
+    match (
+        parse_version(latest_version),
+        parse_version(current_version),
+    ) {
+        // pre-releases are never offered as an update
+        (Some((_, true)), _) => false,
+        // a release is newer than the pre-releases that came before it
+        (Some((latest_version, false)), Some((current_version, current_is_pre_release))) => {
+            latest_version > current_version
+                || (latest_version == current_version && current_is_pre_release)
+        }
+        _ => false,
+    }
+}
+
+pub fn check_latest_version() -> anyhow::Result<()> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let agent = ureq::AgentBuilder::new()
+        .timeout(VERSION_CHECK_TIMEOUT)
+        .user_agent(&format!("diskonaut/{}", current_version))
+        .build();
+    let latest_release: ureq::SerdeValue = agent
+        .get(LATEST_RELEASE_URL)
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .context("Could not reach GitHub to check for a new version")?
+        .into_json()
+        .context("Could not read the latest release from GitHub")?;
+    let latest_version = latest_release
+        .get("tag_name")
+        .and_then(|tag_name| tag_name.as_str())
+        .context("Could not find the version of the latest release")?;
+    if is_newer_version(latest_version, current_version) {
+        let download_url = latest_release
+            .get("html_url")
+            .and_then(|html_url| html_url.as_str())
+            .unwrap_or("https://github.com/xubaiwang/diskonaut/releases/latest");
+        println!(
+            "New version available: v{} (you have v{}). Download: {}",
+            latest_version.trim_start_matches('v'),
+            current_version,
+            download_url
+        );
+    } else {
+        println!("You are up to date (v{})", current_version);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_versions() {
+        assert!(is_newer_version("v0.12.0", "0.11.0"));
+        assert!(is_newer_version("0.11.1", "0.11.0"));
+        assert!(is_newer_version("v1.0.0", "0.11.0"));
+        assert!(!is_newer_version("v0.11.0", "0.11.0"));
+        assert!(!is_newer_version("v0.9.0", "0.11.0"));
+        assert!(!is_newer_version("nightly", "0.11.0"));
+    }
+
+    #[test]
+    fn compare_pre_release_versions() {
+        assert!(!is_newer_version("v0.12.0-beta.1", "0.11.0"));
+        assert!(!is_newer_version("v0.12.0-rc.1+build.5", "0.11.0"));
+        assert!(is_newer_version("v0.12.0", "0.12.0-beta.1"));
+        assert!(is_newer_version("v0.12.1", "0.12.0-beta.1"));
+        assert!(!is_newer_version("v0.11.0", "0.12.0-beta.1"));
+        assert!(!is_newer_version("v0.11.0+build.5", "0.11.0"));
+    }
+}